@@ -0,0 +1,42 @@
+struct Camera {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+struct Grid {
+    line_color: vec4<f32>,
+    base_color: vec4<f32>,
+    spacing: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> grid: Grid;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+struct FloorOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+};
+
+@vertex
+fn vs_floor(in: VertexInput) -> FloorOut {
+    var out: FloorOut;
+    out.position = camera.view_proj * vec4<f32>(in.position, 1.0);
+    out.world_pos = in.position;
+    return out;
+}
+
+@fragment
+fn fs_grid(in: FloorOut) -> @location(0) vec4<f32> {
+    // Расстояние до ближайшей линии в пикселях, линии толщиной ~1px
+    let coord = in.world_pos.xz / grid.spacing;
+    let dist = abs(fract(coord - 0.5) - 0.5) / fwidth(coord);
+    let line = 1.0 - min(min(dist.x, dist.y), 1.0);
+    return vec4<f32>(mix(grid.base_color.rgb, grid.line_color.rgb, line), 1.0);
+}
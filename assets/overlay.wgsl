@@ -0,0 +1,33 @@
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) local: vec2<f32>,
+    @location(3) radial: f32,
+};
+
+struct VSOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) local: vec2<f32>,
+    @location(2) radial: f32,
+};
+
+@vertex
+fn vs_overlay(in: VertexInput) -> VSOut {
+    var out: VSOut;
+    out.position = vec4<f32>(in.position, 0.0, 1.0);
+    out.color = in.color;
+    out.local = in.local;
+    out.radial = in.radial;
+    return out;
+}
+
+@fragment
+fn fs_overlay(in: VSOut) -> @location(0) vec4<f32> {
+    var color = in.color;
+    // Радиальный градиент: прозрачно у центра, полный цвет к краям
+    if in.radial >= 0.0 {
+        color.a *= smoothstep(in.radial, 1.0, length(in.local));
+    }
+    return color;
+}
@@ -30,6 +30,28 @@ fn vs_main(in: VertexInput) -> VSOut {
     return out;
 }
 
+struct InstanceInput {
+    @location(2) model_0: vec4<f32>,
+    @location(3) model_1: vec4<f32>,
+    @location(4) model_2: vec4<f32>,
+    @location(5) model_3: vec4<f32>,
+    @location(6) color: vec3<f32>,
+};
+
+@vertex
+fn vs_instanced(in: VertexInput, instance: InstanceInput) -> VSOut {
+    let model = mat4x4<f32>(
+        instance.model_0,
+        instance.model_1,
+        instance.model_2,
+        instance.model_3,
+    );
+    var out: VSOut;
+    out.position = camera.view_proj * model * vec4<f32>(in.position, 1.0);
+    out.color = instance.color;
+    return out;
+}
+
 @fragment
 fn fs_main(in: VSOut) -> @location(0) vec4<f32> {
     return vec4<f32>(in.color * artifact.intensity, 1.0);
@@ -0,0 +1,99 @@
+// Демо сетевой игры на двоих по UDP.
+// Запуск (два терминала):
+//   cargo run --features net --bin netdemo -- 127.0.0.1:4000 127.0.0.1:4001
+//   cargo run --features net --bin netdemo -- 127.0.0.1:4001 127.0.0.1:4000
+// Каждый экземпляр шлёт снимок своего игрока и рисует чужого кубом.
+
+use astroforge::engine;
+use astroforge::engine::net::{frame, newer_tick, read_frame, Interpolator};
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::snapshot::WorldSnapshot;
+use astroforge::engine::Engine;
+use astroforge::player::Player;
+use glam::{Mat4, Vec3};
+use std::net::UdpSocket;
+
+/// Как часто отправляем снимок, секунд.
+const SEND_INTERVAL: f32 = 1.0 / 20.0;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        eprintln!("usage: netdemo <bind_addr> <peer_addr>");
+        std::process::exit(2);
+    }
+    let socket = UdpSocket::bind(&args[1]).expect("bind UDP socket");
+    socket.connect(&args[2]).expect("connect to peer");
+    socket.set_nonblocking(true).expect("non-blocking socket");
+
+    let engine = Engine::new("AstroForge netdemo", 1024, 768);
+    let mut player = Player::new();
+    let mut remote = Interpolator::new(player.position, SEND_INTERVAL);
+    let mut remote_seen = false;
+    let mut tick = 0u32;
+    let mut last_remote_tick = 0u32;
+    let mut send_timer = 0.0f32;
+    let mut buf = [0u8; 1500];
+
+    engine.run(move |engine| {
+        let dt = engine.dt;
+
+        player.update(&engine.input, dt);
+        let floor = [engine::physics::Aabb::new(
+            Vec3::new(0.0, -0.5, 0.0),
+            Vec3::new(50.0, 0.5, 50.0),
+        )];
+        let mut objs = [engine::physics::PhysicsObject {
+            body: &mut player.body,
+            collider: player.collider,
+        }];
+        engine::physics::step(&mut objs, &floor, dt);
+        player.position = player.body.position;
+
+        send_timer -= dt;
+        if send_timer <= 0.0 {
+            send_timer = SEND_INTERVAL;
+            tick += 1;
+            let snapshot = WorldSnapshot::capture(tick, [&player.body]);
+            // Пир может быть ещё не запущен — просто пробуем снова позже
+            let _ = socket.send(&frame(&snapshot.to_bytes()));
+        }
+
+        while let Ok(n) = socket.recv(&mut buf) {
+            let Some((payload, _)) = read_frame(&buf[..n]) else {
+                continue;
+            };
+            let Some(snapshot) = WorldSnapshot::from_bytes(payload) else {
+                continue;
+            };
+            // UDP может переставлять пакеты, старые снимки отбрасываем;
+            // перезапущенный пир снова считает тики с единицы
+            if !newer_tick(snapshot.tick, last_remote_tick) {
+                continue;
+            }
+            last_remote_tick = snapshot.tick;
+            if let Some(state) = snapshot.bodies.first() {
+                if !remote_seen {
+                    remote = Interpolator::new(state.position, SEND_INTERVAL);
+                    remote_seen = true;
+                } else {
+                    remote.push(state.position);
+                }
+            }
+        }
+        remote.update(dt);
+
+        let view =
+            Mat4::from_quat(player.rotation).inverse() * Mat4::from_translation(-player.position);
+        let aspect = engine.renderer.size.width as f32 / engine.renderer.size.height as f32;
+        let proj = Mat4::perspective_rh(60f32.to_radians(), aspect, 0.1, 100.0);
+        engine.renderer.update_camera(&(proj * view));
+
+        let mut cubes = Vec::new();
+        if remote_seen {
+            cubes.push(CubeInstance::new(remote.position(), 1.0, [0.2, 0.6, 1.0]));
+        }
+        engine.renderer.render(None, 100, &cubes);
+        engine.input.reset();
+    });
+}
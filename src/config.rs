@@ -0,0 +1,176 @@
+//! Scenario chosen on the command line: which level to start, how hard it
+//! is, and switches that make specific situations easy to test.
+
+use glam::Vec3;
+
+/// Бочки арены, см. `Level::barrels`.
+const ARENA_BARRELS: [Vec3; 4] = [
+    Vec3::new(10.0, 0.4, 6.0),
+    Vec3::new(11.2, 0.4, 6.0),
+    Vec3::new(10.6, 0.4, 7.1),
+    Vec3::new(-12.0, 0.4, -4.0),
+];
+
+/// Scene the game starts in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Level {
+    /// Start by the beacon; the enemy arrives after it is activated.
+    #[default]
+    Beacon,
+    /// Start out on the floor with the technology already unlocked, so the
+    /// enemy arrives straight away.
+    Arena,
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "beacon" => Some(Self::Beacon),
+            "arena" => Some(Self::Arena),
+            _ => None,
+        }
+    }
+
+    pub fn player_start(self) -> Vec3 {
+        match self {
+            Self::Beacon => Vec3::new(0.0, 1.0, 2.0),
+            Self::Arena => Vec3::new(0.0, 1.0, 20.0),
+        }
+    }
+
+    /// Where explosive barrels stand: a cluster close enough to set each
+    /// other off, and one on its own, both off to the side of the start.
+    pub fn barrels(self) -> &'static [Vec3] {
+        match self {
+            Self::Beacon => &[],
+            Self::Arena => &ARENA_BARRELS,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Self::Easy),
+            "normal" => Some(Self::Normal),
+            "hard" => Some(Self::Hard),
+            _ => None,
+        }
+    }
+
+    /// Multiplier on the damage the player takes.
+    pub fn damage_scale(self) -> f32 {
+        match self {
+            Self::Easy => 0.5,
+            Self::Normal => 1.0,
+            Self::Hard => 1.5,
+        }
+    }
+
+    /// Half-angle, in radians, of the cone enemy shots scatter over.
+    pub fn enemy_spread(self) -> f32 {
+        match self {
+            Self::Easy => 0.1,
+            Self::Normal => 0.05,
+            Self::Hard => 0.02,
+        }
+    }
+
+    /// Whether enemies lead a moving player instead of shooting where they
+    /// stand.
+    pub fn predictive_aim(self) -> bool {
+        self == Self::Hard
+    }
+
+    /// Shots in each enemy burst.
+    pub fn enemy_burst(self) -> u32 {
+        match self {
+            Self::Easy => 1,
+            Self::Normal => 2,
+            Self::Hard => 3,
+        }
+    }
+
+    /// Seconds between the starts of enemy bursts.
+    pub fn fire_interval(self) -> f32 {
+        match self {
+            Self::Easy => 3.0,
+            Self::Normal => 2.0,
+            Self::Hard => 1.25,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scenario {
+    pub level: Level,
+    pub difficulty: Difficulty,
+    /// Whether the enemy spawns at all.
+    pub enemies: bool,
+    /// The player starts in god mode and takes no damage.
+    pub god: bool,
+    /// Practice range: stationary targets to shoot instead of the enemy.
+    pub practice: bool,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            level: Level::default(),
+            difficulty: Difficulty::default(),
+            enemies: true,
+            god: false,
+            practice: false,
+        }
+    }
+}
+
+/// Flags that decide how the game starts. Other flags, such as display
+/// options, are read by `main` directly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LaunchConfig {
+    pub scenario: Scenario,
+    pub selftest: bool,
+    /// Render one frame without a window and save it.
+    pub screenshot: bool,
+}
+
+impl LaunchConfig {
+    /// Read `--level beacon|arena`, `--difficulty easy|normal|hard`,
+    /// `--no-enemies`, `--god`, `--practice`, `--selftest` and `--screenshot`
+    /// from `args`.
+    /// Arguments it doesn't know are left for others to read.
+    pub fn from_args<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            match arg {
+                "--level" => {
+                    let name = args.next().ok_or("--level takes a level name")?;
+                    config.scenario.level =
+                        Level::from_name(name).ok_or(format!("unknown level: {name}"))?;
+                }
+                "--difficulty" => {
+                    let name = args.next().ok_or("--difficulty takes easy|normal|hard")?;
+                    config.scenario.difficulty =
+                        Difficulty::from_name(name).ok_or(format!("unknown difficulty: {name}"))?;
+                }
+                "--no-enemies" => config.scenario.enemies = false,
+                "--god" => config.scenario.god = true,
+                "--practice" => config.scenario.practice = true,
+                "--selftest" => config.selftest = true,
+                "--screenshot" => config.screenshot = true,
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+}
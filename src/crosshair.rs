@@ -0,0 +1,137 @@
+use crate::engine::overlay::OverlayQuad;
+use glam::Vec2;
+use std::f32::consts::TAU;
+
+/// Segments the circle style is drawn with.
+const CIRCLE_SEGMENTS: usize = 24;
+/// Extra width of the faint quad under every crosshair piece, in pixels,
+/// and its share of the piece's opacity. It softens the hard pixel edges
+/// of the overlay.
+const FEATHER: f32 = 1.0;
+const FEATHER_ALPHA: f32 = 0.35;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrosshairStyle {
+    Dot,
+    /// Four arms around a fixed gap.
+    #[default]
+    Cross,
+    Circle,
+    /// Cross whose gap widens with movement speed and weapon spread.
+    Dynamic,
+}
+
+impl CrosshairStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dot" => Some(Self::Dot),
+            "cross" => Some(Self::Cross),
+            "circle" => Some(Self::Circle),
+            "dynamic" => Some(Self::Dynamic),
+            _ => None,
+        }
+    }
+}
+
+/// Reticle at the screen center, drawn with overlay quads. Sizes are in
+/// pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crosshair {
+    pub style: CrosshairStyle,
+    pub color: [f32; 4],
+    /// Length of each arm of the cross.
+    pub length: f32,
+    pub thickness: f32,
+    /// Distance from the center to the inner end of each arm, and the
+    /// radius of the circle style.
+    pub gap: f32,
+    /// Gap added per unit of movement speed, for the dynamic style.
+    pub speed_gap: f32,
+    /// Gap added per radian of weapon spread, for the dynamic style.
+    pub spread_gap: f32,
+}
+
+impl Default for Crosshair {
+    fn default() -> Self {
+        Self::new(CrosshairStyle::default())
+    }
+}
+
+impl Crosshair {
+    pub fn new(style: CrosshairStyle) -> Self {
+        Self {
+            style,
+            color: [1.0, 1.0, 1.0, 0.9],
+            length: 8.0,
+            thickness: 2.0,
+            gap: 5.0,
+            speed_gap: 2.0,
+            spread_gap: 300.0,
+        }
+    }
+
+    /// Gap for a player moving at `speed` with a weapon at `spread`; only
+    /// the dynamic style widens.
+    pub fn current_gap(&self, speed: f32, spread: f32) -> f32 {
+        match self.style {
+            CrosshairStyle::Dynamic => {
+                self.gap + speed.max(0.0) * self.speed_gap + spread.max(0.0) * self.spread_gap
+            }
+            _ => self.gap,
+        }
+    }
+
+    /// Quads of the crosshair centered on a `screen` sized in pixels, each
+    /// over its own feathered edge.
+    pub fn quads(&self, screen: Vec2, speed: f32, spread: f32) -> Vec<OverlayQuad> {
+        let center = screen * 0.5;
+        let gap = self.current_gap(speed, spread);
+        let pieces = match self.style {
+            CrosshairStyle::Dot => vec![OverlayQuad::new(
+                center,
+                Vec2::splat(self.thickness * 1.5),
+                self.color,
+            )],
+            CrosshairStyle::Cross | CrosshairStyle::Dynamic => {
+                let offset = gap + self.length * 0.5;
+                [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y]
+                    .map(|dir| {
+                        let size = if dir.x != 0.0 {
+                            Vec2::new(self.length, self.thickness)
+                        } else {
+                            Vec2::new(self.thickness, self.length)
+                        };
+                        OverlayQuad::new(center + dir * offset, size, self.color)
+                    })
+                    .to_vec()
+            }
+            CrosshairStyle::Circle => {
+                let segment = TAU * gap / CIRCLE_SEGMENTS as f32;
+                (0..CIRCLE_SEGMENTS)
+                    .map(|i| {
+                        let angle = i as f32 / CIRCLE_SEGMENTS as f32 * TAU;
+                        let dir = Vec2::from_angle(angle);
+                        OverlayQuad::new(
+                            center + dir * gap,
+                            Vec2::new(self.thickness, segment * 1.1),
+                            self.color,
+                        )
+                        .with_rotation(angle)
+                    })
+                    .collect()
+            }
+        };
+        pieces
+            .into_iter()
+            .flat_map(|quad| {
+                let [r, g, b, a] = quad.color;
+                let feather = OverlayQuad {
+                    size: quad.size + Vec2::splat(2.0 * FEATHER),
+                    color: [r, g, b, a * FEATHER_ALPHA],
+                    ..quad
+                };
+                [feather, quad]
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::engine::overlay::{screen_bearing, OverlayQuad};
+use crate::engine::timer::Timer;
+use glam::{Vec2, Vec3};
+
+/// Seconds an indicator takes to fade out.
+const FADE_TIME: f32 = 1.0;
+/// Distance of the indicator from the screen center, as a share of half
+/// the smaller screen side.
+const RADIUS: f32 = 0.8;
+/// Length along the screen edge and thickness, in pixels.
+const LENGTH: f32 = 80.0;
+const THICKNESS: f32 = 14.0;
+const COLOR: [f32; 3] = [1.0, 0.1, 0.1];
+
+/// Marker near the screen edge pointing toward whoever hit the player,
+/// fading out over `FADE_TIME`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DamageIndicator {
+    /// World position the hit came from.
+    pub source: Vec3,
+    fade: Timer,
+}
+
+impl DamageIndicator {
+    pub fn new(source: Vec3) -> Self {
+        Self {
+            source,
+            fade: Timer::new(FADE_TIME),
+        }
+    }
+
+    /// Advance the fade. Returns `false` once the indicator is gone.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.fade.tick(dt);
+        !self.fade.finished()
+    }
+
+    /// Opacity, from 1 right after the hit down to 0.
+    pub fn alpha(&self) -> f32 {
+        1.0 - self.fade.progress()
+    }
+
+    /// Bar on a circle around the screen center in the direction of
+    /// `source`, for a viewer at `eye` looking along `forward`. A source
+    /// straight ahead puts it at the top, one to the right on the right.
+    pub fn quad(&self, eye: Vec3, forward: Vec3, screen: Vec2) -> OverlayQuad {
+        let bearing = screen_bearing(forward, self.source - eye);
+        let radius = screen.min_element() * 0.5 * RADIUS;
+        let center = screen * 0.5 + Vec2::new(bearing.sin(), -bearing.cos()) * radius;
+        let [r, g, b] = COLOR;
+        OverlayQuad::new(
+            center,
+            Vec2::new(LENGTH, THICKNESS),
+            [r, g, b, self.alpha()],
+        )
+        .with_rotation(bearing)
+    }
+}
@@ -0,0 +1,106 @@
+use glam::Vec3;
+use std::f32::consts::TAU;
+
+/// Blocks in the artifact ring unless a level asks for another count.
+pub const RING_BLOCKS: usize = 28;
+/// Distance of the ring blocks from the origin.
+pub const RING_RADIUS: f32 = 3.0;
+
+/// Floor-level centers of `count` unit blocks spaced evenly around the
+/// artifact ring.
+pub fn ring_positions(count: usize) -> Vec<Vec3> {
+    (0..count)
+        .map(|i| {
+            let angle = i as f32 / count as f32 * TAU;
+            Vec3::new(RING_RADIUS * angle.cos(), 0.0, RING_RADIUS * angle.sin())
+        })
+        .collect()
+}
+
+/// Shape of one glow cycle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Triangle,
+    /// Square wave: bright for the first half of the cycle, dim for the rest.
+    Pulse,
+}
+
+/// Pulsing glow of the artifact, written to the artifact uniform each frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArtifactGlow {
+    /// Cycles per second.
+    pub frequency: f32,
+    pub min: f32,
+    pub max: f32,
+    pub waveform: Waveform,
+    time: f32,
+}
+
+impl Default for ArtifactGlow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtifactGlow {
+    pub fn new() -> Self {
+        Self {
+            frequency: 3.0 / TAU,
+            min: 0.2,
+            max: 1.0,
+            waveform: Waveform::Sine,
+            time: 0.0,
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn with_waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = waveform;
+        self
+    }
+
+    /// Intensity `t` seconds into the pulse, in `[min, max]`.
+    pub fn intensity_at(&self, t: f32) -> f32 {
+        let phase = (t * self.frequency).rem_euclid(1.0);
+        let level = match self.waveform {
+            Waveform::Sine => 0.5 + 0.5 * (phase * TAU).sin(),
+            Waveform::Triangle => 1.0 - (2.0 * phase - 1.0).abs(),
+            Waveform::Pulse => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        };
+        self.min + (self.max - self.min) * level
+    }
+
+    /// Advance the pulse and return the new intensity.
+    pub fn tick(&mut self, dt: f32) -> f32 {
+        self.time += dt;
+        self.intensity()
+    }
+
+    /// Intensity at the current point of the pulse.
+    pub fn intensity(&self) -> f32 {
+        self.intensity_at(self.time)
+    }
+
+    /// Intensity with the pulse stopped.
+    pub fn idle(&self) -> f32 {
+        self.min
+    }
+}
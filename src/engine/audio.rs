@@ -1,4 +1,6 @@
 #[cfg(feature = "audio")]
+use rodio::source::{SineWave, Source};
+#[cfg(feature = "audio")]
 use rodio::{Decoder, OutputStream, Sink};
 #[cfg(feature = "audio")]
 use std::io::Cursor;
@@ -7,17 +9,92 @@ use std::io::Cursor;
 pub struct AudioSystem {
     _stream: OutputStream,
     sink: Sink,
+    /// Looping tone whose volume and speed follow `set_hum`.
+    hum: Sink,
+    /// Short feedback tones, kept apart from `sink` so they don't wait for
+    /// longer sounds to finish.
+    effects: Sink,
+}
+
+/// Frequency of the beacon hum at normal speed, in Hz.
+#[cfg(feature = "audio")]
+const HUM_FREQUENCY: f32 = 110.0;
+/// Volume of the tones from `play_tone`.
+#[cfg(feature = "audio")]
+const TONE_GAIN: f32 = 0.3;
+
+/// Hum that gets louder and higher as the player nears the beacon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProximityHum {
+    /// Distance at which the hum becomes audible.
+    pub radius: f32,
+    pub max_gain: f32,
+    /// Playback speed at the edge of the radius and at the center.
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+}
+
+impl Default for ProximityHum {
+    fn default() -> Self {
+        Self::new(15.0)
+    }
+}
+
+impl ProximityHum {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            max_gain: 0.5,
+            min_pitch: 0.8,
+            max_pitch: 1.5,
+        }
+    }
+
+    /// 0 at or beyond `radius`, 1 at the center.
+    pub fn closeness(&self, distance: f32) -> f32 {
+        if self.radius > 0.0 {
+            (1.0 - distance / self.radius).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Squared so the rise is most noticeable close to the beacon.
+    pub fn gain(&self, distance: f32) -> f32 {
+        let c = self.closeness(distance);
+        self.max_gain * c * c
+    }
+
+    pub fn pitch(&self, distance: f32) -> f32 {
+        let c = self.closeness(distance);
+        self.min_pitch + (self.max_pitch - self.min_pitch) * c
+    }
 }
 
 #[cfg(not(feature = "audio"))]
 pub struct AudioSystem;
 
+impl Default for AudioSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AudioSystem {
     #[cfg(feature = "audio")]
     pub fn new() -> Self {
         let (_stream, handle) = OutputStream::try_default().expect("audio init");
         let sink = Sink::try_new(&handle).expect("sink");
-        Self { _stream, sink }
+        let hum = Sink::try_new(&handle).expect("sink");
+        hum.set_volume(0.0);
+        hum.append(SineWave::new(HUM_FREQUENCY));
+        let effects = Sink::try_new(&handle).expect("sink");
+        Self {
+            _stream,
+            sink,
+            hum,
+            effects,
+        }
     }
 
     #[cfg(not(feature = "audio"))]
@@ -37,4 +114,26 @@ impl AudioSystem {
 
     #[cfg(not(feature = "audio"))]
     pub fn play_bytes(&self, _bytes: &[u8]) {}
+
+    /// Play a sine tone of `frequency` Hz for `seconds`.
+    #[cfg(feature = "audio")]
+    pub fn play_tone(&self, frequency: f32, seconds: f32) {
+        let tone = SineWave::new(frequency)
+            .take_duration(std::time::Duration::from_secs_f32(seconds))
+            .amplify(TONE_GAIN);
+        self.effects.append(tone);
+    }
+
+    #[cfg(not(feature = "audio"))]
+    pub fn play_tone(&self, _frequency: f32, _seconds: f32) {}
+
+    /// Set the hum's volume and playback speed; a gain of 0 silences it.
+    #[cfg(feature = "audio")]
+    pub fn set_hum(&self, gain: f32, pitch: f32) {
+        self.hum.set_volume(gain);
+        self.hum.set_speed(pitch);
+    }
+
+    #[cfg(not(feature = "audio"))]
+    pub fn set_hum(&self, _gain: f32, _pitch: f32) {}
 }
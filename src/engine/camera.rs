@@ -0,0 +1,46 @@
+use glam::{Quat, Vec3};
+
+/// Transient view rotation on top of the player's look direction, used
+/// for recoil. It springs back to zero on its own.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewKick {
+    /// Radians, positive looks up.
+    pub pitch: f32,
+    /// Radians, positive turns left.
+    pub yaw: f32,
+    /// Fraction of the offset that remains after one second.
+    pub recovery: f32,
+}
+
+impl Default for ViewKick {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewKick {
+    pub fn new() -> Self {
+        Self {
+            pitch: 0.0,
+            yaw: 0.0,
+            recovery: 0.002,
+        }
+    }
+
+    pub fn kick(&mut self, pitch: f32, yaw: f32) {
+        self.pitch += pitch;
+        self.yaw += yaw;
+    }
+
+    /// Decay the offset toward zero.
+    pub fn tick(&mut self, dt: f32) {
+        let keep = self.recovery.powf(dt);
+        self.pitch *= keep;
+        self.yaw *= keep;
+    }
+
+    /// Offset to apply after the player's own rotation.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_axis_angle(Vec3::Y, self.yaw) * Quat::from_axis_angle(Vec3::X, self.pitch)
+    }
+}
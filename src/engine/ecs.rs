@@ -0,0 +1,129 @@
+//! Generational arena used as the entity store.
+//!
+//! Removing an entity bumps its slot's generation, so an old `EntityId`
+//! never aliases whatever gets inserted into the slot later.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId {
+    index: u32,
+    generation: u32,
+}
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+pub struct Registry<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Registry<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> EntityId {
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            return EntityId {
+                index,
+                generation: slot.generation,
+            };
+        }
+        self.slots.push(Slot {
+            generation: 0,
+            value: Some(value),
+        });
+        EntityId {
+            index: self.slots.len() as u32 - 1,
+            generation: 0,
+        }
+    }
+
+    pub fn remove(&mut self, id: EntityId) -> Option<T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation += 1;
+        self.free.push(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, id: EntityId) -> Option<&T> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: EntityId) -> Option<&mut T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.value.as_mut()
+    }
+
+    pub fn contains(&self, id: EntityId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Live entities in slot order, which keeps iteration deterministic.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &T)> {
+        self.slots.iter().enumerate().filter_map(|(i, slot)| {
+            let id = EntityId {
+                index: i as u32,
+                generation: slot.generation,
+            };
+            slot.value.as_ref().map(|v| (id, v))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(i, slot)| {
+            let id = EntityId {
+                index: i as u32,
+                generation: slot.generation,
+            };
+            slot.value.as_mut().map(|v| (id, v))
+        })
+    }
+
+    /// Remove every entity for which `keep` returns `false`.
+    pub fn retain(&mut self, mut keep: impl FnMut(EntityId, &mut T) -> bool) {
+        let dead: Vec<EntityId> = self
+            .iter_mut()
+            .filter_map(|(id, v)| (!keep(id, v)).then_some(id))
+            .collect();
+        for id in dead {
+            self.remove(id);
+        }
+    }
+}
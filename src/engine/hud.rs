@@ -0,0 +1,42 @@
+/// Colors used by every HUD draw call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HudTheme {
+    /// Overlay messages.
+    pub text: [f32; 4],
+    /// Health value at full health.
+    pub health_high: [f32; 4],
+    /// Health value at zero health.
+    pub health_low: [f32; 4],
+    /// Labels such as "Health:".
+    pub accent: [f32; 4],
+}
+
+impl Default for HudTheme {
+    fn default() -> Self {
+        Self {
+            text: [1.0, 1.0, 0.5, 1.0],
+            health_high: [0.0, 1.0, 0.0, 1.0],
+            health_low: [1.0, 0.0, 0.0, 1.0],
+            accent: [0.0, 1.0, 0.0, 1.0],
+        }
+    }
+}
+
+impl HudTheme {
+    /// White and yellow on the dark scene, for readability.
+    pub fn high_contrast() -> Self {
+        Self {
+            text: [1.0, 1.0, 1.0, 1.0],
+            health_high: [1.0, 1.0, 1.0, 1.0],
+            health_low: [1.0, 1.0, 0.0, 1.0],
+            accent: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Color of the health value, blended from `health_low` at 0 to
+    /// `health_high` at 100.
+    pub fn health_color(&self, health: i32) -> [f32; 4] {
+        let t = (health as f32 / 100.0).clamp(0.0, 1.0);
+        std::array::from_fn(|i| self.health_low[i] + (self.health_high[i] - self.health_low[i]) * t)
+    }
+}
@@ -1,31 +1,125 @@
 use std::collections::HashSet;
-use winit::event::{DeviceEvent, ElementState, Event, VirtualKeyCode, WindowEvent};
+use winit::event::{
+    DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent,
+};
+
+/// Pixels of touchpad scrolling counted as one wheel notch.
+const PIXELS_PER_SCROLL_LINE: f32 = 40.0;
+
+/// Input for one frame of a scripted run: the keys held down and the
+/// mouse movement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputFrame {
+    pub keys: Vec<VirtualKeyCode>,
+    pub mouse_delta: (f32, f32),
+}
+
+impl InputFrame {
+    pub fn holding(keys: &[VirtualKeyCode]) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            mouse_delta: (0.0, 0.0),
+        }
+    }
+}
+
+/// Parse a script with one frame per line. Each line lists the held keys
+/// (`W`, `A`, `S`, `D`, `Space`) and may add `mouse=dx,dy`. Empty lines are
+/// frames with no input; `#` starts a comment.
+pub fn parse_script(text: &str) -> Result<Vec<InputFrame>, String> {
+    let mut frames = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let mut frame = InputFrame::default();
+        for token in line.split_whitespace() {
+            if let Some(delta) = token.strip_prefix("mouse=") {
+                let parsed = delta
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)));
+                frame.mouse_delta =
+                    parsed.ok_or_else(|| format!("line {}: bad mouse delta {token}", n + 1))?;
+                continue;
+            }
+            let key = match token {
+                "W" => VirtualKeyCode::W,
+                "A" => VirtualKeyCode::A,
+                "S" => VirtualKeyCode::S,
+                "D" => VirtualKeyCode::D,
+                "Space" => VirtualKeyCode::Space,
+                _ => return Err(format!("line {}: unknown key {token}", n + 1)),
+            };
+            frame.keys.push(key);
+        }
+        frames.push(frame);
+    }
+    Ok(frames)
+}
 
 #[derive(Default)]
 pub struct InputState {
     pressed: HashSet<VirtualKeyCode>,
+    /// Keys that went down since the last `reset`.
+    just_pressed: HashSet<VirtualKeyCode>,
+    buttons: HashSet<MouseButton>,
+    /// Buttons that went down since the last `reset`.
+    just_clicked: HashSet<MouseButton>,
     pub mouse_delta: (f32, f32),
+    /// Wheel notches since the last `reset`, positive when scrolling up.
+    pub scroll_delta: f32,
 }
 
 impl InputState {
     pub fn handle_event(&mut self, event: &Event<()>) {
-        if let Event::DeviceEvent { event, .. } = event {
-            if let DeviceEvent::MouseMotion { delta } = event {
-                self.mouse_delta.0 += delta.0 as f32;
-                self.mouse_delta.1 += delta.1 as f32;
+        if let Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta },
+            ..
+        } = event
+        {
+            self.mouse_delta.0 += delta.0 as f32;
+            self.mouse_delta.1 += delta.1 as f32;
+        }
+        if let Event::WindowEvent {
+            event: WindowEvent::MouseWheel { delta, .. },
+            ..
+        } = event
+        {
+            self.scroll_delta += match delta {
+                MouseScrollDelta::LineDelta(_, y) => *y,
+                MouseScrollDelta::PixelDelta(p) => p.y as f32 / PIXELS_PER_SCROLL_LINE,
+            };
+        }
+        if let Event::WindowEvent {
+            event: WindowEvent::MouseInput { state, button, .. },
+            ..
+        } = event
+        {
+            match state {
+                ElementState::Pressed => {
+                    if self.buttons.insert(*button) {
+                        self.just_clicked.insert(*button);
+                    }
+                }
+                ElementState::Released => {
+                    self.buttons.remove(button);
+                }
             }
         }
-        if let Event::WindowEvent { event, .. } = event {
-            if let WindowEvent::KeyboardInput { input, .. } = event {
-                if let Some(key) = input.virtual_keycode {
-                    match input.state {
-                        ElementState::Pressed => {
-                            self.pressed.insert(key);
-                        }
-                        ElementState::Released => {
-                            self.pressed.remove(&key);
+        if let Event::WindowEvent {
+            event: WindowEvent::KeyboardInput { input, .. },
+            ..
+        } = event
+        {
+            if let Some(key) = input.virtual_keycode {
+                match input.state {
+                    ElementState::Pressed => {
+                        // Автоповтор клавиши не считается новым нажатием
+                        if self.pressed.insert(key) {
+                            self.just_pressed.insert(key);
                         }
                     }
+                    ElementState::Released => {
+                        self.pressed.remove(&key);
+                    }
                 }
             }
         }
@@ -35,7 +129,46 @@ impl InputState {
         self.pressed.contains(&key)
     }
 
+    /// Replace the held keys with `frame.keys` and add its mouse movement,
+    /// as if the matching window events had arrived.
+    pub fn apply_frame(&mut self, frame: &InputFrame) {
+        let keys: HashSet<VirtualKeyCode> = frame.keys.iter().copied().collect();
+        self.just_pressed.extend(keys.difference(&self.pressed));
+        self.pressed = keys;
+        self.mouse_delta.0 += frame.mouse_delta.0;
+        self.mouse_delta.1 += frame.mouse_delta.1;
+    }
+
+    /// `true` only in the frame the key went down.
+    pub fn just_pressed(&self, key: VirtualKeyCode) -> bool {
+        self.just_pressed.contains(&key)
+    }
+
+    pub fn button_pressed(&self, button: MouseButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// `true` only in the frame the button went down.
+    pub fn just_clicked(&self, button: MouseButton) -> bool {
+        self.just_clicked.contains(&button)
+    }
+
+    /// Press `button` as if the window event had arrived; for scripted
+    /// input and tests.
+    pub fn click(&mut self, button: MouseButton) {
+        if self.buttons.insert(button) {
+            self.just_clicked.insert(button);
+        }
+    }
+
+    pub fn release(&mut self, button: MouseButton) {
+        self.buttons.remove(&button);
+    }
+
     pub fn reset(&mut self) {
         self.mouse_delta = (0.0, 0.0);
+        self.scroll_delta = 0.0;
+        self.just_pressed.clear();
+        self.just_clicked.clear();
     }
 }
@@ -0,0 +1,110 @@
+use crate::engine::hud::HudTheme;
+use crate::engine::input::InputState;
+use wgpu_glyph::{OwnedSection, OwnedText};
+use winit::event::VirtualKeyCode;
+
+/// Keys that move the selection up.
+const UP_KEYS: [VirtualKeyCode; 2] = [VirtualKeyCode::Up, VirtualKeyCode::W];
+/// Keys that move the selection down.
+const DOWN_KEYS: [VirtualKeyCode; 2] = [VirtualKeyCode::Down, VirtualKeyCode::S];
+/// Keys that activate the selected item.
+const ACTIVATE_KEYS: [VirtualKeyCode; 3] = [
+    VirtualKeyCode::Return,
+    VirtualKeyCode::NumpadEnter,
+    VirtualKeyCode::Space,
+];
+
+/// Line of a menu: `id` is what activating it returns, `label` is what the
+/// player sees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem<T> {
+    pub id: T,
+    pub label: String,
+}
+
+impl<T> MenuItem<T> {
+    pub fn new(id: T, label: impl Into<String>) -> Self {
+        Self {
+            id,
+            label: label.into(),
+        }
+    }
+}
+
+/// Vertical list of items with one selected, for pause, settings and
+/// game-over screens. The selection wraps around at either end.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuState<T> {
+    pub items: Vec<MenuItem<T>>,
+    selected: usize,
+}
+
+impl<T: Copy> MenuState<T> {
+    pub fn new(items: Vec<MenuItem<T>>) -> Self {
+        Self { items, selected: 0 }
+    }
+
+    /// Index of the selected item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&MenuItem<T>> {
+        self.items.get(self.selected)
+    }
+
+    /// Select item `index`, clamped to the list.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    pub fn move_up(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    /// Move the selection by the keys that went down this frame. Returns
+    /// the id of the selected item if it was activated.
+    pub fn update(&mut self, input: &InputState) -> Option<T> {
+        if UP_KEYS.iter().any(|&k| input.just_pressed(k)) {
+            self.move_up();
+        }
+        if DOWN_KEYS.iter().any(|&k| input.just_pressed(k)) {
+            self.move_down();
+        }
+        if ACTIVATE_KEYS.iter().any(|&k| input.just_pressed(k)) {
+            return self.selected_item().map(|item| item.id);
+        }
+        None
+    }
+
+    /// One section per item, stacked down from `origin`. The selected item
+    /// is marked and drawn in the accent color.
+    pub fn sections(&self, origin: (f32, f32), scale: f32, theme: &HudTheme) -> Vec<OwnedSection> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let (marker, color) = if i == self.selected {
+                    ("> ", theme.accent)
+                } else {
+                    ("  ", theme.text)
+                };
+                OwnedSection {
+                    screen_position: (origin.0, origin.1 + i as f32 * scale * 1.25),
+                    text: vec![OwnedText::new(format!("{marker}{}", item.label))
+                        .with_color(color)
+                        .with_scale(scale)],
+                    ..OwnedSection::default()
+                }
+            })
+            .collect()
+    }
+}
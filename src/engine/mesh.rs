@@ -0,0 +1,199 @@
+use super::renderer::Vertex;
+use glam::Mat4;
+use std::collections::HashMap;
+use wgpu::util::DeviceExt;
+
+/// Index of a mesh in a `MeshRegistry`.
+pub type MeshId = usize;
+
+/// The unit cube every `CubeInstance` is drawn with. It is always the
+/// first mesh registered.
+pub const CUBE_MESH: MeshId = 0;
+
+/// Vertex and index buffers uploaded once and drawn any number of times.
+pub struct Mesh {
+    pub vertex_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+    pub num_indices: u32,
+}
+
+impl Mesh {
+    pub fn new(device: &wgpu::Device, label: &str, vertices: &[Vertex], indices: &[u16]) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{label} Vertex Buffer")),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{label} Index Buffer")),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        Self {
+            vertex_buffer,
+            index_buffer,
+            num_indices: indices.len() as u32,
+        }
+    }
+}
+
+/// Meshes the renderer can draw instances of, looked up by `MeshId`.
+pub struct MeshRegistry {
+    meshes: Vec<Mesh>,
+}
+
+impl MeshRegistry {
+    /// Registry holding only `cube`, as `CUBE_MESH`.
+    pub fn new(cube: Mesh) -> Self {
+        Self { meshes: vec![cube] }
+    }
+
+    /// Upload a mesh and return the id to draw it with.
+    pub fn register(
+        &mut self,
+        device: &wgpu::Device,
+        vertices: &[Vertex],
+        indices: &[u16],
+    ) -> MeshId {
+        let id = self.meshes.len();
+        self.meshes
+            .push(Mesh::new(device, &format!("Mesh {id}"), vertices, indices));
+        id
+    }
+
+    /// Upload parsed geometry and return the id to draw it with. Only
+    /// positions and colors are uploaded; `normals` stay on the CPU.
+    pub fn register_data(&mut self, device: &wgpu::Device, data: &MeshData) -> MeshId {
+        self.register(device, &data.vertices, &data.indices)
+    }
+
+    pub fn get(&self, id: MeshId) -> Option<&Mesh> {
+        self.meshes.get(id)
+    }
+
+    pub fn cube(&self) -> &Mesh {
+        &self.meshes[CUBE_MESH]
+    }
+
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+}
+
+/// One copy of a registered mesh placed in the world.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshInstance {
+    pub mesh_id: MeshId,
+    /// Model matrix applied to the mesh's own coordinates.
+    pub transform: Mat4,
+    pub color: [f32; 3],
+}
+
+impl MeshInstance {
+    pub fn new(mesh_id: MeshId, transform: Mat4, color: [f32; 3]) -> Self {
+        Self {
+            mesh_id,
+            transform,
+            color,
+        }
+    }
+}
+
+/// Geometry ready to be registered, with a normal per vertex.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshData {
+    pub vertices: Vec<Vertex>,
+    /// Zero for vertices the source gave no normal. The renderer is unlit
+    /// and doesn't draw with these; they are kept for later use.
+    pub normals: Vec<[f32; 3]>,
+    pub indices: Vec<u16>,
+}
+
+/// Parse Wavefront OBJ text. Reads positions (`v`), normals (`vn`) and
+/// triangular faces (`f`) in any of the `v`, `v/vt`, `v//vn` and `v/vt/vn`
+/// forms; negative indices count from the end. Materials, groups and
+/// texture coordinates are skipped. Every vertex is white. Normals are
+/// only parsed into `MeshData::normals`, not used for drawing.
+pub fn parse_obj(text: &str) -> Result<MeshData, String> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut mesh = MeshData::default();
+    // Одна вершина сетки на каждую пару позиция/нормаль
+    let mut seen: HashMap<(usize, Option<usize>), u16> = HashMap::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let mut tokens = line.split_whitespace();
+        let err = |what: &str| format!("line {}: {what}", n + 1);
+        match tokens.next() {
+            Some("v") => {
+                positions.push(parse_vec3(tokens).ok_or_else(|| err("bad position"))?);
+            }
+            Some("vn") => {
+                normals.push(parse_vec3(tokens).ok_or_else(|| err("bad normal"))?);
+            }
+            Some("f") => {
+                let corners: Vec<&str> = tokens.collect();
+                if corners.len() != 3 {
+                    return Err(err(&format!(
+                        "face with {} vertices, only triangles are supported",
+                        corners.len()
+                    )));
+                }
+                for corner in corners {
+                    let mut parts = corner.split('/');
+                    let v = parts
+                        .next()
+                        .and_then(|i| resolve_index(i, positions.len()))
+                        .ok_or_else(|| err(&format!("bad position index in {corner}")))?;
+                    let vn = match parts.nth(1).filter(|i| !i.is_empty()) {
+                        Some(i) => Some(
+                            resolve_index(i, normals.len())
+                                .ok_or_else(|| err(&format!("bad normal index in {corner}")))?,
+                        ),
+                        None => None,
+                    };
+                    let index = match seen.get(&(v, vn)) {
+                        Some(&index) => index,
+                        None => {
+                            let index = u16::try_from(mesh.vertices.len())
+                                .map_err(|_| err("too many vertices"))?;
+                            mesh.vertices.push(Vertex::new(positions[v], [1.0; 3]));
+                            mesh.normals.push(vn.map_or([0.0; 3], |i| normals[i]));
+                            seen.insert((v, vn), index);
+                            index
+                        }
+                    };
+                    mesh.indices.push(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(mesh)
+}
+
+fn parse_vec3<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Option<[f32; 3]> {
+    let mut v = [0.0; 3];
+    for c in &mut v {
+        *c = tokens.next()?.parse().ok()?;
+    }
+    Some(v)
+}
+
+/// Zero-based index for a one-based or negative OBJ index into a list of
+/// `len` items.
+fn resolve_index(index: &str, len: usize) -> Option<usize> {
+    let index: i64 = index.parse().ok()?;
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index - 1
+    };
+    (0..len as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
@@ -1,15 +1,32 @@
+pub mod artifact;
 pub mod audio;
+pub mod camera;
+pub mod ecs;
+pub mod hud;
 pub mod input;
+pub mod menu;
+pub mod mesh;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod overlay;
 pub mod physics;
+pub mod profiler;
 pub mod renderer;
+pub mod rng;
+pub mod screenshot;
+pub mod snapshot;
+pub mod text;
+pub mod timer;
 pub mod window;
 
 use audio::AudioSystem;
 use input::InputState;
 use renderer::Renderer;
+use std::time::{Duration, Instant};
+use timer::{pacing_sleep, FrameClock, RateLimiter, RealClock, TimeSource};
 use window::WindowState;
 use winit::{
-    event::{ElementState, Event, MouseButton, VirtualKeyCode},
+    event::Event,
     event_loop::{ControlFlow, EventLoop},
 };
 
@@ -20,13 +37,28 @@ pub struct Engine {
     pub audio: AudioSystem,
     pub renderer: Renderer,
     pub paused: bool,
+    /// Caps how often `run` calls the update closure, so the simulation
+    /// doesn't speed up on high refresh rate displays. `None` runs it
+    /// every frame.
+    pub update_limit: Option<RateLimiter>,
+    /// Frames per second `run` sleeps down to, to spare the CPU. `None`
+    /// runs flat out.
+    pub frame_cap: Option<f32>,
+    /// Seconds since the previous update, not counting time spent paused.
+    /// Set by `begin_frame` before each call of the update closure.
+    pub dt: f32,
+    time: Box<dyn TimeSource>,
+    clock: FrameClock,
+    /// Time of the previous frame, updated or not, for `update_limit`.
+    last_frame: Duration,
 }
 
 impl Engine {
     pub fn new(title: &str, width: u32, height: u32) -> Self {
         let event_loop = EventLoop::new();
         let window = WindowState::new(&event_loop, title, width, height);
-        let renderer = futures_lite::future::block_on(Renderer::new(&window.window));
+        let renderer =
+            futures_lite::future::block_on(Renderer::new(window.window.as_ref().unwrap()));
         Self {
             event_loop: Some(event_loop),
             window,
@@ -34,6 +66,12 @@ impl Engine {
             audio: AudioSystem::new(),
             renderer,
             paused: false,
+            update_limit: None,
+            frame_cap: None,
+            dt: 0.0,
+            time: Box::new(RealClock::new()),
+            clock: FrameClock::new(Duration::ZERO),
+            last_frame: Duration::ZERO,
         }
     }
 
@@ -41,35 +79,69 @@ impl Engine {
         let renderer = futures_lite::future::block_on(Renderer::new_headless(width, height));
         Self {
             event_loop: None,
-            window: WindowState::dummy(width, height),
+            window: WindowState::dummy(),
             input: InputState::default(),
             audio: AudioSystem::new(),
             renderer,
             paused: false,
+            update_limit: None,
+            frame_cap: None,
+            dt: 0.0,
+            time: Box::new(RealClock::new()),
+            clock: FrameClock::new(Duration::ZERO),
+            last_frame: Duration::ZERO,
         }
     }
 
     /// Pause the engine and release the cursor.
     pub fn pause(&mut self) {
         self.paused = true;
+        self.clock.pause(self.time.now());
         self.window.release_cursor();
     }
 
     /// Resume the engine and capture the cursor.
     pub fn resume(&mut self) {
         self.paused = false;
+        self.clock.resume(self.time.now());
         self.window.capture_cursor();
         self.input.reset();
     }
 
+    /// Read time from `source` from now on, e.g. a `VirtualClock` for
+    /// exact timing in tests and replays.
+    pub fn set_time_source(&mut self, source: Box<dyn TimeSource>) {
+        let now = source.now();
+        self.time = source;
+        self.clock = FrameClock::new(now);
+        self.last_frame = now;
+    }
+
+    /// Start a frame: advance the time source and decide whether the
+    /// update runs. When it returns `true`, `dt` holds the time since the
+    /// previous update.
+    pub fn begin_frame(&mut self) -> bool {
+        self.time.frame();
+        let now = self.time.now();
+        let frame_dt = now.saturating_sub(self.last_frame).as_secs_f32();
+        self.last_frame = now;
+        if let Some(limit) = self.update_limit.as_mut() {
+            if !limit.tick(frame_dt) {
+                return false;
+            }
+        }
+        self.dt = self.clock.tick(now);
+        true
+    }
+
     pub fn run<F: FnMut(&mut Self) + 'static>(mut self, mut update: F) {
         let event_loop = self.event_loop.take().unwrap();
         let mut engine = self;
         event_loop.run(move |event, _, control_flow| {
             engine.input.handle_event(&event);
             // Handle global input for pausing/resuming the game.
-            match &event {
-                winit::event::Event::WindowEvent { event, .. } => match event {
+            if let winit::event::Event::WindowEvent { event, .. } = &event {
+                match event {
                     winit::event::WindowEvent::KeyboardInput { input, .. } => {
                         if let (
                             Some(winit::event::VirtualKeyCode::Escape),
@@ -85,20 +157,27 @@ impl Engine {
                         state: winit::event::ElementState::Pressed,
                         button: winit::event::MouseButton::Left,
                         ..
-                    } => {
-                        if engine.paused {
-                            engine.resume();
-                        }
+                    } if engine.paused => {
+                        engine.resume();
                     }
                     _ => {}
-                },
-                _ => {}
+                }
             }
             match event {
-                Event::MainEventsCleared => {
-                    if !engine.paused {
-                        update(&mut engine);
-                        engine.window.request_redraw();
+                Event::MainEventsCleared if !engine.paused => {
+                    if !engine.begin_frame() {
+                        let wait = engine.update_limit.map_or(0.0, |limit| limit.remaining());
+                        *control_flow =
+                            ControlFlow::WaitUntil(Instant::now() + Duration::from_secs_f32(wait));
+                        return;
+                    }
+                    let start = engine.time.now();
+                    *control_flow = ControlFlow::Poll;
+                    update(&mut engine);
+                    engine.window.request_redraw();
+                    if let Some(fps) = engine.frame_cap {
+                        let frame_time = engine.time.now().saturating_sub(start);
+                        std::thread::sleep(pacing_sleep(frame_time, fps));
                     }
                 }
                 Event::RedrawRequested(_) => {
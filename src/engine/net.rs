@@ -0,0 +1,76 @@
+//! Minimal packet framing and remote-body smoothing for the UDP demo.
+//!
+//! Every packet is a little-endian `u32` payload length followed by the
+//! payload, so a receiver can tell a truncated datagram from a whole one.
+
+use glam::Vec3;
+
+pub fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(4 + payload.len());
+    packet.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+/// Split the first framed packet off `buf`, returning its payload and the
+/// remaining bytes, or `None` if `buf` does not hold a complete packet.
+pub fn read_frame(buf: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (len, rest) = buf.split_first_chunk::<4>()?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return None;
+    }
+    Some(rest.split_at(len))
+}
+
+/// A tick this far behind the last one accepted means the peer restarted
+/// and counts from the start again, not that a packet came late.
+const RESTART_GAP: u32 = 64;
+
+/// Whether a snapshot stamped `tick` should replace the last accepted one,
+/// stamped `last`. Late and repeated ticks are dropped, but one far behind
+/// `last` is taken as a restarted peer. Ticks compare with wrapping
+/// arithmetic, so the counter may overflow.
+pub fn newer_tick(tick: u32, last: u32) -> bool {
+    let ahead = tick.wrapping_sub(last);
+    let behind = last.wrapping_sub(tick);
+    // Какое из расстояний короче, в ту сторону тик и сдвинулся
+    if ahead != 0 && ahead <= behind {
+        return true;
+    }
+    behind >= RESTART_GAP
+}
+
+/// Smooths a remotely simulated position by gliding from where it was
+/// drawn last towards each newly received position over `interval` seconds.
+pub struct Interpolator {
+    pub interval: f32,
+    from: Vec3,
+    to: Vec3,
+    t: f32,
+}
+
+impl Interpolator {
+    pub fn new(start: Vec3, interval: f32) -> Self {
+        Self {
+            interval,
+            from: start,
+            to: start,
+            t: 1.0,
+        }
+    }
+
+    pub fn push(&mut self, target: Vec3) {
+        self.from = self.position();
+        self.to = target;
+        self.t = 0.0;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.t = (self.t + dt / self.interval).min(1.0);
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.from.lerp(self.to, self.t)
+    }
+}
@@ -0,0 +1,167 @@
+use super::timer::Timer;
+use glam::{Vec2, Vec3};
+
+/// Corner of a screen-space overlay triangle, in normalized device
+/// coordinates.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OverlayVertex {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+    /// Position within the quad, from -1 at one edge to 1 at the other.
+    pub local: [f32; 2],
+    /// `OverlayQuad::radial` of the quad, or -1 for a flat one.
+    pub radial: f32,
+}
+
+impl OverlayVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 4] =
+        wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4, 2 => Float32x2, 3 => Float32];
+
+    pub(crate) fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<OverlayVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Flat, alpha-blended rectangle drawn over the finished frame, placed in
+/// pixels from the top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OverlayQuad {
+    pub center: Vec2,
+    pub size: Vec2,
+    /// Clockwise rotation around `center`, in radians.
+    pub rotation: f32,
+    pub color: [f32; 4],
+    /// Fade the quad out toward its center: clear within this share of
+    /// the half-size from the center, ramping up to `color` at the edges.
+    /// `None` fills it flat.
+    pub radial: Option<f32>,
+}
+
+impl OverlayQuad {
+    pub fn new(center: Vec2, size: Vec2, color: [f32; 4]) -> Self {
+        Self {
+            center,
+            size,
+            rotation: 0.0,
+            color,
+            radial: None,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_radial(mut self, inner: f32) -> Self {
+        self.radial = Some(inner);
+        self
+    }
+
+    /// Corners in pixels, clockwise from the top-left.
+    pub fn corners(&self) -> [Vec2; 4] {
+        // Ось y экрана смотрит вниз, поэтому поворот на +angle здесь по
+        // часовой стрелке
+        let rotation = Vec2::from_angle(self.rotation);
+        let half = self.size * 0.5;
+        [
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(half.x, half.y),
+            Vec2::new(-half.x, half.y),
+        ]
+        .map(|corner| self.center + rotation.rotate(corner))
+    }
+
+    /// Two triangles covering the quad on a `screen` sized in pixels.
+    pub fn vertices(&self, screen: Vec2) -> [OverlayVertex; 6] {
+        let local = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+        let radial = self.radial.map_or(-1.0, |r| r.max(0.0));
+        let corners = self.corners();
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| OverlayVertex {
+            position: to_ndc(corners[i], screen),
+            color: self.color,
+            local: local[i],
+            radial,
+        });
+        [a, b, c, a, c, d]
+    }
+}
+
+/// Fade to `color` and back, for hiding a cut such as a respawn. The
+/// screen is covered more and more over the fade-out, then cleared again
+/// over the fade-in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenFade {
+    pub color: [f32; 3],
+    fade_out: Timer,
+    fade_in: Timer,
+}
+
+impl ScreenFade {
+    /// Fade to black over `fade_out` seconds and back over `fade_in`.
+    pub fn new(fade_out: f32, fade_in: f32) -> Self {
+        Self {
+            color: [0.0; 3],
+            fade_out: Timer::new(fade_out),
+            fade_in: Timer::new(fade_in),
+        }
+    }
+
+    pub fn with_color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Advance the fade. Returns `true` only on the tick that covers the
+    /// screen fully, which is when the cut should happen.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        if self.fade_out.finished() {
+            self.fade_in.tick(dt);
+            false
+        } else {
+            self.fade_out.tick(dt)
+        }
+    }
+
+    /// Opacity of the fade: 0 clear, 1 fully covered.
+    pub fn alpha(&self) -> f32 {
+        if self.fade_out.finished() {
+            1.0 - self.fade_in.progress()
+        } else {
+            self.fade_out.progress()
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.fade_in.finished()
+    }
+
+    /// Quad over the whole of a `screen` sized in pixels.
+    pub fn quad(&self, screen: Vec2) -> OverlayQuad {
+        let [r, g, b] = self.color;
+        OverlayQuad::new(screen * 0.5, screen, [r, g, b, self.alpha()])
+    }
+}
+
+/// Pixel position on a `screen` in normalized device coordinates.
+pub fn to_ndc(pixel: Vec2, screen: Vec2) -> [f32; 2] {
+    let ndc = pixel / screen * 2.0 - Vec2::ONE;
+    [ndc.x, -ndc.y]
+}
+
+/// Angle of `direction` as seen by a viewer facing `forward`, for placing
+/// markers around the screen center: 0 straight ahead, growing clockwise
+/// so a direction to the viewer's right is a quarter turn. Heights are
+/// ignored.
+pub fn screen_bearing(forward: Vec3, direction: Vec3) -> f32 {
+    let forward = Vec2::new(forward.x, forward.z);
+    let direction = Vec2::new(direction.x, direction.z);
+    // Правее взгляда — поворот по часовой стрелке, если смотреть сверху
+    forward.perp_dot(direction).atan2(forward.dot(direction))
+}
@@ -1,10 +1,113 @@
-use glam::Vec3;
+//! Rigid-body physics for the player, enemies and projectiles.
+//!
+//! `step` has to stay deterministic so that a recorded scene replays the
+//! same way: bodies are visited in slice order, pairs in `(i, j)` index
+//! order, and contact pairs come back in that same order. Keep hash-based
+//! collections (`HashMap`/`HashSet`) and anything with unspecified iteration
+//! order out of this module, and avoid `mul_add`, whose fused rounding
+//! differs between targets with and without FMA.
+
+use glam::{Vec2, Vec3};
 
 pub const GRAVITY: f32 = 9.81;
 
+/// Collision layer bits. A pair of colliders is only tested when each one's
+/// layer is in the other's mask.
+pub mod layers {
+    pub const DEFAULT: u32 = 1;
+    pub const PLAYER: u32 = 1 << 1;
+    pub const ENEMY: u32 = 1 << 2;
+    pub const PLAYER_BULLET: u32 = 1 << 3;
+    pub const ENEMY_BULLET: u32 = 1 << 4;
+    /// Every projectile layer.
+    pub const BULLETS: u32 = PLAYER_BULLET | ENEMY_BULLET;
+    /// Pieces of destroyed bodies, which only collide with the level.
+    pub const DEBRIS: u32 = 1 << 5;
+    pub const ALL: u32 = u32::MAX;
+}
+
+/// Solid a collider has against static obstacles.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ColliderShape {
+    /// The box given by `half_extents`.
+    #[default]
+    Box,
+    /// Upright capsule: a vertical segment of half length `half_height`
+    /// swept by a sphere of `radius`. Its rounded sides slide off edges and
+    /// corners instead of catching on them.
+    Capsule { radius: f32, half_height: f32 },
+}
+
+/// What a surface is made of, so impacts and footsteps can sound and look
+/// different on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Material {
+    #[default]
+    Generic,
+    Floor,
+    /// Blocks of the artifact ring.
+    Artifact,
+    Metal,
+}
+
+/// Body pairs always collide as boxes; `shape` only changes how the
+/// collider meets static obstacles.
 #[derive(Clone, Copy)]
 pub struct Collider {
     pub half_extents: Vec3,
+    pub shape: ColliderShape,
+    /// Center of the box relative to the body position, e.g. half the
+    /// height for a body whose origin is at its feet.
+    pub center_offset: Vec3,
+    pub layer: u32,
+    pub mask: u32,
+    pub material: Material,
+}
+
+impl Collider {
+    pub fn new(half_extents: Vec3) -> Self {
+        Self {
+            half_extents,
+            shape: ColliderShape::Box,
+            center_offset: Vec3::ZERO,
+            layer: layers::DEFAULT,
+            mask: layers::ALL,
+            material: Material::Generic,
+        }
+    }
+
+    /// Upright capsule `height` tall in total, caps included. Its box for
+    /// body pairs is the capsule's bounding box.
+    pub fn capsule(radius: f32, height: f32) -> Self {
+        let half = (height * 0.5).max(radius);
+        Self {
+            shape: ColliderShape::Capsule {
+                radius,
+                half_height: half - radius,
+            },
+            ..Self::new(Vec3::new(radius, half, radius))
+        }
+    }
+
+    pub fn with_center_offset(mut self, offset: Vec3) -> Self {
+        self.center_offset = offset;
+        self
+    }
+
+    pub fn with_layer(mut self, layer: u32, mask: u32) -> Self {
+        self.layer = layer;
+        self.mask = mask;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn interacts_with(&self, other: &Collider) -> bool {
+        self.layer & other.mask != 0 && other.layer & self.mask != 0
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -14,6 +117,9 @@ pub struct RigidBody {
     pub on_ground: bool,
     pub mass: f32,
     pub force: Vec3,
+    /// Linear air resistance: the fraction of velocity lost per second,
+    /// roughly, at small `dt`. Zero disables it.
+    pub drag: f32,
 }
 
 impl RigidBody {
@@ -24,9 +130,15 @@ impl RigidBody {
             on_ground: false,
             mass,
             force: Vec3::ZERO,
+            drag: 0.0,
         }
     }
 
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
     pub fn apply_force(&mut self, force: Vec3) {
         self.force += force;
     }
@@ -36,10 +148,193 @@ impl RigidBody {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Aabb {
     pub center: Vec3,
     pub half_extents: Vec3,
+    pub material: Material,
+}
+
+impl Aabb {
+    pub fn new(center: Vec3, half_extents: Vec3) -> Self {
+        Self {
+            center,
+            half_extents,
+            material: Material::Generic,
+        }
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Box a collider occupies when its body is at `position`, made of the
+    /// collider's material.
+    pub fn around(position: Vec3, collider: &Collider) -> Self {
+        Self::new(position + collider.center_offset, collider.half_extents)
+            .with_material(collider.material)
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        (point - self.center).abs().cmple(self.half_extents).all()
+    }
+
+    /// Distance along `direction` (unit length) at which a ray from
+    /// `origin` enters the box, or 0 if it starts inside.
+    pub fn ray_distance(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let (near, far) = self.ray_span(origin, direction)?;
+        (far >= 0.0).then_some(near.max(0.0))
+    }
+
+    /// Distances along `direction` (unit length) at which the line through
+    /// `origin` enters and leaves the box. The entry is negative when
+    /// `origin` is inside and both are when the box is behind it.
+    pub fn ray_span(&self, origin: Vec3, direction: Vec3) -> Option<(f32, f32)> {
+        let min = self.center - self.half_extents;
+        let max = self.center + self.half_extents;
+        let mut near = f32::NEG_INFINITY;
+        let mut far = f32::INFINITY;
+        for axis in 0..3 {
+            if direction[axis] == 0.0 {
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = 1.0 / direction[axis];
+            let a = (min[axis] - origin[axis]) * inv;
+            let b = (max[axis] - origin[axis]) * inv;
+            near = near.max(a.min(b));
+            far = far.min(a.max(b));
+            if near > far {
+                return None;
+            }
+        }
+        Some((near, far))
+    }
+
+    /// Strict overlap: boxes that only touch do not count.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        let overlap = self.half_extents + other.half_extents - (self.center - other.center).abs();
+        overlap.x > 0.0 && overlap.y > 0.0 && overlap.z > 0.0
+    }
+
+    /// How far the boxes sink into each other along the axis of least
+    /// overlap, the one `resolve_pair` separates them on. 0 if apart.
+    pub fn penetration(&self, other: &Aabb) -> f32 {
+        let overlap = self.half_extents + other.half_extents - (self.center - other.center).abs();
+        overlap.min_element().max(0.0)
+    }
+}
+
+/// What one `PhysicsWorld::step` worked through.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepStats {
+    /// Bodies stepped.
+    pub objects: usize,
+    /// Pairs whose layers let them collide, each tested for overlap.
+    pub pairs: usize,
+    /// Pairs found touching.
+    pub contacts: usize,
+}
+
+/// Two bodies found overlapping during a step, before they were pushed
+/// apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    /// Indices into the objects passed to `step`, `a < b`.
+    pub a: usize,
+    pub b: usize,
+    /// Middle of the overlap region.
+    pub point: Vec3,
+    /// Axis the bodies are separated along, pointing from `b` to `a`.
+    pub normal: Vec3,
+    pub depth: f32,
+    /// Materials of `a` and `b`, in that order.
+    pub materials: [Material; 2],
+}
+
+impl Contact {
+    /// Contact between boxes `box_a` and `box_b` of objects `a` and `b`.
+    /// The normal follows the axis `resolve_pair` picks.
+    pub fn between(a: usize, b: usize, box_a: &Aabb, box_b: &Aabb) -> Option<Self> {
+        let delta = box_a.center - box_b.center;
+        let overlap = box_a.half_extents + box_b.half_extents - delta.abs();
+        if overlap.min_element() <= 0.0 {
+            return None;
+        }
+        let axis = if overlap.x < overlap.y && overlap.x < overlap.z {
+            0
+        } else if overlap.y < overlap.z {
+            1
+        } else {
+            2
+        };
+        let mut normal = Vec3::ZERO;
+        normal[axis] = if delta[axis] > 0.0 { 1.0 } else { -1.0 };
+        let min = (box_a.center - box_a.half_extents).max(box_b.center - box_b.half_extents);
+        let max = (box_a.center + box_a.half_extents).min(box_b.center + box_b.half_extents);
+        Some(Self {
+            a,
+            b,
+            point: (min + max) * 0.5,
+            normal,
+            depth: overlap[axis],
+            materials: [box_a.material, box_b.material],
+        })
+    }
+}
+
+/// Volume of a sensor trigger, relative to its center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TriggerShape {
+    /// Upright cylinder around the Y axis.
+    Cylinder {
+        radius: f32,
+        half_height: f32,
+    },
+    Box {
+        half_extents: Vec3,
+    },
+}
+
+/// Sensor volume: it reports whether a point is inside but never takes
+/// part in collision resolution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trigger {
+    pub center: Vec3,
+    pub shape: TriggerShape,
+}
+
+impl Trigger {
+    pub fn cylinder(center: Vec3, radius: f32, half_height: f32) -> Self {
+        Self {
+            center,
+            shape: TriggerShape::Cylinder {
+                radius,
+                half_height,
+            },
+        }
+    }
+
+    pub fn aabb(center: Vec3, half_extents: Vec3) -> Self {
+        Self {
+            center,
+            shape: TriggerShape::Box { half_extents },
+        }
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        let d = point - self.center;
+        match self.shape {
+            TriggerShape::Cylinder {
+                radius,
+                half_height,
+            } => d.x * d.x + d.z * d.z < radius * radius && d.y.abs() <= half_height,
+            TriggerShape::Box { half_extents } => d.abs().cmple(half_extents).all(),
+        }
+    }
 }
 
 pub fn apply_gravity(body: &mut RigidBody) {
@@ -51,65 +346,301 @@ pub fn apply_gravity(body: &mut RigidBody) {
 pub fn integrate(body: &mut RigidBody, dt: f32) {
     let acceleration = body.force / body.mass;
     body.velocity += acceleration * dt;
+    // Implicit form: never flips the velocity, however large drag * dt is
+    body.velocity /= 1.0 + body.drag * dt;
     body.position += body.velocity * dt;
     body.force = Vec3::ZERO;
 }
 
-pub fn resolve_aabb_collisions(
-    body: &mut RigidBody,
-    collider: &Collider,
-    obstacles: &[Aabb],
-) {
+/// Classic fourth-order Runge-Kutta step, much more accurate than
+/// `integrate` when the acceleration changes with position, as it does
+/// near attractors. `acceleration` gets a position and a velocity.
+pub fn integrate_rk4(body: &mut RigidBody, dt: f32, acceleration: impl Fn(Vec3, Vec3) -> Vec3) {
+    let half = dt * 0.5;
+    let (p1, v1) = (body.position, body.velocity);
+    let a1 = acceleration(p1, v1);
+    let (p2, v2) = (p1 + v1 * half, v1 + a1 * half);
+    let a2 = acceleration(p2, v2);
+    let (p3, v3) = (p1 + v2 * half, v1 + a2 * half);
+    let a3 = acceleration(p3, v3);
+    let (p4, v4) = (p1 + v3 * dt, v1 + a3 * dt);
+    let a4 = acceleration(p4, v4);
+    body.position = p1 + (v1 + 2.0 * v2 + 2.0 * v3 + v4) * (dt / 6.0);
+    body.velocity = v1 + (a1 + 2.0 * a2 + 2.0 * a3 + a4) * (dt / 6.0);
+    body.force = Vec3::ZERO;
+}
+
+pub fn resolve_aabb_collisions(body: &mut RigidBody, collider: &Collider, obstacles: &[Aabb]) {
+    if let ColliderShape::Capsule {
+        radius,
+        half_height,
+    } = collider.shape
+    {
+        resolve_capsule_collisions(body, collider, radius, half_height, obstacles);
+        return;
+    }
+    let offset = collider.center_offset;
     for obs in obstacles {
-        let delta = body.position - obs.center;
+        let delta = body.position + offset - obs.center;
         let overlap = collider.half_extents + obs.half_extents - delta.abs();
         if overlap.x > 0.0 && overlap.y > 0.0 && overlap.z > 0.0 {
             if overlap.x < overlap.y && overlap.x < overlap.z {
                 let sign = if delta.x > 0.0 { 1.0 } else { -1.0 };
-                body.position.x = obs.center.x + sign * (obs.half_extents.x + collider.half_extents.x);
+                body.position.x =
+                    obs.center.x + sign * (obs.half_extents.x + collider.half_extents.x) - offset.x;
                 body.velocity.x = 0.0;
             } else if overlap.y < overlap.z {
                 let sign = if delta.y > 0.0 { 1.0 } else { -1.0 };
-                body.position.y = obs.center.y + sign * (obs.half_extents.y + collider.half_extents.y);
+                body.position.y =
+                    obs.center.y + sign * (obs.half_extents.y + collider.half_extents.y) - offset.y;
                 body.velocity.y = 0.0;
                 if sign > 0.0 {
                     body.on_ground = true;
                 }
             } else {
                 let sign = if delta.z > 0.0 { 1.0 } else { -1.0 };
-                body.position.z = obs.center.z + sign * (obs.half_extents.z + collider.half_extents.z);
+                body.position.z =
+                    obs.center.z + sign * (obs.half_extents.z + collider.half_extents.z) - offset.z;
                 body.velocity.z = 0.0;
             }
         }
     }
 }
 
+/// Push a capsule out of every obstacle along the line between the
+/// obstacle and the nearest point of the capsule's segment, removing only
+/// the velocity into the surface so the body slides along it.
+fn resolve_capsule_collisions(
+    body: &mut RigidBody,
+    collider: &Collider,
+    radius: f32,
+    half_height: f32,
+    obstacles: &[Aabb],
+) {
+    let as_box = Collider {
+        shape: ColliderShape::Box,
+        ..*collider
+    };
+    for obs in obstacles {
+        let center = body.position + collider.center_offset;
+        let min = obs.center - obs.half_extents;
+        let max = obs.center + obs.half_extents;
+        // Для вертикального отрезка и AABB ближайшую точку отрезка даёт
+        // проекция центра коробки на отрезок
+        let y = obs
+            .center
+            .y
+            .clamp(center.y - half_height, center.y + half_height);
+        let point = Vec3::new(center.x, y, center.z);
+        let closest = point.clamp(min, max);
+        let delta = point - closest;
+        let distance = delta.length();
+        if distance >= radius {
+            continue;
+        }
+        if distance == 0.0 {
+            // Ось капсулы внутри коробки: выталкиваем как коробку
+            resolve_aabb_collisions(body, &as_box, std::slice::from_ref(obs));
+            continue;
+        }
+        let normal = delta / distance;
+        body.position += normal * (radius - distance);
+        let into = body.velocity.dot(normal);
+        if into < 0.0 {
+            body.velocity -= normal * into;
+        }
+        if normal.y > 0.7 {
+            body.on_ground = true;
+        }
+    }
+}
+
+/// First box a ray hits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Index into the slice passed to `raycast`.
+    pub index: usize,
+    pub distance: f32,
+    pub point: Vec3,
+    /// Material of the box that was hit.
+    pub material: Material,
+}
+
+/// Nearest box within `max_distance` along `direction` from `origin`. Ties
+/// go to the lower index.
+pub fn raycast(origin: Vec3, direction: Vec3, max_distance: f32, boxes: &[Aabb]) -> Option<RayHit> {
+    let direction = direction.normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return None;
+    }
+    let mut best: Option<RayHit> = None;
+    for (index, aabb) in boxes.iter().enumerate() {
+        let Some(distance) = aabb.ray_distance(origin, direction) else {
+            continue;
+        };
+        if distance <= max_distance && best.is_none_or(|b| distance < b.distance) {
+            best = Some(RayHit {
+                index,
+                distance,
+                point: origin + direction * distance,
+                material: aabb.material,
+            });
+        }
+    }
+    best
+}
+
+/// Every box a ray from `origin` enters within `max_distance`, nearest
+/// first. Unlike `raycast`, boxes the ray starts inside are skipped, so a
+/// moving body sweeping its path each step meets each box only once.
+pub fn sweep(origin: Vec3, direction: Vec3, max_distance: f32, boxes: &[Aabb]) -> Vec<RayHit> {
+    let direction = direction.normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return Vec::new();
+    }
+    let mut hits: Vec<RayHit> = boxes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, aabb)| {
+            let (distance, _) = aabb.ray_span(origin, direction)?;
+            (0.0..=max_distance).contains(&distance).then(|| RayHit {
+                index,
+                distance,
+                point: origin + direction * distance,
+                material: aabb.material,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    hits
+}
+
+/// Path of a projectile launched from `start`, one point per step with the
+/// start first, stepped with the same integration as `step`. Pure: meant
+/// for drawing an aiming arc.
+pub fn predict_trajectory(
+    start: Vec3,
+    velocity: Vec3,
+    gravity: Vec3,
+    steps: usize,
+    dt: f32,
+) -> Vec<Vec3> {
+    predict_trajectory_until(start, velocity, gravity, steps, dt, &[])
+}
+
+/// Like `predict_trajectory`, but the path ends at the first point inside
+/// one of `static_obs`.
+pub fn predict_trajectory_until(
+    start: Vec3,
+    velocity: Vec3,
+    gravity: Vec3,
+    steps: usize,
+    dt: f32,
+    static_obs: &[Aabb],
+) -> Vec<Vec3> {
+    let mut body = RigidBody::new(1.0, start);
+    body.velocity = velocity;
+    let mut path = Vec::with_capacity(steps + 1);
+    path.push(start);
+    for _ in 0..steps {
+        body.apply_force(gravity * body.mass);
+        integrate(&mut body, dt);
+        path.push(body.position);
+        if static_obs.iter().any(|obs| obs.contains(body.position)) {
+            break;
+        }
+    }
+    path
+}
+
+/// How many obstacles in a row `teleport` will step out of.
+const TELEPORT_DEPTH: usize = 3;
+
+/// Move `body` to `position` at rest, then to the nearest spot that does
+/// not overlap any obstacle. Candidates are built by stepping out of an
+/// overlapped obstacle through one of its faces, up to `TELEPORT_DEPTH`
+/// times, so a body can leave a cluster of touching boxes. Returns `false`
+/// and leaves the body at `position` if no free spot was found.
+pub fn teleport(
+    body: &mut RigidBody,
+    collider: &Collider,
+    position: Vec3,
+    static_obs: &[Aabb],
+) -> bool {
+    body.position = position;
+    body.velocity = Vec3::ZERO;
+    body.force = Vec3::ZERO;
+    body.on_ground = false;
+
+    let mut best: Option<Vec3> = None;
+    let mut frontier = vec![position];
+    for _ in 0..=TELEPORT_DEPTH {
+        let mut next = Vec::new();
+        for candidate in frontier {
+            let bounds = Aabb::around(candidate, collider);
+            let mut blocked = false;
+            for obs in static_obs.iter().filter(|obs| obs.overlaps(&bounds)) {
+                blocked = true;
+                for axis in 0..3 {
+                    let reach = obs.half_extents[axis] + collider.half_extents[axis];
+                    for sign in [-1.0, 1.0] {
+                        let mut exit = candidate;
+                        exit[axis] = obs.center[axis] + sign * reach - collider.center_offset[axis];
+                        next.push(exit);
+                    }
+                }
+            }
+            let closer = best.is_none_or(|b| {
+                candidate.distance_squared(position) < b.distance_squared(position)
+            });
+            if !blocked && closer {
+                best = Some(candidate);
+            }
+        }
+        frontier = next;
+    }
+    match best {
+        Some(free) => {
+            body.position = free;
+            true
+        }
+        None => false,
+    }
+}
+
 pub struct PhysicsObject<'a> {
     pub body: &'a mut RigidBody,
     pub collider: Collider,
 }
 
 pub fn resolve_pair(a: &mut PhysicsObject, b: &mut PhysicsObject) -> bool {
-    let delta = a.body.position - b.body.position;
+    resolve_pair_limited(a, b, f32::INFINITY)
+}
+
+/// `resolve_pair` that moves the pair apart by at most `max_push`, so a
+/// deep overlap is worked off over several calls instead of in one jump.
+pub fn resolve_pair_limited(a: &mut PhysicsObject, b: &mut PhysicsObject, max_push: f32) -> bool {
+    let delta =
+        (a.body.position + a.collider.center_offset) - (b.body.position + b.collider.center_offset);
     let overlap = a.collider.half_extents + b.collider.half_extents - delta.abs();
     if overlap.x > 0.0 && overlap.y > 0.0 && overlap.z > 0.0 {
         if overlap.x < overlap.y && overlap.x < overlap.z {
             let sign = if delta.x > 0.0 { 1.0 } else { -1.0 };
-            let push = overlap.x * 0.5;
+            let push = overlap.x.min(max_push) * 0.5;
             a.body.position.x += sign * push;
             b.body.position.x -= sign * push;
             a.body.velocity.x = 0.0;
             b.body.velocity.x = 0.0;
         } else if overlap.y < overlap.z {
             let sign = if delta.y > 0.0 { 1.0 } else { -1.0 };
-            let push = overlap.y * 0.5;
+            let push = overlap.y.min(max_push) * 0.5;
             a.body.position.y += sign * push;
             b.body.position.y -= sign * push;
             a.body.velocity.y = 0.0;
             b.body.velocity.y = 0.0;
         } else {
             let sign = if delta.z > 0.0 { 1.0 } else { -1.0 };
-            let push = overlap.z * 0.5;
+            let push = overlap.z.min(max_push) * 0.5;
             a.body.position.z += sign * push;
             b.body.position.z -= sign * push;
             a.body.velocity.z = 0.0;
@@ -121,24 +652,393 @@ pub fn resolve_pair(a: &mut PhysicsObject, b: &mut PhysicsObject) -> bool {
     }
 }
 
-pub fn step(objects: &mut [PhysicsObject], static_obs: &[Aabb], dt: f32) -> Vec<(usize, usize)> {
+/// Horizontal bounds that bodies cannot leave, in world XZ.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arena {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Arena {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    /// Keep the whole collider inside the bounds and drop the velocity
+    /// that points out of them.
+    pub fn confine(&self, body: &mut RigidBody, collider: &Collider) {
+        let half = Vec2::new(collider.half_extents.x, collider.half_extents.z);
+        let offset = Vec2::new(collider.center_offset.x, collider.center_offset.z);
+        let lo = self.min + half - offset;
+        let hi = self.max - half - offset;
+        if body.position.x < lo.x {
+            body.position.x = lo.x;
+            body.velocity.x = body.velocity.x.max(0.0);
+        } else if body.position.x > hi.x {
+            body.position.x = hi.x;
+            body.velocity.x = body.velocity.x.min(0.0);
+        }
+        if body.position.z < lo.y {
+            body.position.z = lo.y;
+            body.velocity.z = body.velocity.z.max(0.0);
+        } else if body.position.z > hi.y {
+            body.position.z = hi.y;
+            body.velocity.z = body.velocity.z.min(0.0);
+        }
+    }
+}
+
+/// Closer than this an attractor's pull stops growing, so a body at the
+/// center does not get an unbounded force.
+pub const ATTRACTOR_MIN_DISTANCE: f32 = 0.5;
+
+/// Point that pulls bodies within `radius` toward `center` with an
+/// inverse-square force.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attractor {
+    pub center: Vec3,
+    /// Acceleration at a distance of one unit.
+    pub strength: f32,
+    pub radius: f32,
+}
+
+impl Attractor {
+    pub fn new(center: Vec3, strength: f32, radius: f32) -> Self {
+        Self {
+            center,
+            strength,
+            radius,
+        }
+    }
+
+    /// Force on `body`, scaled by its mass like gravity.
+    pub fn force_on(&self, body: &RigidBody) -> Vec3 {
+        self.force_at(body.position, body.mass)
+    }
+
+    /// Force on a body of `mass` at `position`.
+    pub fn force_at(&self, position: Vec3, mass: f32) -> Vec3 {
+        let offset = self.center - position;
+        let distance_sq = offset.length_squared();
+        if distance_sq >= self.radius * self.radius || distance_sq == 0.0 {
+            return Vec3::ZERO;
+        }
+        let clamped = distance_sq.max(ATTRACTOR_MIN_DISTANCE * ATTRACTOR_MIN_DISTANCE);
+        offset / distance_sq.sqrt() * (self.strength * mass / clamped)
+    }
+}
+
+/// Volume that pushes every body overlapping it with a constant force,
+/// like a vent or a gust of wind.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindZone {
+    pub bounds: Aabb,
+    pub force: Vec3,
+}
+
+impl WindZone {
+    pub fn new(bounds: Aabb, force: Vec3) -> Self {
+        Self { bounds, force }
+    }
+}
+
+/// Volume whose gravity replaces the usual pull on every body overlapping
+/// it, for low-gravity or upside-down sections.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GravityZone {
+    pub bounds: Aabb,
+    /// Acceleration due to gravity inside the zone.
+    pub gravity: Vec3,
+}
+
+impl GravityZone {
+    pub fn new(bounds: Aabb, gravity: Vec3) -> Self {
+        Self { bounds, gravity }
+    }
+
+    /// Zone with the usual gravity scaled by `scale`; a negative scale
+    /// flips it upward.
+    pub fn scaled(bounds: Aabb, scale: f32) -> Self {
+        Self::new(bounds, Vec3::new(0.0, -GRAVITY * scale, 0.0))
+    }
+}
+
+/// Blast that throws bodies away from `center`. Its strength falls off
+/// linearly to nothing at `radius`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Explosion {
+    pub center: Vec3,
+    pub radius: f32,
+    /// Impulse given to a body right at the center.
+    pub impulse: f32,
+}
+
+impl Explosion {
+    pub fn new(center: Vec3, radius: f32, impulse: f32) -> Self {
+        Self {
+            center,
+            radius,
+            impulse,
+        }
+    }
+
+    /// Strength at `position`, from 1 at the center down to 0 at `radius`
+    /// and beyond.
+    pub fn falloff(&self, position: Vec3) -> f32 {
+        if self.radius <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - position.distance(self.center) / self.radius).max(0.0)
+    }
+
+    /// Push `body` away from the center. A body right at the center is
+    /// thrown straight up.
+    pub fn push(&self, body: &mut RigidBody) {
+        let strength = self.falloff(body.position);
+        if strength <= 0.0 {
+            return;
+        }
+        let direction = (body.position - self.center)
+            .try_normalize()
+            .unwrap_or(Vec3::Y);
+        body.apply_impulse(direction * self.impulse * strength);
+    }
+}
+
+/// Push every object caught in `explosion`.
+pub fn apply_explosion(explosion: &Explosion, objects: &mut [PhysicsObject]) {
     for obj in objects.iter_mut() {
-        apply_gravity(obj.body);
-        integrate(obj.body, dt);
-        resolve_aabb_collisions(obj.body, &obj.collider, static_obs);
-    }
-
-    let mut pairs = Vec::new();
-    for i in 0..objects.len() {
-        for j in (i + 1)..objects.len() {
-            // Split borrow to avoid double mutable borrow
-            let (left, right) = objects.split_at_mut(j);
-            let a = &mut left[i];
-            let b = &mut right[0];
-            if resolve_pair(a, b) {
-                pairs.push((i, j));
+        explosion.push(obj.body);
+    }
+}
+
+/// How `PhysicsWorld::step` advances bodies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Integrator {
+    /// Semi-implicit Euler, see `integrate`.
+    #[default]
+    Euler,
+    /// Fourth-order Runge-Kutta, see `integrate_rk4`. Worth its four
+    /// force evaluations for orbits around attractors.
+    Rk4,
+}
+
+/// Settings applied on every `PhysicsWorld::step`. The default adds
+/// nothing to plain `step`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhysicsWorld {
+    pub arena: Option<Arena>,
+    /// Applied in insertion order.
+    pub attractors: Vec<Attractor>,
+    pub wind_zones: Vec<WindZone>,
+    /// Where zones overlap, the last one added wins.
+    pub gravity_zones: Vec<GravityZone>,
+    /// Passes over the body pairs per step. Resolving one pair can push a
+    /// body into another, so crowds need several. At least one pass is
+    /// always made.
+    pub solver_iterations: usize,
+    /// Passes stop early once no pair penetrates deeper than this.
+    pub penetration_tolerance: f32,
+    /// How far one step may move a pair of bodies apart, shared between
+    /// the solver passes. Unlimited if `None`.
+    pub max_depenetration: Option<f32>,
+    pub integrator: Integrator,
+}
+
+impl PhysicsWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_arena(mut self, arena: Arena) -> Self {
+        self.arena = Some(arena);
+        self
+    }
+
+    pub fn with_solver_iterations(mut self, iterations: usize) -> Self {
+        self.solver_iterations = iterations;
+        self
+    }
+
+    pub fn with_max_depenetration(mut self, distance: f32) -> Self {
+        self.max_depenetration = Some(distance);
+        self
+    }
+
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    pub fn add_attractor(&mut self, center: Vec3, strength: f32, radius: f32) {
+        self.attractors
+            .push(Attractor::new(center, strength, radius));
+    }
+
+    pub fn add_wind_zone(&mut self, bounds: Aabb, force: Vec3) {
+        self.wind_zones.push(WindZone::new(bounds, force));
+    }
+
+    pub fn add_gravity_zone(&mut self, zone: GravityZone) {
+        self.gravity_zones.push(zone);
+    }
+
+    /// Acceleration due to gravity on a body at `position`.
+    pub fn gravity_at(&self, position: Vec3, collider: &Collider) -> Vec3 {
+        let bounds = Aabb::around(position, collider);
+        self.gravity_zones
+            .iter()
+            .rev()
+            .find(|zone| zone.bounds.overlaps(&bounds))
+            .map_or(Vec3::new(0.0, -GRAVITY, 0.0), |zone| zone.gravity)
+    }
+
+    /// `apply_gravity` with the gravity of the zone the body is in. Ground
+    /// only holds a body up against gravity pulling it down.
+    fn apply_zone_gravity(&self, body: &mut RigidBody, collider: &Collider) {
+        let gravity = self.gravity_at(body.position, collider);
+        if !body.on_ground || gravity.y > 0.0 {
+            body.force += gravity * body.mass;
+        }
+    }
+
+    /// Attractor and wind force on a body of `mass` at `position`.
+    fn field_force(&self, position: Vec3, mass: f32, collider: &Collider) -> Vec3 {
+        let mut force = Vec3::ZERO;
+        for attractor in &self.attractors {
+            force += attractor.force_at(position, mass);
+        }
+        let bounds = Aabb::around(position, collider);
+        for zone in &self.wind_zones {
+            if zone.bounds.overlaps(&bounds) {
+                force += zone.force;
             }
         }
+        force
+    }
+
+    /// Advance every body by `dt` and return the index pairs that touched.
+    pub fn step(
+        &self,
+        objects: &mut [PhysicsObject],
+        static_obs: &[Aabb],
+        dt: f32,
+    ) -> Vec<(usize, usize)> {
+        self.step_contacts(objects, static_obs, dt)
+            .iter()
+            .map(|c| (c.a, c.b))
+            .collect()
+    }
+
+    /// `step` that also reports where each pair touched, one contact per
+    /// pair as first found.
+    pub fn step_contacts(
+        &self,
+        objects: &mut [PhysicsObject],
+        static_obs: &[Aabb],
+        dt: f32,
+    ) -> Vec<Contact> {
+        self.step_stats(objects, static_obs, dt).0
     }
-    pairs
+
+    /// `step_contacts` that also counts the bodies and pairs it handled.
+    pub fn step_stats(
+        &self,
+        objects: &mut [PhysicsObject],
+        static_obs: &[Aabb],
+        dt: f32,
+    ) -> (Vec<Contact>, StepStats) {
+        for obj in objects.iter_mut() {
+            self.apply_zone_gravity(obj.body, &obj.collider);
+            let (mass, drag, collider) = (obj.body.mass, obj.body.drag, obj.collider);
+            match self.integrator {
+                Integrator::Euler => {
+                    let force = self.field_force(obj.body.position, mass, &collider);
+                    obj.body.apply_force(force);
+                    integrate(obj.body, dt);
+                }
+                Integrator::Rk4 => {
+                    // Приложенные силы и гравитация постоянны в пределах шага
+                    let applied = obj.body.force;
+                    integrate_rk4(obj.body, dt, |position, velocity| {
+                        (applied + self.field_force(position, mass, &collider)) / mass
+                            - velocity * drag
+                    });
+                }
+            }
+            resolve_aabb_collisions(obj.body, &obj.collider, static_obs);
+        }
+
+        let mut contacts: Vec<Contact> = Vec::new();
+        let passes = self.solver_iterations.max(1);
+        let max_push = self
+            .max_depenetration
+            .map_or(f32::INFINITY, |d| d / passes as f32);
+        let mut pairs = 0;
+        for pass in 0..passes {
+            let mut deepest = 0.0f32;
+            for i in 0..objects.len() {
+                for j in (i + 1)..objects.len() {
+                    // Split borrow to avoid double mutable borrow
+                    let (left, right) = objects.split_at_mut(j);
+                    let a = &mut left[i];
+                    let b = &mut right[0];
+                    if !a.collider.interacts_with(&b.collider) {
+                        continue;
+                    }
+                    if pass == 0 {
+                        pairs += 1;
+                    }
+                    let box_a = Aabb::around(a.body.position, &a.collider);
+                    let box_b = Aabb::around(b.body.position, &b.collider);
+                    deepest = deepest.max(box_a.penetration(&box_b));
+                    let contact = Contact::between(i, j, &box_a, &box_b);
+                    if resolve_pair_limited(a, b, max_push)
+                        && !contacts.iter().any(|c| (c.a, c.b) == (i, j))
+                    {
+                        contacts.extend(contact);
+                    }
+                }
+            }
+            if deepest <= self.penetration_tolerance {
+                break;
+            }
+        }
+
+        if let Some(arena) = &self.arena {
+            for obj in objects.iter_mut() {
+                arena.confine(obj.body, &obj.collider);
+            }
+        }
+        let stats = StepStats {
+            objects: objects.len(),
+            pairs,
+            contacts: contacts.len(),
+        };
+        (contacts, stats)
+    }
+}
+
+/// FNV-1a over the bit patterns of every body's position and velocity, in
+/// order. Equal scenes give equal hashes on every platform and build, so
+/// tests can pin a whole simulation to one number.
+pub fn scene_hash(objects: &[PhysicsObject]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    for obj in objects {
+        let p = obj.body.position;
+        let v = obj.body.velocity;
+        for value in [p.x, p.y, p.z, v.x, v.y, v.z] {
+            for byte in value.to_bits().to_le_bytes() {
+                hash = (hash ^ byte as u64).wrapping_mul(PRIME);
+            }
+        }
+    }
+    hash
+}
+
+/// Step with default settings.
+pub fn step(objects: &mut [PhysicsObject], static_obs: &[Aabb], dt: f32) -> Vec<(usize, usize)> {
+    PhysicsWorld::default().step(objects, static_obs, dt)
 }
@@ -0,0 +1,126 @@
+//! GPU frame timing from timestamp queries.
+//!
+//! Timestamps need `wgpu::Features::TIMESTAMP_QUERY`, which many adapters
+//! (the GL backend among them) don't offer. Without it the profiler is
+//! still created but never measures and reports itself unavailable.
+
+use std::time::Duration;
+
+/// Timestamps written per frame: one before the first pass and one after
+/// the last.
+const QUERIES: u32 = 2;
+const BUFFER_SIZE: wgpu::BufferAddress = (QUERIES * wgpu::QUERY_SIZE) as wgpu::BufferAddress;
+
+struct Queries {
+    set: wgpu::QuerySet,
+    /// Where the query set is resolved to on the GPU.
+    resolve: wgpu::Buffer,
+    /// Copy of `resolve` the CPU can map.
+    readback: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick.
+    period: f32,
+}
+
+pub struct GpuProfiler {
+    queries: Option<Queries>,
+    last: Option<Duration>,
+}
+
+impl GpuProfiler {
+    /// Profiler for `device`, measuring only if it has timestamp queries.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return Self::unavailable();
+        }
+        let buffer = |label, usage| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: BUFFER_SIZE,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let queries = Queries {
+            set: device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("GPU Timestamps"),
+                ty: wgpu::QueryType::Timestamp,
+                count: QUERIES,
+            }),
+            resolve: buffer(
+                "Timestamp Resolve Buffer",
+                wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            ),
+            readback: buffer(
+                "Timestamp Readback Buffer",
+                wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            ),
+            period: queue.get_timestamp_period(),
+        };
+        Self {
+            queries: Some(queries),
+            last: None,
+        }
+    }
+
+    /// Profiler that never measures.
+    pub fn unavailable() -> Self {
+        Self {
+            queries: None,
+            last: None,
+        }
+    }
+
+    pub fn available(&self) -> bool {
+        self.queries.is_some()
+    }
+
+    /// Mark the start of the frame's GPU work.
+    pub fn begin(&self, encoder: &mut wgpu::CommandEncoder) {
+        if let Some(q) = &self.queries {
+            encoder.write_timestamp(&q.set, 0);
+        }
+    }
+
+    /// Mark the end of the frame's GPU work and queue the copy `read`
+    /// needs.
+    pub fn end(&self, encoder: &mut wgpu::CommandEncoder) {
+        if let Some(q) = &self.queries {
+            encoder.write_timestamp(&q.set, 1);
+            encoder.resolve_query_set(&q.set, 0..QUERIES, &q.resolve, 0);
+            encoder.copy_buffer_to_buffer(&q.resolve, 0, &q.readback, 0, BUFFER_SIZE);
+        }
+    }
+
+    /// Read back the times written between `begin` and `end` once their
+    /// commands are submitted. Waits for the GPU to finish the frame.
+    pub fn read(&mut self, device: &wgpu::Device) {
+        let Some(q) = &self.queries else {
+            return;
+        };
+        let slice = q.readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let ticks: Vec<u64> = slice
+            .get_mapped_range()
+            .chunks_exact(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        q.readback.unmap();
+        let nanos = ticks[1].saturating_sub(ticks[0]) as f64 * q.period as f64;
+        self.last = Some(Duration::from_nanos(nanos as u64));
+    }
+
+    /// GPU time of the last frame read.
+    pub fn last(&self) -> Option<Duration> {
+        self.last
+    }
+
+    /// One line for the debug HUD.
+    pub fn report(&self) -> String {
+        match (self.available(), self.last) {
+            (false, _) => "gpu: unavailable".to_string(),
+            (true, None) => "gpu: waiting".to_string(),
+            (true, Some(time)) => format!("gpu: {:.3} ms", time.as_secs_f64() * 1000.0),
+        }
+    }
+}
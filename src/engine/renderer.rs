@@ -1,12 +1,29 @@
-use glam::{Mat4, Vec3};
+use super::artifact::{ring_positions, RING_BLOCKS};
+use super::hud::HudTheme;
+use super::mesh::{parse_obj, Mesh, MeshId, MeshInstance, MeshRegistry, CUBE_MESH};
+use super::overlay::{OverlayQuad, OverlayVertex};
+use super::profiler::GpuProfiler;
+use super::text::{self, TextStyle};
+use glam::{Mat4, Quat, Vec3};
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::{Arc, Condvar, Mutex};
 use wgpu::util::DeviceExt;
 use wgpu_glyph::GlyphBrush as WgpuGlyphBrush;
-use wgpu_glyph::{ab_glyph, GlyphBrush, GlyphBrushBuilder, Section, Text};
+use wgpu_glyph::{ab_glyph, GlyphBrushBuilder, Layout, OwnedSection, OwnedText};
 
 const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+/// Vertical field of view of the viewmodel, in degrees.
+const VIEWMODEL_FOV: f32 = 60.0;
+/// Device features used when the adapter has them, and done without
+/// otherwise.
+const OPTIONAL_FEATURES: wgpu::Features =
+    wgpu::Features::TIMESTAMP_QUERY.union(wgpu::Features::POLYGON_MODE_LINE);
+/// Instances the cube instance buffer starts with room for.
+const INITIAL_INSTANCES: usize = 64;
+/// Size of each staging belt chunk, in bytes.
+const STAGING_BELT_CHUNK: wgpu::BufferAddress = 1024;
 
 pub struct Renderer {
     pub surface: Option<wgpu::Surface>,
@@ -16,31 +33,270 @@ pub struct Renderer {
     pub size: winit::dpi::PhysicalSize<u32>,
     pub camera_bind: wgpu::BindGroup,
     camera_buffer: wgpu::Buffer,
+    /// Projection for the viewmodel, which has no view transform.
+    viewmodel_bind: wgpu::BindGroup,
+    viewmodel_camera: wgpu::Buffer,
+    /// Cubes in camera space (looking down -Z), drawn over the scene
+    /// with their own depth buffer clear so they never clip into walls.
+    pub viewmodel: Vec<CubeInstance>,
     pub pipeline: wgpu::RenderPipeline,
-    pub vertex_buffer: wgpu::Buffer,
-    pub index_buffer: wgpu::Buffer,
-    pub num_indices: u32,
+    /// Draws the cube mesh once per `CubeInstance`.
+    pub instance_pipeline: wgpu::RenderPipeline,
+    /// Draws the back faces of enlarged cubes, which shows up as an outline
+    /// around instances with `outline` set.
+    pub outline_pipeline: wgpu::RenderPipeline,
+    pub outline_color: [f32; 3],
+    /// Outline thickness in world units.
+    pub outline_width: f32,
+    /// Outline of highlighted instances, drawn twice as thick.
+    pub highlight_color: [f32; 3],
+    /// Flat quads drawn over the scene and under the HUD text, e.g. damage
+    /// indicators. Hidden along with the rest of the HUD.
+    pub overlay_quads: Vec<OverlayQuad>,
+    /// Crosshair quads, drawn over `overlay_quads`.
+    pub crosshair_quads: Vec<OverlayQuad>,
+    /// Full-screen fade, drawn over the crosshair.
+    pub fade_quad: Option<OverlayQuad>,
+    overlay_pipeline: wgpu::RenderPipeline,
+    /// Meshes instances can be drawn with. `CUBE_MESH` is the cube.
+    pub meshes: MeshRegistry,
+    /// Instances of registered meshes, drawn with the scene after the cubes.
+    pub mesh_instances: Vec<MeshInstance>,
+    /// Draws the floor quad with a procedural grid.
+    pub floor_pipeline: wgpu::RenderPipeline,
+    floor_bind: wgpu::BindGroup,
+    grid_buffer: wgpu::Buffer,
+    /// Depth convention every scene pipeline was built for.
+    depth_mode: DepthMode,
+    /// Scene pipelines draw triangle edges only.
+    wireframe: bool,
+    /// Kept to rebuild the scene pipelines in `set_depth_mode` and
+    /// `set_wireframe`.
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    floor_layout: wgpu::PipelineLayout,
     pub floor_vertex: wgpu::Buffer,
     pub floor_index: wgpu::Buffer,
     pub floor_indices: u32,
     pub artifact_vertex: wgpu::Buffer,
     pub artifact_index: wgpu::Buffer,
     pub artifact_indices: u32,
+    /// Blocks in the ring held by the artifact buffers.
+    artifact_blocks: usize,
     pub default_bind: wgpu::BindGroup,
     pub artifact_bind: wgpu::BindGroup,
     artifact_buffer: wgpu::Buffer,
+    /// Intensity last written to `artifact_buffer`.
+    artifact_intensity: f32,
+    /// Time constant of `fade_artifact`, in seconds: the gap to the target
+    /// shrinks by about two thirds over this long.
+    pub artifact_fade_time: f32,
     pub depth_texture: wgpu::Texture,
     pub depth_view: wgpu::TextureView,
     pub glyph_brush: WgpuGlyphBrush<()>,
+    /// Instances of `render`'s cubes, hulls and meshes.
+    instances: InstanceBuffer,
+    /// `draw_queued` calls made by the last `render`.
+    text_draws: u32,
+    /// Upload memory for glyph vertices, recalled after every submit so
+    /// its chunks are reused from frame to frame.
+    staging_belt: wgpu::util::StagingBelt,
+    /// Measure the GPU time of each frame, if the adapter can. Waits for
+    /// the GPU every frame, so only worth it while someone is looking.
+    pub profile_gpu: bool,
+    pub gpu_profiler: GpuProfiler,
+    /// Same font the glyph brush draws with, kept for measuring text.
+    pub font: ab_glyph::FontArc,
+    /// Multiplier for the distance between wrapped lines of HUD text.
+    pub line_spacing: f32,
+    pub hud_theme: HudTheme,
+    /// Shadow or outline drawn under every piece of HUD text.
+    pub text_style: TextStyle,
+    /// When `false`, `render` draws no text at all, for clean screenshots.
+    pub hud_visible: bool,
+    /// Loaded and reserve rounds shown under the health readout.
+    pub hud_ammo: Option<(u32, u32)>,
+    /// Stamina as a fraction of the maximum.
+    pub hud_stamina: Option<f32>,
+    /// Seconds of spawn protection left.
+    pub hud_protection: Option<f32>,
+    /// Multi-line debug readout in the bottom-left corner.
+    pub hud_debug: Option<String>,
     pub offscreen_texture: Option<wgpu::Texture>,
     pub offscreen_view: Option<wgpu::TextureView>,
+    capture_requested: bool,
+    captured: Option<Vec<u8>>,
 }
 
-#[derive(Clone, Copy)]
+/// Look of the floor grid: lines every `spacing` world units on the xz
+/// plane, drawn over `base_color`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridStyle {
+    pub spacing: f32,
+    pub line_color: [f32; 3],
+    pub base_color: [f32; 3],
+}
+
+impl Default for GridStyle {
+    fn default() -> Self {
+        Self {
+            spacing: 1.0,
+            line_color: [0.6, 0.6, 0.6],
+            base_color: [0.3, 0.3, 0.3],
+        }
+    }
+}
+
+impl GridStyle {
+    fn to_uniform(self) -> GridUniform {
+        let [r, g, b] = self.line_color;
+        let [br, bg, bb] = self.base_color;
+        GridUniform {
+            line_color: [r, g, b, 1.0],
+            base_color: [br, bg, bb, 1.0],
+            spacing: self.spacing,
+            _pad: [0.0; 3],
+        }
+    }
+}
+
+/// How depth is stored and compared. `Reversed` maps the near plane to 1
+/// and the far plane to 0, which spreads float precision more evenly over
+/// distance and keeps far geometry from fighting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DepthMode {
+    /// Clear to 1.0 and keep fragments with less depth.
+    #[default]
+    Standard,
+    /// Clear to 0.0 and keep fragments with greater depth.
+    Reversed,
+}
+
+impl DepthMode {
+    /// Value the depth buffer is cleared to: the far plane.
+    pub fn clear_value(self) -> f32 {
+        match self {
+            Self::Standard => 1.0,
+            Self::Reversed => 0.0,
+        }
+    }
+
+    /// Comparison that lets nearer fragments through.
+    pub fn compare(self) -> wgpu::CompareFunction {
+        match self {
+            Self::Standard => wgpu::CompareFunction::Less,
+            Self::Reversed => wgpu::CompareFunction::Greater,
+        }
+    }
+
+    /// Right-handed perspective projection producing depth in this mode.
+    pub fn perspective(self, fov_y: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        match self {
+            Self::Standard => Mat4::perspective_rh(fov_y, aspect, near, far),
+            Self::Reversed => Mat4::perspective_rh(fov_y, aspect, far, near),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GridUniform {
+    line_color: [f32; 4],
+    base_color: [f32; 4],
+    spacing: f32,
+    _pad: [f32; 3],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct CubeInstance {
+    /// Center of the cube in world space.
     pub position: Vec3,
-    pub size: f32,
+    /// Edge lengths along the cube's local x, y and z.
+    pub size: Vec3,
+    /// Orientation around the center.
+    pub rotation: Quat,
     pub color: [f32; 3],
+    /// Draw a solid outline around the cube's silhouette.
+    pub outline: bool,
+    /// Draw the thicker highlight outline instead, e.g. for the object
+    /// under the crosshair.
+    pub highlight: bool,
+}
+
+impl CubeInstance {
+    /// Axis-aligned cube with all edges `size` long.
+    pub fn new(position: Vec3, size: f32, color: [f32; 3]) -> Self {
+        Self {
+            position,
+            size: Vec3::splat(size),
+            rotation: Quat::IDENTITY,
+            color,
+            outline: false,
+            highlight: false,
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec3) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_outline(mut self) -> Self {
+        self.outline = true;
+        self
+    }
+
+    pub fn with_highlight(mut self) -> Self {
+        self.highlight = true;
+        self
+    }
+
+    /// Model matrix mapping the unit cube mesh onto this instance. The mesh
+    /// sits on y = 0, so it is first shifted down to be centered.
+    pub fn model_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.size, self.rotation, self.position)
+            * Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))
+    }
+
+    fn to_raw(self) -> InstanceRaw {
+        InstanceRaw {
+            model: self.model_matrix().to_cols_array_2d(),
+            color: self.color,
+        }
+    }
+}
+
+/// The same cube as an instance of `CUBE_MESH`. The outline flags are
+/// dropped.
+impl From<CubeInstance> for MeshInstance {
+    fn from(cube: CubeInstance) -> Self {
+        MeshInstance::new(CUBE_MESH, cube.model_matrix(), cube.color)
+    }
+}
+
+/// Edge length of the cube marking a contact point.
+const CONTACT_MARKER_SIZE: f32 = 0.08;
+/// Length and thickness of the bar showing a contact normal.
+const CONTACT_NORMAL_LENGTH: f32 = 0.4;
+const CONTACT_NORMAL_WIDTH: f32 = 0.02;
+
+/// Debug markers for a contact: a small cube at `point` and a thin bar
+/// sticking out of it along `normal`.
+pub fn contact_markers(point: Vec3, normal: Vec3, color: [f32; 3]) -> [CubeInstance; 2] {
+    let normal = normal.normalize_or_zero();
+    let bar = CubeInstance::new(point + normal * CONTACT_NORMAL_LENGTH * 0.5, 1.0, color)
+        .with_size(Vec3::new(
+            CONTACT_NORMAL_WIDTH,
+            CONTACT_NORMAL_LENGTH,
+            CONTACT_NORMAL_WIDTH,
+        ))
+        .with_rotation(Quat::from_rotation_arc(Vec3::Y, normal));
+    [CubeInstance::new(point, CONTACT_MARKER_SIZE, color), bar]
 }
 
 impl Renderer {
@@ -54,20 +310,29 @@ impl Renderer {
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::HighPerformance,
-                compatible_surface: Some(&surface.as_ref().unwrap()),
+                compatible_surface: Some(surface.as_ref().unwrap()),
                 force_fallback_adapter: false,
             })
             .await
             .expect("No adapter");
         let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    features: adapter.features() & OPTIONAL_FEATURES,
+                    ..Default::default()
+                },
+                None,
+            )
             .await
             .expect("device");
+        let gpu_profiler = GpuProfiler::new(&device, &queue);
 
         let surface_caps = surface.as_ref().unwrap().get_capabilities(&adapter);
         let surface_format = surface_caps.formats[0];
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // COPY_SRC нужен, чтобы сохранять кадр окна в скриншот
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (surface_caps.usages & wgpu::TextureUsages::COPY_SRC),
             format: surface_format,
             width: size.width,
             height: size.height,
@@ -118,6 +383,8 @@ impl Renderer {
             }],
             label: Some("camera bind group"),
         });
+        let (viewmodel_camera, viewmodel_bind) =
+            create_viewmodel_camera(&device, &camera_bind_group_layout);
 
         // artifact intensity uniform
         #[repr(C)]
@@ -174,58 +441,48 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("render pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                cull_mode: Some(wgpu::Face::Back),
-                ..Default::default()
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        let pipeline = create_scene_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            config.format,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let instance_pipeline = create_instance_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            config.format,
+            wgpu::Face::Back,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let outline_pipeline = create_instance_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            config.format,
+            wgpu::Face::Front,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let (grid_buffer, floor_bind, floor_layout) =
+            create_grid_bind(&device, &camera_bind_group_layout);
+        let floor_pipeline = create_floor_pipeline(
+            &device,
+            &floor_layout,
+            config.format,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
 
-        let (vertex_buffer, index_buffer, num_indices) = create_cube_buffers(&device);
+        let overlay_pipeline = create_overlay_pipeline(&device, config.format);
+        let meshes = MeshRegistry::new(create_cube_mesh(&device));
+        let instances = InstanceBuffer::new(&device, INITIAL_INSTANCES);
         let (floor_vertex, floor_index, floor_indices) = create_floor_buffers(&device);
-        let (artifact_vertex, artifact_index, artifact_indices) = create_artifact_buffers(&device);
-
-        // Offscreen texture
-        let offscreen_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Offscreen Texture"),
-            size: wgpu::Extent3d {
-                width: size.width,
-                height: size.height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: surface_format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
-            view_formats: &[],
-        });
-        let offscreen_view = offscreen_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (artifact_vertex, artifact_index, artifact_indices) =
+            create_artifact_buffers(&device, RING_BLOCKS);
 
         // Glyph brush
         let font_path = "assets/DejaVuSans.ttf";
@@ -308,7 +565,8 @@ impl Renderer {
                     std::process::exit(1);
                 }
             };
-        let glyph_brush = GlyphBrushBuilder::using_font(font).build(&device, surface_format);
+        let glyph_brush =
+            GlyphBrushBuilder::using_font(font.clone()).build(&device, surface_format);
 
         Self {
             surface,
@@ -318,24 +576,62 @@ impl Renderer {
             size,
             camera_bind,
             camera_buffer,
+            viewmodel_bind,
+            viewmodel_camera,
+            viewmodel: Vec::new(),
             pipeline,
-            vertex_buffer,
-            index_buffer,
-            num_indices,
+            instance_pipeline,
+            outline_pipeline,
+            outline_color: [1.0, 1.0, 1.0],
+            outline_width: 0.03,
+            highlight_color: [1.0, 0.8, 0.2],
+            floor_pipeline,
+            floor_bind,
+            grid_buffer,
+            depth_mode: DepthMode::default(),
+            wireframe: false,
+            shader,
+            pipeline_layout,
+            floor_layout,
+            overlay_quads: Vec::new(),
+            crosshair_quads: Vec::new(),
+            fade_quad: None,
+            overlay_pipeline,
+            meshes,
+            mesh_instances: Vec::new(),
             floor_vertex,
             floor_index,
             floor_indices,
             artifact_vertex,
             artifact_index,
             artifact_indices,
+            artifact_blocks: RING_BLOCKS,
             default_bind,
             artifact_bind,
             artifact_buffer,
+            artifact_intensity: 0.2,
+            artifact_fade_time: 0.15,
             depth_texture,
             depth_view,
             glyph_brush,
+            instances,
+            text_draws: 0,
+            staging_belt: wgpu::util::StagingBelt::new(STAGING_BELT_CHUNK),
+            profile_gpu: false,
+            gpu_profiler,
+            font,
+            line_spacing: 1.0,
+            hud_theme: HudTheme::default(),
+            text_style: TextStyle::default(),
+            hud_visible: true,
+            hud_ammo: None,
+            hud_stamina: None,
+            hud_protection: None,
+            hud_debug: None,
             offscreen_texture: None,
             offscreen_view: None,
+            capture_requested: false,
+            captured: None,
         }
     }
 
@@ -353,9 +649,16 @@ impl Renderer {
             .await
             .expect("No adapter");
         let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    features: adapter.features() & OPTIONAL_FEATURES,
+                    ..Default::default()
+                },
+                None,
+            )
             .await
             .expect("device");
+        let gpu_profiler = GpuProfiler::new(&device, &queue);
         let texture_format = wgpu::TextureFormat::Rgba8UnormSrgb;
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -405,6 +708,8 @@ impl Renderer {
             }],
             label: Some("camera bind group"),
         });
+        let (viewmodel_camera, viewmodel_bind) =
+            create_viewmodel_camera(&device, &camera_bind_group_layout);
         #[repr(C)]
         #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
         struct ArtifactUniform {
@@ -456,40 +761,47 @@ impl Renderer {
             bind_group_layouts: &[&camera_bind_group_layout, &artifact_bind_group_layout],
             push_constant_ranges: &[],
         });
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("render pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[Vertex::desc()],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: texture_format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                cull_mode: Some(wgpu::Face::Back),
-                ..Default::default()
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
-        let (vertex_buffer, index_buffer, num_indices) = create_cube_buffers(&device);
+        let pipeline = create_scene_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            texture_format,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let instance_pipeline = create_instance_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            texture_format,
+            wgpu::Face::Back,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let outline_pipeline = create_instance_pipeline(
+            &device,
+            &pipeline_layout,
+            &shader,
+            texture_format,
+            wgpu::Face::Front,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let (grid_buffer, floor_bind, floor_layout) =
+            create_grid_bind(&device, &camera_bind_group_layout);
+        let floor_pipeline = create_floor_pipeline(
+            &device,
+            &floor_layout,
+            texture_format,
+            DepthMode::default(),
+            wgpu::PolygonMode::Fill,
+        );
+        let overlay_pipeline = create_overlay_pipeline(&device, config.format);
+        let meshes = MeshRegistry::new(create_cube_mesh(&device));
+        let instances = InstanceBuffer::new(&device, INITIAL_INSTANCES);
         let (floor_vertex, floor_index, floor_indices) = create_floor_buffers(&device);
-        let (artifact_vertex, artifact_index, artifact_indices) = create_artifact_buffers(&device);
+        let (artifact_vertex, artifact_index, artifact_indices) =
+            create_artifact_buffers(&device, RING_BLOCKS);
 
         // Offscreen texture
         let offscreen_texture = device.create_texture(&wgpu::TextureDescriptor {
@@ -512,7 +824,8 @@ impl Renderer {
         let font_path = "assets/DejaVuSans.ttf";
         let font =
             ab_glyph::FontArc::try_from_vec(fs::read(font_path).expect("read font file")).unwrap();
-        let glyph_brush = GlyphBrushBuilder::using_font(font).build(&device, texture_format);
+        let glyph_brush =
+            GlyphBrushBuilder::using_font(font.clone()).build(&device, texture_format);
         Self {
             surface: None,
             device,
@@ -521,24 +834,62 @@ impl Renderer {
             size: winit::dpi::PhysicalSize::new(width, height),
             camera_bind,
             camera_buffer,
+            viewmodel_bind,
+            viewmodel_camera,
+            viewmodel: Vec::new(),
             pipeline,
-            vertex_buffer,
-            index_buffer,
-            num_indices,
+            instance_pipeline,
+            outline_pipeline,
+            outline_color: [1.0, 1.0, 1.0],
+            outline_width: 0.03,
+            highlight_color: [1.0, 0.8, 0.2],
+            floor_pipeline,
+            floor_bind,
+            grid_buffer,
+            depth_mode: DepthMode::default(),
+            wireframe: false,
+            shader,
+            pipeline_layout,
+            floor_layout,
+            overlay_quads: Vec::new(),
+            crosshair_quads: Vec::new(),
+            fade_quad: None,
+            overlay_pipeline,
+            meshes,
+            mesh_instances: Vec::new(),
             floor_vertex,
             floor_index,
             floor_indices,
             artifact_vertex,
             artifact_index,
             artifact_indices,
+            artifact_blocks: RING_BLOCKS,
             default_bind,
             artifact_bind,
             artifact_buffer,
+            artifact_intensity: 0.2,
+            artifact_fade_time: 0.15,
             depth_texture,
             depth_view,
             glyph_brush,
+            instances,
+            text_draws: 0,
+            staging_belt: wgpu::util::StagingBelt::new(STAGING_BELT_CHUNK),
+            profile_gpu: false,
+            gpu_profiler,
+            font,
+            line_spacing: 1.0,
+            hud_theme: HudTheme::default(),
+            text_style: TextStyle::default(),
+            hud_visible: true,
+            hud_ammo: None,
+            hud_stamina: None,
+            hud_protection: None,
+            hud_debug: None,
             offscreen_texture: Some(offscreen_texture),
             offscreen_view: Some(offscreen_view),
+            capture_requested: false,
+            captured: None,
         }
     }
 
@@ -569,7 +920,25 @@ impl Renderer {
             .write_buffer(&self.camera_buffer, 0, bytemuck::bytes_of(&data));
     }
 
-    pub fn update_artifact(&self, intensity: f32) {
+    /// Move the artifact intensity toward `target` instead of jumping to
+    /// it, so activation changes fade in and out.
+    pub fn fade_artifact(&mut self, target: f32, dt: f32) {
+        let keep = if self.artifact_fade_time > 0.0 {
+            (-dt / self.artifact_fade_time).exp()
+        } else {
+            0.0
+        };
+        let intensity = target + (self.artifact_intensity - target) * keep;
+        self.update_artifact(intensity);
+    }
+
+    pub fn artifact_intensity(&self) -> f32 {
+        self.artifact_intensity
+    }
+
+    /// Set the artifact intensity right away.
+    pub fn update_artifact(&mut self, intensity: f32) {
+        self.artifact_intensity = intensity;
         #[repr(C)]
         #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
         struct ArtifactUniform {
@@ -580,72 +949,531 @@ impl Renderer {
             .write_buffer(&self.artifact_buffer, 0, bytemuck::bytes_of(&data));
     }
 
-    pub fn render_overlay_text(
+    pub fn set_grid(&self, style: GridStyle) {
+        let data = style.to_uniform();
+        self.queue
+            .write_buffer(&self.grid_buffer, 0, bytemuck::bytes_of(&data));
+    }
+
+    pub fn depth_mode(&self) -> DepthMode {
+        self.depth_mode
+    }
+
+    /// Rebuild the scene pipelines for `mode`. Projections passed to
+    /// `update_camera` have to come from `perspective` to match.
+    pub fn set_depth_mode(&mut self, mode: DepthMode) {
+        self.depth_mode = mode;
+        self.rebuild_scene_pipelines();
+    }
+
+    /// Whether the scene is drawn as wireframe.
+    pub fn wireframe(&self) -> bool {
+        self.wireframe
+    }
+
+    /// Draw the floor and every mesh as outlines of their triangles, or
+    /// filled again. Adapters without `POLYGON_MODE_LINE` stay filled;
+    /// returns whether wireframe is on afterwards.
+    pub fn set_wireframe(&mut self, on: bool) -> bool {
+        let on = on
+            && self
+                .device
+                .features()
+                .contains(wgpu::Features::POLYGON_MODE_LINE);
+        if on != self.wireframe {
+            self.wireframe = on;
+            self.rebuild_scene_pipelines();
+        }
+        on
+    }
+
+    /// Recreate every pipeline drawing into the depth buffer for the
+    /// current depth mode and wireframe setting.
+    fn rebuild_scene_pipelines(&mut self) {
+        let format = self.config.format;
+        let depth = self.depth_mode;
+        let polygon_mode = if self.wireframe {
+            wgpu::PolygonMode::Line
+        } else {
+            wgpu::PolygonMode::Fill
+        };
+        self.pipeline = create_scene_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            &self.shader,
+            format,
+            depth,
+            polygon_mode,
+        );
+        self.instance_pipeline = create_instance_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            &self.shader,
+            format,
+            wgpu::Face::Back,
+            depth,
+            polygon_mode,
+        );
+        self.outline_pipeline = create_instance_pipeline(
+            &self.device,
+            &self.pipeline_layout,
+            &self.shader,
+            format,
+            wgpu::Face::Front,
+            depth,
+            polygon_mode,
+        );
+        self.floor_pipeline = create_floor_pipeline(
+            &self.device,
+            &self.floor_layout,
+            format,
+            depth,
+            polygon_mode,
+        );
+    }
+
+    /// Scene projection for the current size and depth mode.
+    pub fn perspective(&self, fov_y: f32, near: f32, far: f32) -> Mat4 {
+        let aspect = self.size.width as f32 / self.size.height as f32;
+        self.depth_mode.perspective(fov_y, aspect, near, far)
+    }
+
+    /// Blocks in the artifact ring geometry.
+    pub fn artifact_blocks(&self) -> usize {
+        self.artifact_blocks
+    }
+
+    /// Rebuild the artifact ring geometry with `count` blocks.
+    pub fn set_artifact_blocks(&mut self, count: usize) {
+        let (vertex, index, indices) = create_artifact_buffers(&self.device, count);
+        self.artifact_vertex = vertex;
+        self.artifact_index = index;
+        self.artifact_indices = indices;
+        self.artifact_blocks = count;
+    }
+
+    /// Upload a mesh to draw `mesh_instances` of.
+    pub fn register_mesh(&mut self, vertices: &[Vertex], indices: &[u16]) -> MeshId {
+        self.meshes.register(&self.device, vertices, indices)
+    }
+
+    /// Parse the OBJ file at `path` and upload it as a mesh.
+    pub fn load_obj(&mut self, path: &Path) -> Result<MeshId, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let data = parse_obj(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(self.meshes.register_data(&self.device, &data))
+    }
+
+    /// Instance data for `cubes`, then the outline hulls of the cubes that
+    /// want one, then `mesh_instances` grouped by mesh.
+    fn build_instances(&self, cubes: &[CubeInstance]) -> (Vec<InstanceRaw>, InstanceLayout) {
+        let mut instances: Vec<InstanceRaw> = cubes.iter().map(|c| c.to_raw()).collect();
+        for c in cubes.iter().filter(|c| c.outline || c.highlight) {
+            let (width, color) = if c.highlight {
+                (self.outline_width * 2.0, self.highlight_color)
+            } else {
+                (self.outline_width, self.outline_color)
+            };
+            let hull = CubeInstance {
+                size: c.size + Vec3::splat(2.0 * width),
+                color,
+                ..*c
+            };
+            instances.push(hull.to_raw());
+        }
+        let mut layout = InstanceLayout {
+            cubes: 0..cubes.len() as u32,
+            hulls: cubes.len() as u32..instances.len() as u32,
+            meshes: Vec::new(),
+        };
+        let mut meshes = self.mesh_instances.clone();
+        meshes.sort_by_key(|m| m.mesh_id);
+        for m in meshes {
+            let index = instances.len() as u32;
+            instances.push(InstanceRaw {
+                model: m.transform.to_cols_array_2d(),
+                color: m.color,
+            });
+            match layout.meshes.last_mut() {
+                Some((id, range)) if *id == m.mesh_id => range.end = index + 1,
+                _ => layout.meshes.push((m.mesh_id, index..index + 1)),
+            }
+        }
+        (instances, layout)
+    }
+
+    /// Floor, base cube, instanced cubes with their outline hulls and mesh
+    /// instances. Shared by the windowed and headless paths of `render`.
+    fn draw_scene<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        instance_buffer: &'a wgpu::Buffer,
+        layout: &InstanceLayout,
+    ) {
+        render_pass.set_pipeline(&self.floor_pipeline);
+        render_pass.set_bind_group(0, &self.camera_bind, &[]);
+        render_pass.set_bind_group(1, &self.floor_bind, &[]);
+        render_pass.set_vertex_buffer(0, self.floor_vertex.slice(..));
+        render_pass.set_index_buffer(self.floor_index.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.floor_indices, 0, 0..1);
+
+        let cube = self.meshes.cube();
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(1, &self.default_bind, &[]);
+        render_pass.set_vertex_buffer(0, cube.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(cube.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..cube.num_indices, 0, 0..1);
+        render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+        render_pass.set_pipeline(&self.instance_pipeline);
+        if !layout.cubes.is_empty() {
+            render_pass.draw_indexed(0..cube.num_indices, 0, layout.cubes.clone());
+        }
+        for (id, range) in &layout.meshes {
+            // Экземпляры незарегистрированной сетки просто не рисуются
+            let Some(mesh) = self.meshes.get(*id) else {
+                continue;
+            };
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            render_pass.draw_indexed(0..mesh.num_indices, 0, range.clone());
+        }
+        if !layout.hulls.is_empty() {
+            render_pass.set_pipeline(&self.outline_pipeline);
+            render_pass.set_vertex_buffer(0, cube.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(cube.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            render_pass.draw_indexed(0..cube.num_indices, 0, layout.hulls.clone());
+        }
+    }
+
+    /// Draw `viewmodel` over the finished scene in `view`.
+    fn draw_viewmodel(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        if self.viewmodel.is_empty() {
+            return;
+        }
+        let aspect = self.size.width as f32 / self.size.height as f32;
+        let proj = self
+            .depth_mode
+            .perspective(VIEWMODEL_FOV.to_radians(), aspect, 0.01, 10.0);
+        self.queue.write_buffer(
+            &self.viewmodel_camera,
+            0,
+            bytemuck::bytes_of(&proj.to_cols_array()),
+        );
+        let instances: Vec<InstanceRaw> = self.viewmodel.iter().map(|c| c.to_raw()).collect();
+        let instance_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Viewmodel Instance Buffer"),
+                contents: bytemuck::cast_slice(&instances),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Viewmodel Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(self.depth_mode.clear_value()),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        render_pass.set_pipeline(&self.instance_pipeline);
+        render_pass.set_bind_group(0, &self.viewmodel_bind, &[]);
+        render_pass.set_bind_group(1, &self.default_bind, &[]);
+        let cube = self.meshes.cube();
+        render_pass.set_vertex_buffer(0, cube.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+        render_pass.set_index_buffer(cube.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..cube.num_indices, 0, 0..instances.len() as u32);
+    }
+
+    /// Draw `overlay_quads`, `crosshair_quads` and then `fade_quad` over
+    /// whatever is already in `view`.
+    fn draw_overlay(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        if self.overlay_quads.is_empty()
+            && self.crosshair_quads.is_empty()
+            && self.fade_quad.is_none()
+        {
+            return;
+        }
+        let screen = glam::Vec2::new(self.size.width as f32, self.size.height as f32);
+        let vertices: Vec<OverlayVertex> = self
+            .overlay_quads
+            .iter()
+            .chain(&self.crosshair_quads)
+            .chain(&self.fade_quad)
+            .flat_map(|q| q.vertices(screen))
+            .collect();
+        let vertex_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Overlay Vertex Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Overlay Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.overlay_pipeline);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..vertices.len() as u32, 0..1);
+    }
+
+    /// Sections for `text` wrapped to `max_width`, one per line, so that line
+    /// spacing follows `line_spacing` instead of the brush layout.
+    pub fn text_block_sections(
+        &self,
+        text: &str,
+        position: (f32, f32),
+        max_width: f32,
+        scale: f32,
+        color: [f32; 4],
+    ) -> Vec<OwnedSection> {
+        let step = text::line_height(&self.font, scale) * self.line_spacing;
+        text::wrap_text(&self.font, text, scale, max_width)
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| OwnedSection {
+                screen_position: (position.0, position.1 + i as f32 * step),
+                bounds: (f32::INFINITY, f32::INFINITY),
+                text: vec![OwnedText::new(line).with_color(color).with_scale(scale)],
+                layout: Layout::default_single_line(),
+            })
+            .collect()
+    }
+
+    /// Queue `text` wrapped to `max_width`, see `text_block_sections`.
+    pub fn queue_text_block(
         &mut self,
         text: &str,
-        encoder: &mut wgpu::CommandEncoder,
-        view: &wgpu::TextureView,
-        staging_belt: &mut wgpu::util::StagingBelt,
+        position: (f32, f32),
+        max_width: f32,
+        scale: f32,
+        color: [f32; 4],
     ) {
-        let section = Section {
-            screen_position: (30.0, 30.0),
+        for section in self.text_block_sections(text, position, max_width, scale, color) {
+            self.queue_section(&section);
+        }
+    }
+
+    /// Queue `section` on the glyph brush together with the backing copies
+    /// of `text_style`.
+    pub fn queue_section(&mut self, section: &OwnedSection) {
+        for section in self.text_style.apply(section) {
+            self.glyph_brush.queue(&section);
+        }
+    }
+
+    /// Sections of the overlay message in the top-left corner.
+    pub fn overlay_sections(&self, text: &str) -> Vec<OwnedSection> {
+        self.text_block_sections(
+            text,
+            (30.0, 30.0),
+            self.size.width as f32 - 60.0,
+            36.0,
+            self.hud_theme.text,
+        )
+    }
+
+    /// Section with the "Health: N" readout.
+    pub fn health_section(&self, health: i32) -> OwnedSection {
+        OwnedSection {
+            screen_position: (30.0, 70.0),
             bounds: (
                 self.size.width as f32 - 60.0,
                 self.size.height as f32 - 60.0,
             ),
-            text: vec![Text::new(text)
-                .with_color([1.0, 1.0, 0.5, 1.0])
-                .with_scale(36.0)],
-            ..Section::default()
+            text: vec![
+                OwnedText::new("Health: ")
+                    .with_color(self.hud_theme.accent)
+                    .with_scale(28.0),
+                OwnedText::new(health.to_string())
+                    .with_color(self.hud_theme.health_color(health))
+                    .with_scale(28.0),
+            ],
+            ..OwnedSection::default()
+        }
+    }
+
+    /// Section with the "Ammo: loaded / reserve" readout.
+    pub fn ammo_section(&self, loaded: u32, reserve: u32) -> OwnedSection {
+        let color = if loaded == 0 {
+            self.hud_theme.health_low
+        } else {
+            self.hud_theme.text
         };
-        self.glyph_brush.queue(section);
-        self.glyph_brush
-            .draw_queued(
-                &self.device,
-                staging_belt,
-                encoder,
-                view,
-                self.size.width,
-                self.size.height,
-            )
-            .expect("Draw glyphs");
+        OwnedSection {
+            screen_position: (30.0, 105.0),
+            bounds: (
+                self.size.width as f32 - 60.0,
+                self.size.height as f32 - 60.0,
+            ),
+            text: vec![
+                OwnedText::new("Ammo: ")
+                    .with_color(self.hud_theme.accent)
+                    .with_scale(28.0),
+                OwnedText::new(format!("{loaded} / {reserve}"))
+                    .with_color(color)
+                    .with_scale(28.0),
+            ],
+            ..OwnedSection::default()
+        }
     }
 
-    pub fn render_health_text(
+    /// Section with the "Stamina: N%" readout.
+    pub fn stamina_section(&self, fraction: f32) -> OwnedSection {
+        let percent = (fraction.clamp(0.0, 1.0) * 100.0).round() as i32;
+        OwnedSection {
+            screen_position: (30.0, 140.0),
+            bounds: (
+                self.size.width as f32 - 60.0,
+                self.size.height as f32 - 60.0,
+            ),
+            text: vec![
+                OwnedText::new("Stamina: ")
+                    .with_color(self.hud_theme.accent)
+                    .with_scale(28.0),
+                OwnedText::new(format!("{percent}%"))
+                    .with_color(self.hud_theme.health_color(percent))
+                    .with_scale(28.0),
+            ],
+            ..OwnedSection::default()
+        }
+    }
+
+    /// Section with the "Protected: N.Ns" readout.
+    pub fn protection_section(&self, seconds: f32) -> OwnedSection {
+        OwnedSection {
+            screen_position: (30.0, 175.0),
+            bounds: (
+                self.size.width as f32 - 60.0,
+                self.size.height as f32 - 60.0,
+            ),
+            text: vec![
+                OwnedText::new("Protected: ")
+                    .with_color(self.hud_theme.accent)
+                    .with_scale(28.0),
+                OwnedText::new(format!("{seconds:.1}s"))
+                    .with_color(self.hud_theme.text)
+                    .with_scale(28.0),
+            ],
+            ..OwnedSection::default()
+        }
+    }
+
+    /// Sections of the debug readout, one per line, anchored to the
+    /// bottom-left corner.
+    pub fn debug_sections(&self, text: &str) -> Vec<OwnedSection> {
+        const SCALE: f32 = 20.0;
+        let lines = text.lines().count() as f32;
+        let height = lines * text::line_height(&self.font, SCALE) * self.line_spacing;
+        self.text_block_sections(
+            text,
+            (30.0, self.size.height as f32 - 30.0 - height),
+            self.size.width as f32 - 60.0,
+            SCALE,
+            self.hud_theme.text,
+        )
+    }
+
+    /// Every HUD text section of a frame in drawing order: the overlay
+    /// message, the readouts and the debug text.
+    pub fn hud_sections(&self, overlay_text: Option<&str>, health: i32) -> Vec<OwnedSection> {
+        let mut sections = overlay_text
+            .map(|text| self.overlay_sections(text))
+            .unwrap_or_default();
+        sections.push(self.health_section(health));
+        sections.extend(
+            self.hud_ammo
+                .map(|(loaded, reserve)| self.ammo_section(loaded, reserve)),
+        );
+        sections.extend(self.hud_stamina.map(|f| self.stamina_section(f)));
+        sections.extend(self.hud_protection.map(|s| self.protection_section(s)));
+        if let Some(text) = &self.hud_debug {
+            sections.extend(self.debug_sections(text));
+        }
+        sections
+    }
+
+    /// Queue all of `hud_sections` and draw them in one glyph pass.
+    fn draw_hud_text(
         &mut self,
+        overlay_text: Option<&str>,
         health: i32,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
-        staging_belt: &mut wgpu::util::StagingBelt,
     ) {
-        let text = format!("Health: {}", health);
-        let section = Section {
-            screen_position: (30.0, 70.0),
-            bounds: (
-                self.size.width as f32 - 60.0,
-                self.size.height as f32 - 60.0,
-            ),
-            text: vec![Text::new(&text)
-                .with_color([0.0, 1.0, 0.0, 1.0])
-                .with_scale(28.0)],
-            ..Section::default()
-        };
-        self.glyph_brush.queue(section);
+        for section in self.hud_sections(overlay_text, health) {
+            self.queue_section(&section);
+        }
         self.glyph_brush
             .draw_queued(
                 &self.device,
-                staging_belt,
+                &mut self.staging_belt,
                 encoder,
                 view,
                 self.size.width,
                 self.size.height,
             )
             .expect("Draw glyphs");
+        self.text_draws += 1;
+    }
+
+    fn begin_gpu_profile(&self, encoder: &mut wgpu::CommandEncoder) {
+        if self.profile_gpu {
+            self.gpu_profiler.begin(encoder);
+        }
+    }
+
+    fn end_gpu_profile(&self, encoder: &mut wgpu::CommandEncoder) {
+        if self.profile_gpu {
+            self.gpu_profiler.end(encoder);
+        }
+    }
+
+    fn read_gpu_profile(&mut self) {
+        if self.profile_gpu {
+            self.gpu_profiler.read(&self.device);
+        }
+    }
+
+    /// Cube instances the instance buffer has room for.
+    pub fn instance_capacity(&self) -> usize {
+        self.instances.capacity
+    }
+
+    /// Times the instance buffer has been allocated, counting the first.
+    pub fn instance_allocations(&self) -> u32 {
+        self.instances.allocations
+    }
+
+    /// Glyph passes the last `render` made.
+    pub fn text_draws(&self) -> u32 {
+        self.text_draws
     }
 
     pub fn render(&mut self, overlay_text: Option<&str>, health: i32, cubes: &[CubeInstance]) {
-        use wgpu::util::StagingBelt;
-        let mut staging_belt = StagingBelt::new(1024);
+        self.text_draws = 0;
+        let (instances, layout) = self.build_instances(cubes);
+        self.instances.write(&self.device, &self.queue, &instances);
         if let Some(surface) = &self.surface {
             let output = match surface.get_current_texture() {
                 Ok(frame) => frame,
@@ -669,6 +1497,7 @@ impl Renderer {
                 .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                     label: Some("Render Encoder"),
                 });
+            self.begin_gpu_profile(&mut encoder);
             {
                 let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("Render Pass"),
@@ -683,36 +1512,37 @@ impl Renderer {
                     depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                         view: &self.depth_view,
                         depth_ops: Some(wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(1.0),
+                            load: wgpu::LoadOp::Clear(self.depth_mode.clear_value()),
                             store: true,
                         }),
                         stencil_ops: None,
                     }),
                 });
-                render_pass.set_pipeline(&self.pipeline);
-                render_pass.set_bind_group(0, &self.camera_bind, &[]);
-                render_pass.set_bind_group(1, &self.default_bind, &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-                render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
-                // ...добавьте рендер кубов, артефактов и т.д. по вашей логике...
+                self.draw_scene(&mut render_pass, &self.instances.buffer, &layout);
             }
-            if let Some(text) = overlay_text {
-                self.render_overlay_text(text, &mut encoder, &view, &mut staging_belt);
+            self.draw_viewmodel(&mut encoder, &view);
+            if self.hud_visible {
+                self.draw_overlay(&mut encoder, &view);
+                self.draw_hud_text(overlay_text, health, &mut encoder, &view);
             }
-            self.render_health_text(health, &mut encoder, &view, &mut staging_belt);
-            staging_belt.finish();
+            self.staging_belt.finish();
+            self.end_gpu_profile(&mut encoder);
             self.queue.submit(Some(encoder.finish()));
+            self.staging_belt.recall();
+            self.read_gpu_profile();
+            if self.capture_requested && self.config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+                self.captured = Some(self.read_texture_rgba8(&output.texture));
+            }
             output.present();
         } else {
             // Headless/offscreen: рендерим в offscreen_view
-            let mut view = self.offscreen_view.take().unwrap();
+            let view = self.offscreen_view.take().unwrap();
             let mut encoder = self
                 .device
                 .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                     label: Some("Render Encoder (Headless)"),
                 });
+            self.begin_gpu_profile(&mut encoder);
             {
                 let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("Render Pass"),
@@ -727,47 +1557,71 @@ impl Renderer {
                     depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                         view: &self.depth_view,
                         depth_ops: Some(wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(1.0),
+                            load: wgpu::LoadOp::Clear(self.depth_mode.clear_value()),
                             store: true,
                         }),
                         stencil_ops: None,
                     }),
                 });
-                render_pass.set_pipeline(&self.pipeline);
-                render_pass.set_bind_group(0, &self.camera_bind, &[]);
-                render_pass.set_bind_group(1, &self.default_bind, &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-                render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
-                // ...добавьте рендер кубов, артефактов и т.д. по вашей логике...
+                self.draw_scene(&mut render_pass, &self.instances.buffer, &layout);
             }
-            if let Some(text) = overlay_text {
-                self.render_overlay_text(text, &mut encoder, &view, &mut staging_belt);
+            self.draw_viewmodel(&mut encoder, &view);
+            if self.hud_visible {
+                self.draw_overlay(&mut encoder, &view);
+                self.draw_hud_text(overlay_text, health, &mut encoder, &view);
             }
-            self.render_health_text(health, &mut encoder, &view, &mut staging_belt);
-            staging_belt.finish();
+            self.staging_belt.finish();
+            self.end_gpu_profile(&mut encoder);
             self.queue.submit(Some(encoder.finish()));
+            self.staging_belt.recall();
+            self.read_gpu_profile();
             self.device.poll(wgpu::Maintain::Wait);
             self.offscreen_view = Some(view);
+            if self.capture_requested {
+                self.captured = Some(self.get_frame_rgba8());
+            }
         }
+        self.capture_requested = false;
+    }
+
+    /// Keep a copy of the next rendered frame, to be picked up with
+    /// `take_capture`. Works for windowed and headless renderers.
+    pub fn request_capture(&mut self) {
+        self.capture_requested = true;
     }
 
+    /// RGBA8 pixels of the frame captured after `request_capture`, row by
+    /// row from the top. `None` if no capture is pending or the surface
+    /// can't be copied from.
+    pub fn take_capture(&mut self) -> Option<Vec<u8>> {
+        self.captured.take()
+    }
+
+    /// Pixels of the last frame of a headless renderer. A window surface
+    /// can only be read while the frame is alive, so windowed renderers go
+    /// through `request_capture` instead.
     pub fn get_frame_rgba8(&self) -> Vec<u8> {
+        let texture = self
+            .offscreen_texture
+            .as_ref()
+            .expect("get_frame_rgba8 needs a headless renderer");
+        self.read_texture_rgba8(texture)
+    }
+
+    fn read_texture_rgba8(&self, src_texture: &wgpu::Texture) -> Vec<u8> {
         let width = self.size.width;
         let height = self.size.height;
-        let buffer_size = (width * height * 4) as wgpu::BufferAddress;
+        // Строки в буфере копирования выравниваются по COPY_BYTES_PER_ROW_ALIGNMENT
+        let row_bytes = 4 * width;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = row_bytes.div_ceil(align) * align;
+        let buffer_size = (padded_row * height) as wgpu::BufferAddress;
         let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Screenshot Buffer"),
             size: buffer_size,
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
             mapped_at_creation: false,
         });
-        let src_texture = if let Some(surface) = &self.surface {
-            &surface.get_current_texture().unwrap().texture
-        } else {
-            self.offscreen_texture.as_ref().unwrap()
-        };
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -784,7 +1638,7 @@ impl Renderer {
                 buffer: &buffer,
                 layout: wgpu::ImageDataLayout {
                     offset: 0,
-                    bytes_per_row: Some(4 * width),
+                    bytes_per_row: Some(padded_row),
                     rows_per_image: Some(height),
                 },
             },
@@ -795,38 +1649,56 @@ impl Renderer {
             },
         );
         self.queue.submit(Some(encoder.finish()));
-        self.device.poll(wgpu::Maintain::Wait);
         // map_async через callback и condvar
         let slice = buffer.slice(..);
         let pair = Arc::new((Mutex::new(false), Condvar::new()));
         let pair2 = pair.clone();
-        slice.map_async(wgpu::MapMode::Read, move |res| {
+        slice.map_async(wgpu::MapMode::Read, move |_res| {
             let (lock, cvar) = &*pair2;
             let mut done = lock.lock().unwrap();
             *done = true;
             cvar.notify_one();
         });
+        // Колбэк map_async вызывается только внутри poll, поэтому ждём после него
+        self.device.poll(wgpu::Maintain::Wait);
         // Ждём завершения map_async
         let (lock, cvar) = &*pair;
         let mut done = lock.lock().unwrap();
         while !*done {
             done = cvar.wait(done).unwrap();
         }
-        let data = slice.get_mapped_range().to_vec();
-        drop(slice);
+        let mut data: Vec<u8> = slice
+            .get_mapped_range()
+            .chunks(padded_row as usize)
+            .flat_map(|row| &row[..row_bytes as usize])
+            .copied()
+            .collect();
         buffer.unmap();
+        // Поверхность окна часто в BGRA — приводим к RGBA
+        if matches!(
+            self.config.format,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        ) {
+            for px in data.chunks_exact_mut(4) {
+                px.swap(0, 2);
+            }
+        }
         data
     }
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Vertex {
     position: [f32; 3],
     color: [f32; 3],
 }
 
 impl Vertex {
+    pub const fn new(position: [f32; 3], color: [f32; 3]) -> Self {
+        Self { position, color }
+    }
+
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
         use std::mem;
         wgpu::VertexBufferLayout {
@@ -848,7 +1720,308 @@ impl Vertex {
     }
 }
 
-fn create_cube_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, u32) {
+/// Where each kind of instance sits in the buffer built by
+/// `build_instances`.
+struct InstanceLayout {
+    cubes: Range<u32>,
+    hulls: Range<u32>,
+    meshes: Vec<(MeshId, Range<u32>)>,
+}
+
+/// Vertex buffer for `InstanceRaw`s kept from frame to frame. It is only
+/// replaced, with twice the room needed, when a frame has more instances
+/// than fit.
+struct InstanceBuffer {
+    buffer: wgpu::Buffer,
+    capacity: usize,
+    /// Buffers created so far, the first one included.
+    allocations: u32,
+}
+
+impl InstanceBuffer {
+    fn new(device: &wgpu::Device, capacity: usize) -> Self {
+        Self {
+            buffer: Self::allocate(device, capacity),
+            capacity,
+            allocations: 1,
+        }
+    }
+
+    fn allocate(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cube Instance Buffer"),
+            size: (capacity * std::mem::size_of::<InstanceRaw>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Upload `instances` to the start of the buffer, growing it first if
+    /// they don't fit.
+    fn write(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, instances: &[InstanceRaw]) {
+        if instances.len() > self.capacity {
+            self.capacity = instances.len() * 2;
+            self.buffer = Self::allocate(device, self.capacity);
+            self.allocations += 1;
+        }
+        if !instances.is_empty() {
+            queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(instances));
+        }
+    }
+}
+
+/// Per-instance data for `vs_instanced`: model matrix columns and color.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct InstanceRaw {
+    model: [[f32; 4]; 4],
+    color: [f32; 3],
+}
+
+impl InstanceRaw {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+        2 => Float32x4,
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Float32x3,
+    ];
+
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceRaw>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Pipeline for meshes drawn without instances, such as the base cube.
+fn create_scene_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    depth: DepthMode,
+    polygon_mode: wgpu::PolygonMode,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("render pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main",
+            buffers: &[Vertex::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            cull_mode: Some(wgpu::Face::Back),
+            polygon_mode,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: depth.compare(),
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+fn create_instance_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    cull: wgpu::Face,
+    depth: DepthMode,
+    polygon_mode: wgpu::PolygonMode,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("instance pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_instanced",
+            buffers: &[Vertex::desc(), InstanceRaw::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            cull_mode: Some(cull),
+            polygon_mode,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: depth.compare(),
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// Pipeline for `OverlayQuad`s: no depth, no culling, alpha blending.
+fn create_overlay_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::include_wgsl!("../../assets/overlay.wgsl"));
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("overlay pipeline layout"),
+        bind_group_layouts: &[],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("overlay pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_overlay",
+            buffers: &[OverlayVertex::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: "fs_overlay",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// Camera uniform and bind group for the viewmodel pass.
+fn create_viewmodel_camera(
+    device: &wgpu::Device,
+    camera_layout: &wgpu::BindGroupLayout,
+) -> (wgpu::Buffer, wgpu::BindGroup) {
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Viewmodel Camera Buffer"),
+        contents: bytemuck::bytes_of(&Mat4::IDENTITY.to_cols_array()),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+    let bind = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: camera_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+        label: Some("viewmodel camera bind group"),
+    });
+    (buffer, bind)
+}
+
+/// Grid style uniform set to `GridStyle::default()`, its bind group and the
+/// floor pipeline layout that binds it after the camera.
+fn create_grid_bind(
+    device: &wgpu::Device,
+    camera_layout: &wgpu::BindGroupLayout,
+) -> (wgpu::Buffer, wgpu::BindGroup, wgpu::PipelineLayout) {
+    let grid_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Grid Buffer"),
+        contents: bytemuck::bytes_of(&GridStyle::default().to_uniform()),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+    let grid_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("grid bind layout"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let grid_bind = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &grid_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: grid_buffer.as_entire_binding(),
+        }],
+        label: Some("grid bind group"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("floor pipeline layout"),
+        bind_group_layouts: &[camera_layout, &grid_layout],
+        push_constant_ranges: &[],
+    });
+    (grid_buffer, grid_bind, layout)
+}
+
+/// Pipeline for the grid floor.
+fn create_floor_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth: DepthMode,
+    polygon_mode: wgpu::PolygonMode,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::include_wgsl!("../../assets/grid.wgsl"));
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("floor pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: "vs_floor",
+            buffers: &[Vertex::desc()],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: "fs_grid",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        // Пол виден и снизу, если камера провалилась под него
+        primitive: wgpu::PrimitiveState {
+            polygon_mode,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: depth.compare(),
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+fn create_cube_mesh(device: &wgpu::Device) -> Mesh {
     let vertices = [
         // front
         Vertex {
@@ -893,17 +2066,7 @@ fn create_cube_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, u3
         3, 2, 6, 6, 7, 3, // top
         4, 5, 1, 1, 0, 4, // bottom
     ];
-    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Cube Vertex Buffer"),
-        contents: bytemuck::cast_slice(&vertices),
-        usage: wgpu::BufferUsages::VERTEX,
-    });
-    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Cube Index Buffer"),
-        contents: bytemuck::cast_slice(indices),
-        usage: wgpu::BufferUsages::INDEX,
-    });
-    (vertex_buffer, index_buffer, indices.len() as u32)
+    Mesh::new(device, "Cube", &vertices, indices)
 }
 
 fn create_floor_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, u32) {
@@ -943,7 +2106,11 @@ fn create_floor_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, u
     (vertex_buffer, index_buffer, indices.len() as u32)
 }
 
-fn create_artifact_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, u32) {
+/// One cube per ring block, merged into a single mesh.
+fn create_artifact_buffers(
+    device: &wgpu::Device,
+    count: usize,
+) -> (wgpu::Buffer, wgpu::Buffer, u32) {
     let base_vertices = [
         // front
         Vertex {
@@ -989,16 +2156,12 @@ fn create_artifact_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer
         4, 5, 1, 1, 0, 4, // bottom
     ];
 
-    let count = 28u16;
-    let radius = 3.0f32;
-    let mut vertices = Vec::with_capacity((base_vertices.len() as u16 * count) as usize);
-    let mut indices = Vec::with_capacity((base_indices.len() as u16 * count) as usize);
+    let mut vertices = Vec::with_capacity(base_vertices.len() * count);
+    let mut indices = Vec::with_capacity(base_indices.len() * count);
 
-    for i in 0..count {
-        let angle = i as f32 / count as f32 * std::f32::consts::TAU;
-        let x = radius * angle.cos();
-        let z = radius * angle.sin();
-        let base = i * base_vertices.len() as u16;
+    for (i, center) in ring_positions(count).into_iter().enumerate() {
+        let (x, z) = (center.x, center.z);
+        let base = (i * base_vertices.len()) as u16;
         for v in &base_vertices {
             vertices.push(Vertex {
                 position: [v.position[0] + x, v.position[1], v.position[2] + z],
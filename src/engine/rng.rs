@@ -0,0 +1,49 @@
+//! Small seeded random source for gameplay that has to replay the same way
+//! every run, such as enemy aim.
+
+use glam::Vec3;
+use std::f32::consts::TAU;
+
+/// Xorshift generator. The same seed always gives the same sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Нулевое состояние xorshift никогда не покидает
+        Self { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Uniform value in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Direction at most `angle` radians away from `direction`, spread
+    /// evenly over the cone. `direction` needn't be normalized; the result
+    /// is.
+    pub fn in_cone(&mut self, direction: Vec3, angle: f32) -> Vec3 {
+        let forward = direction.normalize_or_zero();
+        if angle <= 0.0 || forward == Vec3::ZERO {
+            return forward;
+        }
+        let (side, up) = forward.any_orthonormal_pair();
+        // Косинус равномерен на сферическом сегменте, так что точки не
+        // сгущаются к оси конуса
+        let cos = 1.0 - self.next_f32() * (1.0 - angle.cos());
+        let sin = (1.0 - cos * cos).max(0.0).sqrt();
+        let (s, c) = (self.next_f32() * TAU).sin_cos();
+        forward * cos + (side * c + up * s) * sin
+    }
+}
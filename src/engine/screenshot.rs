@@ -0,0 +1,19 @@
+use image::{ImageBuffer, ImageResult, Rgba};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Write tightly packed RGBA8 pixels as a PNG.
+pub fn save_png(rgba: &[u8], width: u32, height: u32, path: &Path) -> ImageResult<()> {
+    let img = ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, rgba)
+        .expect("buffer size matches dimensions");
+    img.save(path)
+}
+
+/// `dir/screenshot_<unix ms>.png` for a capture taken at `time`.
+pub fn timestamped_path(dir: &Path, time: SystemTime) -> PathBuf {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    dir.join(format!("screenshot_{millis}.png"))
+}
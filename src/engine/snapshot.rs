@@ -0,0 +1,179 @@
+//! World state snapshots for sending the simulation from a host to clients.
+//!
+//! Bodies are identified by their index in the snapshot, so host and client
+//! must build their body lists in the same order. Everything is encoded as
+//! little-endian `f32`/`u32` without any framing; the transport adds that.
+
+use super::physics::RigidBody;
+use glam::Vec3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BodyState {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+impl BodyState {
+    const BYTES: usize = 24;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for v in self
+            .position
+            .to_array()
+            .into_iter()
+            .chain(self.velocity.to_array())
+        {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Option<Self> {
+        let mut v = [0.0f32; 6];
+        for x in &mut v {
+            *x = reader.f32()?;
+        }
+        Some(Self {
+            position: Vec3::new(v[0], v[1], v[2]),
+            velocity: Vec3::new(v[3], v[4], v[5]),
+        })
+    }
+}
+
+impl From<&RigidBody> for BodyState {
+    fn from(body: &RigidBody) -> Self {
+        Self {
+            position: body.position,
+            velocity: body.velocity,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorldSnapshot {
+    pub tick: u32,
+    pub bodies: Vec<BodyState>,
+}
+
+/// Changes needed to turn one snapshot into a newer one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotDelta {
+    pub tick: u32,
+    /// Body count of the newer snapshot.
+    pub len: u32,
+    pub changed: Vec<(u32, BodyState)>,
+}
+
+impl WorldSnapshot {
+    pub fn capture<'a>(tick: u32, bodies: impl IntoIterator<Item = &'a RigidBody>) -> Self {
+        Self {
+            tick,
+            bodies: bodies.into_iter().map(BodyState::from).collect(),
+        }
+    }
+
+    /// Write positions and velocities back into `bodies`, in snapshot order.
+    pub fn restore<'a>(&self, bodies: impl IntoIterator<Item = &'a mut RigidBody>) {
+        for (body, state) in bodies.into_iter().zip(&self.bodies) {
+            body.position = state.position;
+            body.velocity = state.velocity;
+        }
+    }
+
+    pub fn diff(&self, newer: &WorldSnapshot) -> SnapshotDelta {
+        let changed = newer
+            .bodies
+            .iter()
+            .enumerate()
+            .filter(|(i, state)| self.bodies.get(*i) != Some(*state))
+            .map(|(i, state)| (i as u32, *state))
+            .collect();
+        SnapshotDelta {
+            tick: newer.tick,
+            len: newer.bodies.len() as u32,
+            changed,
+        }
+    }
+
+    pub fn apply(&mut self, delta: &SnapshotDelta) {
+        let filler = BodyState {
+            position: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+        };
+        self.tick = delta.tick;
+        self.bodies.resize(delta.len as usize, filler);
+        for (i, state) in &delta.changed {
+            if let Some(slot) = self.bodies.get_mut(*i as usize) {
+                *slot = *state;
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(8 + self.bodies.len() * BodyState::BYTES);
+        out.extend_from_slice(&self.tick.to_le_bytes());
+        out.extend_from_slice(&(self.bodies.len() as u32).to_le_bytes());
+        for state in &self.bodies {
+            state.write(&mut out);
+        }
+        out
+    }
+
+    /// Decode a snapshot, or `None` if `bytes` is truncated or has
+    /// trailing data.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let tick = reader.u32()?;
+        let len = reader.u32()?;
+        let bodies = (0..len)
+            .map(|_| BodyState::read(&mut reader))
+            .collect::<Option<Vec<_>>>()?;
+        reader.finish(Self { tick, bodies })
+    }
+}
+
+impl SnapshotDelta {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(12 + self.changed.len() * (4 + BodyState::BYTES));
+        out.extend_from_slice(&self.tick.to_le_bytes());
+        out.extend_from_slice(&self.len.to_le_bytes());
+        out.extend_from_slice(&(self.changed.len() as u32).to_le_bytes());
+        for (i, state) in &self.changed {
+            out.extend_from_slice(&i.to_le_bytes());
+            state.write(&mut out);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let tick = reader.u32()?;
+        let len = reader.u32()?;
+        let count = reader.u32()?;
+        let changed = (0..count)
+            .map(|_| Some((reader.u32()?, BodyState::read(&mut reader)?)))
+            .collect::<Option<Vec<_>>>()?;
+        reader.finish(Self { tick, len, changed })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take4(&mut self) -> Option<[u8; 4]> {
+        let (head, rest) = self.0.split_first_chunk::<4>()?;
+        self.0 = rest;
+        Some(*head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take4().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.take4().map(f32::from_le_bytes)
+    }
+
+    fn finish<T>(&self, value: T) -> Option<T> {
+        self.0.is_empty().then_some(value)
+    }
+}
@@ -0,0 +1,176 @@
+use super::timer::Timer;
+use wgpu_glyph::ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use wgpu_glyph::OwnedSection;
+
+/// Dark backing drawn under HUD text so it stays readable over bright
+/// parts of the scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TextStyle {
+    #[default]
+    Plain,
+    /// One copy of the text shifted by `offset` pixels.
+    Shadow { offset: (f32, f32), color: [f32; 4] },
+    /// Copies shifted `width` pixels in the eight compass directions.
+    Outline { width: f32, color: [f32; 4] },
+}
+
+impl TextStyle {
+    /// Black shadow two pixels down and to the right.
+    pub fn shadow() -> Self {
+        TextStyle::Shadow {
+            offset: (2.0, 2.0),
+            color: [0.0, 0.0, 0.0, 1.0],
+        }
+    }
+
+    /// Black outline two pixels wide.
+    pub fn outline() -> Self {
+        TextStyle::Outline {
+            width: 2.0,
+            color: [0.0, 0.0, 0.0, 1.0],
+        }
+    }
+
+    /// `section` preceded by its backing copies, in the order they have to
+    /// be queued so the text itself ends up on top.
+    pub fn apply(&self, section: &OwnedSection) -> Vec<OwnedSection> {
+        let (offsets, color) = match *self {
+            TextStyle::Plain => (Vec::new(), [0.0; 4]),
+            TextStyle::Shadow { offset, color } => (vec![offset], color),
+            TextStyle::Outline { width, color } => {
+                let mut offsets = Vec::with_capacity(8);
+                for dy in [-width, 0.0, width] {
+                    for dx in [-width, 0.0, width] {
+                        if (dx, dy) != (0.0, 0.0) {
+                            offsets.push((dx, dy));
+                        }
+                    }
+                }
+                (offsets, color)
+            }
+        };
+        let mut sections: Vec<OwnedSection> = offsets
+            .into_iter()
+            .map(|(dx, dy)| {
+                let mut copy = section.clone();
+                copy.screen_position.0 += dx;
+                copy.screen_position.1 += dy;
+                for text in &mut copy.text {
+                    text.extra.color = color;
+                }
+                copy
+            })
+            .collect();
+        sections.push(section.clone());
+        sections
+    }
+}
+
+/// Natural distance between two baselines for `font` at `scale`.
+pub fn line_height(font: &FontArc, scale: f32) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(scale));
+    scaled.height() + scaled.line_gap()
+}
+
+/// Horizontal extent of a single line of text, kerning included.
+pub fn text_width(font: &FontArc, text: &str, scale: f32) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(scale));
+    let mut width = 0.0;
+    let mut prev = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = prev {
+            width += scaled.kern(prev, id);
+        }
+        width += scaled.h_advance(id);
+        prev = Some(id);
+    }
+    width
+}
+
+/// Split `text` into lines no wider than `max_width`.
+///
+/// Explicit `\n` always starts a new line. Words are never split, so a
+/// single word wider than `max_width` ends up on a line of its own.
+pub fn wrap_text(font: &FontArc, text: &str, scale: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+            let candidate = format!("{line} {word}");
+            if text_width(font, &candidate, scale) > max_width {
+                lines.push(std::mem::take(&mut line));
+                line.push_str(word);
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Size of the block `wrap_text` produces, as `(width, height)`.
+///
+/// `line_spacing` scales the font's natural line height, so `1.0` keeps
+/// the default spacing.
+pub fn measure_text(
+    font: &FontArc,
+    text: &str,
+    scale: f32,
+    max_width: f32,
+    line_spacing: f32,
+) -> (f32, f32) {
+    let lines = wrap_text(font, text, scale, max_width);
+    let width = lines
+        .iter()
+        .map(|l| text_width(font, l, scale))
+        .fold(0.0, f32::max);
+    let height = lines.len() as f32 * line_height(font, scale) * line_spacing;
+    (width, height)
+}
+
+/// Prefix of `text` holding the characters revealed after `elapsed`
+/// seconds at `rate` characters per second.
+pub fn reveal(text: &str, elapsed: f32, rate: f32) -> &str {
+    let count = (elapsed.max(0.0) * rate) as usize;
+    match text.char_indices().nth(count) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Message that types itself out and then stays on screen for `hold`
+/// seconds before expiring.
+pub struct Typewriter {
+    pub text: String,
+    pub rate: f32,
+    timer: Timer,
+}
+
+impl Typewriter {
+    pub fn new(text: &str, rate: f32, hold: f32) -> Self {
+        let reveal_time = text.chars().count() as f32 / rate;
+        Self {
+            text: text.to_string(),
+            rate,
+            timer: Timer::new(reveal_time + hold),
+        }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.timer.tick(dt);
+    }
+
+    pub fn visible(&self) -> &str {
+        reveal(&self.text, self.timer.elapsed(), self.rate)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.timer.finished()
+    }
+}
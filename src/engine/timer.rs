@@ -0,0 +1,206 @@
+use std::time::{Duration, Instant};
+
+/// Countdown driven by frame `dt`, shared by cooldowns and timed messages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timer {
+    pub duration: f32,
+    elapsed: f32,
+}
+
+impl Timer {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advance the timer. Returns `true` only on the tick that finishes it.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        let was_finished = self.finished();
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        !was_finished && self.finished()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        self.duration - self.elapsed
+    }
+
+    /// Fraction of the duration that has passed, in `[0, 1]`.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+/// Lets through at most `rate` updates per second, however often it is
+/// ticked. Time left over from a frame carries into the next interval, so
+/// the average rate stays right when frames don't divide it evenly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimiter {
+    pub interval: f32,
+    elapsed: f32,
+}
+
+/// Frame timing jitter tolerated before an update is put off to the next
+/// frame, in seconds.
+const RATE_SLACK: f32 = 0.0005;
+
+impl RateLimiter {
+    pub fn new(rate: f32) -> Self {
+        Self {
+            interval: 1.0 / rate,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Add `dt` seconds. Returns `true` when an update is due.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.elapsed += dt;
+        if self.elapsed + RATE_SLACK < self.interval {
+            return false;
+        }
+        self.elapsed = (self.elapsed - self.interval).max(0.0);
+        // После долгого кадра не навёрстываем пропущенные обновления
+        if self.elapsed >= self.interval {
+            self.elapsed = 0.0;
+        }
+        true
+    }
+
+    /// Seconds until the next update is due.
+    pub fn remaining(&self) -> f32 {
+        (self.interval - self.elapsed).max(0.0)
+    }
+}
+
+/// How long to sleep after a frame that took `frame_time` so frames come
+/// no faster than `fps` per second. Zero once the frame has used up its
+/// budget.
+pub fn pacing_sleep(frame_time: Duration, fps: f32) -> Duration {
+    Duration::from_secs_f32(1.0 / fps).saturating_sub(frame_time)
+}
+
+/// Where the engine reads the time. Timestamps are durations since the
+/// source started.
+pub trait TimeSource {
+    fn now(&self) -> Duration;
+
+    /// Called once at the start of every engine frame, before `now`.
+    fn frame(&mut self) {}
+}
+
+/// Wall-clock time.
+pub struct RealClock {
+    start: Instant,
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl TimeSource for RealClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Time that only moves when told to: by `step` every frame, and by
+/// `advance`. Makes engine timing exact for tests and replays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualClock {
+    pub step: Duration,
+    now: Duration,
+}
+
+impl VirtualClock {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step,
+            now: Duration::ZERO,
+        }
+    }
+
+    pub fn advance(&mut self, by: Duration) {
+        self.now += by;
+    }
+}
+
+impl TimeSource for VirtualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn frame(&mut self) {
+        self.now += self.step;
+    }
+}
+
+/// Measures the time between updates, leaving out time spent paused so a
+/// resume doesn't hand the game one huge step. Timestamps come from a
+/// `TimeSource`.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameClock {
+    last: Duration,
+    paused_since: Option<Duration>,
+    paused: Duration,
+}
+
+impl FrameClock {
+    pub fn new(now: Duration) -> Self {
+        Self {
+            last: now,
+            paused_since: None,
+            paused: Duration::ZERO,
+        }
+    }
+
+    pub fn pause(&mut self, now: Duration) {
+        self.paused_since.get_or_insert(now);
+    }
+
+    pub fn resume(&mut self, now: Duration) {
+        if let Some(since) = self.paused_since.take() {
+            self.paused += now.saturating_sub(since);
+        }
+    }
+
+    /// Seconds since the previous tick, minus the time paused in between.
+    pub fn tick(&mut self, now: Duration) -> f32 {
+        // Пауза ещё идёт: уже прошедшая её часть не в счёт
+        if let Some(since) = self.paused_since.as_mut() {
+            self.paused += now.saturating_sub(*since);
+            *since = now;
+        }
+        let elapsed = now.saturating_sub(self.last);
+        let dt = elapsed.saturating_sub(self.paused);
+        self.last = now;
+        self.paused = Duration::ZERO;
+        dt.as_secs_f32()
+    }
+}
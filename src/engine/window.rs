@@ -5,7 +5,8 @@ use winit::{
 };
 
 pub struct WindowState {
-    pub window: Window,
+    /// `None` in headless mode, where there is no OS window to drive.
+    pub window: Option<Window>,
 }
 
 impl WindowState {
@@ -24,28 +25,35 @@ impl WindowState {
             .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
         window.set_cursor_visible(false);
 
-        Self { window }
+        Self {
+            window: Some(window),
+        }
     }
 
     /// Attempt to capture the cursor and hide it. This works even on
     /// platforms where pointer locking might not be available by
     /// falling back to confining the cursor to the window.
     pub fn capture_cursor(&self) {
-        let _ = self
-            .window
-            .set_cursor_grab(CursorGrabMode::Locked)
-            .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined));
-        self.window.set_cursor_visible(false);
+        if let Some(window) = &self.window {
+            let _ = window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+            window.set_cursor_visible(false);
+        }
     }
 
     /// Release the cursor and make it visible again.
     pub fn release_cursor(&self) {
-        let _ = self.window.set_cursor_grab(CursorGrabMode::None);
-        self.window.set_cursor_visible(true);
+        if let Some(window) = &self.window {
+            let _ = window.set_cursor_grab(CursorGrabMode::None);
+            window.set_cursor_visible(true);
+        }
     }
 
     pub fn request_redraw(&self) {
-        self.window.request_redraw();
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
     }
 
     pub fn present(&mut self) {
@@ -53,7 +61,9 @@ impl WindowState {
     }
 
     pub fn set_title(&self, title: &str) {
-        self.window.set_title(title);
+        if let Some(window) = &self.window {
+            window.set_title(title);
+        }
     }
 
     pub fn handle_window_event(
@@ -67,16 +77,8 @@ impl WindowState {
         }
     }
 
-    pub fn dummy(width: u32, height: u32) -> Self {
-        struct DummyWindow;
-        impl DummyWindow {
-            fn request_redraw(&self) {}
-            fn set_title(&self, _title: &str) {}
-            fn set_cursor_grab(&self, _mode: CursorGrabMode) -> Result<(), ()> { Ok(()) }
-            fn set_cursor_visible(&self, _visible: bool) {}
-        }
-        // SAFETY: мы не используем это окно нигде, кроме как для заглушки
-        let window: Window = unsafe { std::mem::zeroed() };
-        Self { window }
+    /// Window stand-in for headless rendering: every window call is a no-op.
+    pub fn dummy() -> Self {
+        Self { window: None }
     }
 }
@@ -0,0 +1,37 @@
+use crate::engine::input::InputFrame;
+use crate::engine::screenshot;
+use crate::engine::Engine;
+use crate::game::Game;
+use image::ImageResult;
+use std::path::{Path, PathBuf};
+
+/// Fixed step used for dumped frames, so a script always plays back the
+/// same way regardless of how long rendering takes.
+pub const FRAME_DT: f32 = 1.0 / 60.0;
+
+/// Play `script` through `game` on a headless `engine`, one fixed step per
+/// entry, and save every rendered frame as `dir/frame_0001.png` and so on.
+pub fn dump_frames(
+    game: &mut Game,
+    engine: &mut Engine,
+    script: &[InputFrame],
+    dir: &Path,
+) -> ImageResult<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let size = engine.renderer.size;
+    let mut paths = Vec::with_capacity(script.len());
+    for (i, input) in script.iter().enumerate() {
+        engine.input.apply_frame(input);
+        game.frame(engine, FRAME_DT);
+        let path = dir.join(format!("frame_{:04}.png", i + 1));
+        screenshot::save_png(
+            &engine.renderer.get_frame_rgba8(),
+            size.width,
+            size.height,
+            &path,
+        )?;
+        paths.push(path);
+        engine.input.reset();
+    }
+    Ok(paths)
+}
@@ -0,0 +1,1211 @@
+use crate::config::{Level, Scenario};
+use crate::crosshair::Crosshair;
+use crate::damage_indicator::DamageIndicator;
+use crate::engine::artifact::{ArtifactGlow, RING_BLOCKS};
+use crate::engine::audio::ProximityHum;
+use crate::engine::camera::ViewKick;
+use crate::engine::ecs::EntityId;
+use crate::engine::overlay::ScreenFade;
+use crate::engine::physics::{
+    layers, raycast, teleport, Aabb, Arena, Collider, Contact, Material, PhysicsObject,
+    PhysicsWorld, RigidBody, StepStats, Trigger,
+};
+use crate::engine::renderer::{contact_markers, CubeInstance, Renderer};
+use crate::engine::text::Typewriter;
+use crate::engine::timer::Timer;
+use crate::engine::Engine;
+use crate::hitmarker::{HitKind, Hitmarker};
+use crate::i18n::{Language, Locale};
+use crate::killcam::{KillCam, Replay, ReplayFrame};
+use crate::palette::Palette;
+use crate::player::{Enemy, Player};
+use crate::viewmodel::Viewmodel;
+use crate::vignette::DamageVignette;
+use crate::weapon::{Hitscan, Tracer, WeaponKind};
+use crate::world::{Entity, Explosive, Hit, Penetration, Projectile, Renderable, Tag, World};
+use base64::Engine as _;
+use glam::{EulerRot, Mat4, Quat, Vec2, Vec3};
+use std::time::{Duration, Instant};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const ACTIVATION_B64: &str = include_str!("../assets/activation.ogg.b64");
+/// Скорость «печати» сообщений, символов в секунду.
+const MESSAGE_REVEAL_RATE: f32 = 30.0;
+/// Сколько полностью напечатанное сообщение остаётся на экране.
+const MESSAGE_HOLD: f32 = 3.0;
+/// Сколько секунд пуля после выстрела пролетает сквозь тела.
+const BULLET_ARMING_TIME: f32 = 0.1;
+/// Скорость пули врага.
+const ENEMY_BULLET_SPEED: f32 = 5.0;
+/// Удлинение пули вдоль скорости на каждую единицу скорости.
+const BULLET_TRACER: f32 = 0.04;
+/// Радиус зоны активации маяка: внутренний край кольца артефакта.
+pub const BEACON_RADIUS: f32 = 2.5;
+/// Половина стороны квадратного пола.
+const FLOOR_HALF_SIZE: f32 = 50.0;
+/// Высота, толщина и цвет луча над активным маяком.
+const BEAM_HEIGHT: f32 = 40.0;
+const BEAM_WIDTH: f32 = 0.15;
+const BEAM_COLOR: [f32; 3] = [0.4, 0.9, 1.0];
+/// Масса и размер гранаты игрока.
+const GRENADE_MASS: f32 = 0.4;
+const GRENADE_SIZE: f32 = 0.15;
+/// Сколько раз за шаг физики разводятся пересёкшиеся тела.
+const SOLVER_ITERATIONS: usize = 4;
+/// На сколько метров за шаг физики можно растолкнуть пару тел: глубокое
+/// пересечение уходит за несколько кадров, а не одним рывком.
+const MAX_DEPENETRATION: f32 = 0.25;
+/// Сколько живут, как быстро разлетаются и сколько весят обломки
+/// убитого врага.
+const DEBRIS_LIFETIME: f32 = 1.5;
+const DEBRIS_SPEED: f32 = 4.0;
+const DEBRIS_MASS: f32 = 2.0;
+/// Сколько секунд игрок неуязвим после появления, возрождения и появления
+/// врага.
+pub const SPAWN_PROTECTION: f32 = 2.0;
+/// Взрывающаяся бочка: размер, масса, прочность, цвет и сила взрыва.
+const BARREL_SIZE: f32 = 0.8;
+const BARREL_MASS: f32 = 30.0;
+const BARREL_HEALTH: i32 = 30;
+const BARREL_COLOR: [f32; 3] = [0.85, 0.3, 0.1];
+const BARREL_BLAST: Explosive = Explosive {
+    radius: 4.0,
+    impulse: 600.0,
+    damage: 60,
+};
+/// Мишень тира: размер, масса, прочность и цвет.
+const TARGET_SIZE: f32 = 0.6;
+const TARGET_MASS: f32 = 30.0;
+const TARGET_HEALTH: i32 = 25;
+const TARGET_COLOR: [f32; 3] = [0.9, 0.9, 0.9];
+/// Где стоят мишени тира: ряд на полу перед стартом арены, не заслонённый
+/// кольцом артефакта.
+const PRACTICE_TARGETS: [Vec3; 5] = [
+    Vec3::new(-4.0, 0.3, 10.0),
+    Vec3::new(-2.0, 0.3, 10.0),
+    Vec3::new(0.0, 0.3, 10.0),
+    Vec3::new(2.0, 0.3, 10.0),
+    Vec3::new(4.0, 0.3, 10.0),
+];
+/// Цвет отладочных меток контактов (F4).
+const CONTACT_COLOR: [f32; 3] = [1.0, 0.9, 0.1];
+/// Откуда рисуется трассер относительно камеры, чтобы его было видно.
+const MUZZLE_OFFSET: Vec3 = Vec3::new(0.2, -0.2, 0.0);
+/// Дальше этого враг не замечает игрока: не гонится и не стреляет.
+const ENEMY_AGGRO_RANGE: f32 = 25.0;
+/// Как далеко подсвечивается враг или предмет под прицелом.
+const TARGET_RANGE: f32 = 30.0;
+/// Ниже этой высоты тела считаются выпавшими из мира.
+const KILL_PLANE_Y: f32 = -50.0;
+/// Затемнение при возрождении: секунды до полной темноты и обратно.
+const RESPAWN_FADE_OUT: f32 = 0.3;
+const RESPAWN_FADE_IN: f32 = 0.4;
+/// Секунды перед смертью, которые показывает повтор
+const KILL_CAM_LENGTH: f32 = 3.0;
+
+/// Health the player starts with and can regenerate up to.
+pub const MAX_HEALTH: i32 = 100;
+
+/// Passive healing after a break from taking damage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HealthRegen {
+    /// Seconds without damage before healing starts.
+    pub delay: f32,
+    /// Health restored per second.
+    pub rate: f32,
+}
+
+/// Settings picked on the command line.
+#[derive(Clone, Copy, Debug)]
+pub struct GameOptions {
+    pub locale: Locale,
+    pub palette: Palette,
+    /// Show the Cyrillic overlay every frame instead of playing.
+    pub selftest: bool,
+    /// Blocks in the artifact ring, for both collision and rendering.
+    pub artifact_blocks: usize,
+    pub crosshair: Crosshair,
+    pub scenario: Scenario,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            palette: Palette::default(),
+            selftest: false,
+            artifact_blocks: RING_BLOCKS,
+            crosshair: Crosshair::default(),
+            scenario: Scenario::default(),
+        }
+    }
+}
+
+/// Gameplay state and the per-frame update that used to live in `main`.
+pub struct Game {
+    pub options: GameOptions,
+    pub player: Player,
+    pub player_id: EntityId,
+    pub world: World,
+    pub enemy: Option<(EntityId, Enemy)>,
+    pub health: i32,
+    /// Off unless set.
+    pub health_regen: Option<HealthRegen>,
+    /// The player takes no damage from anything (F6). Starts as set by the
+    /// scenario.
+    pub god_mode: bool,
+    /// Speed of the simulation: 0.5 is slow motion, and 0 freezes it while
+    /// frames keep rendering.
+    pub time_scale: f32,
+    /// Bodies that fall below this height are out of the world: the player
+    /// dies, or respawns in god mode, and anything else is removed.
+    pub kill_plane: f32,
+    /// Restarted by every hit; healing waits for it to run out.
+    regen_delay: Timer,
+    /// Healing earned but not yet added, below one whole point.
+    regen_carry: f32,
+    pub game_over: bool,
+    /// Hits on practice targets, and targets broken.
+    pub practice_hits: u32,
+    pub targets_destroyed: u32,
+    /// The last `KILL_CAM_LENGTH` seconds of frames while the player lives.
+    pub kill_cam: KillCam,
+    /// Death replay, played from the moment the player dies.
+    pub replay: Option<Replay>,
+    /// Game time since the last frame went into `kill_cam`.
+    unrecorded: f32,
+    /// Fade hiding a respawn in progress. The player is moved once the
+    /// screen is dark.
+    pub fade: Option<ScreenFade>,
+    pub tech_unlocked: bool,
+    /// Standing on the ground inside this volume activates the beacon.
+    pub beacon_trigger: Trigger,
+    /// Artifact glow once the technology is unlocked.
+    pub glow: ArtifactGlow,
+    /// Audio guidance toward the beacon until it is activated.
+    pub hum: ProximityHum,
+    pub physics: PhysicsWorld,
+    /// Seconds of invulnerability the player gets on (re)spawning and when
+    /// an enemy spawns.
+    pub spawn_protection: f32,
+    tracers: Vec<Tracer>,
+    /// Recoil folded into the camera on top of the player's look.
+    pub view_kick: ViewKick,
+    pub viewmodel: Viewmodel,
+    /// Show the movement readout from `debug_text` (F3).
+    pub debug_hud: bool,
+    /// Markers pointing toward recent hits on the player.
+    pub damage_indicators: Vec<DamageIndicator>,
+    /// Confirms that the player's last shot landed, around the crosshair.
+    pub hitmarker: Option<Hitmarker>,
+    /// Tone still to play for a hit scored since the last update.
+    hit_sound: Option<HitKind>,
+    /// Red screen edges on taking damage and while health is low.
+    pub vignette: DamageVignette,
+    /// Draw the contacts found by the last physics step (F4).
+    pub show_contacts: bool,
+    /// Show `physics_text` on the debug HUD (F5).
+    pub show_physics_stats: bool,
+    /// Counts from the last physics step and how long it took.
+    pub physics_stats: StepStats,
+    pub physics_step_time: Duration,
+    /// Physics steps taken since the game started.
+    pub physics_steps: u64,
+    /// Hold the simulation still while the camera and HUD keep going (F8).
+    pub physics_frozen: bool,
+    /// Take one step on the next update despite `physics_frozen` (F9).
+    step_requested: bool,
+    contacts: Vec<Contact>,
+    message: Option<Typewriter>,
+    overlay: Option<String>,
+    spawn_timer: f32,
+    spawn_started: bool,
+    /// The player is standing in the beacon trigger.
+    activated: bool,
+    activations: u32,
+    overlay_tested: bool,
+    activation_sound: Vec<u8>,
+}
+
+impl Game {
+    pub fn new(options: GameOptions) -> Self {
+        let b64_clean: String = ACTIVATION_B64
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '+' || *c == '/' || *c == '=')
+            .collect();
+        let activation_sound = base64::engine::general_purpose::STANDARD
+            .decode(b64_clean)
+            .expect("valid base64");
+        let mut world = World::new();
+        let player_id = world.spawn(Entity::new(Tag::Player));
+        let player = Player::new().with_position(options.scenario.level.player_start());
+        let tech_unlocked = options.scenario.level == Level::Arena;
+        let god_mode = options.scenario.god;
+        let mut game = Self {
+            options,
+            player,
+            player_id,
+            world,
+            enemy: None,
+            health: MAX_HEALTH,
+            health_regen: None,
+            god_mode,
+            time_scale: 1.0,
+            kill_plane: KILL_PLANE_Y,
+            regen_delay: Timer::new(0.0),
+            regen_carry: 0.0,
+            game_over: false,
+            practice_hits: 0,
+            targets_destroyed: 0,
+            kill_cam: KillCam::new(KILL_CAM_LENGTH),
+            replay: None,
+            unrecorded: 0.0,
+            fade: None,
+            tech_unlocked,
+            beacon_trigger: Trigger::cylinder(Vec3::new(0.0, 1.0, 0.0), BEACON_RADIUS, 2.0),
+            glow: ArtifactGlow::new(),
+            hum: ProximityHum::default(),
+            // Стены арены совпадают с краями пола
+            physics: PhysicsWorld::new()
+                .with_arena(Arena::new(
+                    Vec2::splat(-FLOOR_HALF_SIZE),
+                    Vec2::splat(FLOOR_HALF_SIZE),
+                ))
+                .with_solver_iterations(SOLVER_ITERATIONS)
+                .with_max_depenetration(MAX_DEPENETRATION),
+            spawn_protection: SPAWN_PROTECTION,
+            tracers: Vec::new(),
+            view_kick: ViewKick::new(),
+            viewmodel: Viewmodel::new(),
+            debug_hud: false,
+            damage_indicators: Vec::new(),
+            hitmarker: None,
+            hit_sound: None,
+            vignette: DamageVignette::new(),
+            show_contacts: false,
+            show_physics_stats: false,
+            physics_stats: StepStats::default(),
+            physics_step_time: Duration::ZERO,
+            physics_steps: 0,
+            physics_frozen: false,
+            step_requested: false,
+            contacts: Vec::new(),
+            message: None,
+            overlay: None,
+            spawn_timer: 0.0,
+            spawn_started: false,
+            activated: false,
+            activations: 0,
+            overlay_tested: false,
+            activation_sound,
+        };
+        if game.options.scenario.practice {
+            game.setup_practice_range();
+        }
+        for &position in game.options.scenario.level.barrels() {
+            game.spawn_barrel(position);
+        }
+        game.player.protect(game.spawn_protection);
+        game
+    }
+
+    /// Advance the game by `dt` using the engine's current input, then
+    /// draw the frame.
+    pub fn frame(&mut self, engine: &mut Engine, dt: f32) {
+        self.update(engine, dt);
+        self.render(engine);
+    }
+
+    /// Advance gameplay and physics by `dt` scaled by `time_scale`. Screen
+    /// effects such as the fades, markers and recoil run on the unscaled
+    /// `dt`, so they play out even while time stands still.
+    pub fn update(&mut self, engine: &mut Engine, dt: f32) {
+        let game_dt = dt * self.time_scale.max(0.0);
+        if engine.input.just_pressed(VirtualKeyCode::F3) {
+            self.debug_hud = !self.debug_hud;
+        }
+        if engine.input.just_pressed(VirtualKeyCode::F4) {
+            self.show_contacts = !self.show_contacts;
+        }
+        if engine.input.just_pressed(VirtualKeyCode::F5) {
+            self.show_physics_stats = !self.show_physics_stats;
+        }
+        if engine.input.just_pressed(VirtualKeyCode::F6) {
+            self.god_mode = !self.god_mode;
+        }
+        if engine.input.just_pressed(VirtualKeyCode::F8) {
+            self.physics_frozen = !self.physics_frozen;
+        }
+        if engine.input.just_pressed(VirtualKeyCode::F9) {
+            self.request_step();
+        }
+        // Игрока переносят, когда экран полностью потемнел
+        if self.fade.as_mut().is_some_and(|f| f.tick(dt)) {
+            self.respawn_player();
+        }
+        if self.fade.is_some_and(|f| f.finished()) {
+            self.fade = None;
+        }
+        let static_obs = self.static_obstacles();
+        self.player.aim_target = self.enemy.as_ref().map(|(_, e)| e.body.position);
+        let player = &mut self.player;
+        // Взгляд работает и при замороженной физике, а тело — нет
+        let frozen_body = player.body;
+        player.update(&engine.input, game_dt);
+        self.view_kick.tick(dt);
+        self.damage_indicators.retain_mut(|i| i.tick(dt));
+        self.vignette.tick(dt);
+        if self.hitmarker.as_mut().is_some_and(|m| !m.tick(dt)) {
+            self.hitmarker = None;
+        }
+        let speed = Vec2::new(player.body.velocity.x, player.body.velocity.z).length();
+        self.viewmodel.update(engine.input.mouse_delta, speed, dt);
+        let look = player.rotation * self.view_kick.rotation();
+        let view = Mat4::from_quat(look).inverse() * Mat4::from_translation(-player.position);
+        engine
+            .renderer
+            .update_camera(&(projection(&engine.renderer) * view));
+
+        let in_beacon = self.beacon_trigger.contains(player.body.position);
+        self.overlay = None;
+
+        if self.options.selftest {
+            // Selftest проверяет именно кириллицу, поэтому берём русский текст
+            let text = Locale::new(Language::Russian).get("beacon_unlocked");
+            self.overlay = Some(text.to_string());
+            if !self.overlay_tested {
+                // Попытка отрисовать overlay, вывод результата после первого кадра
+                // (если не упало, считаем успехом)
+                println!("Overlay Cyrillic test: OK");
+                self.overlay_tested = true;
+            }
+        } else {
+            // Маяк срабатывает один раз: повторный вход в зону уже ничего
+            // не разблокирует и звук не повторяет
+            self.activated = in_beacon && (self.activated || player.body.on_ground);
+            if self.activated && !self.tech_unlocked {
+                self.tech_unlocked = true;
+                self.activations += 1;
+                engine.audio.play_bytes(&self.activation_sound);
+                self.message = Some(Typewriter::new(
+                    self.options.locale.get("beacon_unlocked"),
+                    MESSAGE_REVEAL_RATE,
+                    MESSAGE_HOLD,
+                ));
+            }
+            if self.tech_unlocked {
+                engine.audio.set_hum(0.0, 1.0);
+                engine.renderer.fade_artifact(self.glow.tick(dt), dt);
+            } else {
+                let offset = player.body.position - self.beacon_trigger.center;
+                let distance = Vec2::new(offset.x, offset.z).length();
+                engine
+                    .audio
+                    .set_hum(self.hum.gain(distance), self.hum.pitch(distance));
+                engine.renderer.fade_artifact(self.glow.idle(), dt);
+            }
+
+            if let Some(m) = &mut self.message {
+                m.tick(dt);
+                if m.finished() {
+                    self.message = None;
+                }
+            }
+            if let Some(m) = &self.message {
+                self.overlay = Some(m.visible().to_string());
+            } else if self.options.scenario.practice {
+                let hits = self.options.locale.get("practice_hits");
+                self.overlay = Some(format!("{hits} {}", self.practice_hits));
+            }
+        }
+
+        // enemy spawn logic after tech unlock
+        if self.tech_unlocked && !self.spawn_started {
+            self.spawn_timer = 5.0;
+            self.spawn_started = true;
+        }
+        if self.spawn_started && self.spawn_timer > 0.0 {
+            self.spawn_timer -= game_dt;
+            // В тире вместо врага мишени
+            let scenario = self.options.scenario;
+            if self.spawn_timer <= 0.0 && scenario.enemies && !scenario.practice {
+                let mut e = Enemy::new()
+                    .with_aggro_range(ENEMY_AGGRO_RANGE)
+                    .with_spread(scenario.difficulty.enemy_spread())
+                    .with_lead(scenario.difficulty.predictive_aim());
+                e.burst = scenario.difficulty.enemy_burst();
+                e.color = self.options.palette.enemy;
+                e.bullet_color = self.options.palette.enemy_bullet;
+                player.protect(self.spawn_protection);
+                self.enemy = Some((self.world.spawn(Entity::new(Tag::Enemy)), e));
+            }
+        }
+
+        // Пока физика заморожена, стоят и враг, и снаряды; шаг по запросу
+        // проходит ровно один
+        let step_requested = std::mem::take(&mut self.step_requested);
+        if !self.physics_frozen || step_requested {
+            self.simulate(&static_obs, game_dt);
+        } else {
+            self.player.body = frozen_body;
+        }
+
+        self.tracers.retain_mut(|t| t.tick(game_dt));
+        if !self.game_over {
+            // R перезаряжает оружие в руках, ЛКМ стреляет
+            if engine.input.just_pressed(VirtualKeyCode::R) {
+                if let Some(weapon) = self.player.inventory.active_mut() {
+                    weapon.ammo.reload();
+                }
+            }
+            if engine.input.just_clicked(MouseButton::Left) {
+                self.fire();
+            }
+        }
+        if let Some(kind) = self.hit_sound.take() {
+            let (frequency, seconds) = kind.tone();
+            engine.audio.play_tone(frequency, seconds);
+        }
+        self.world.remove_spent_projectiles();
+        self.tick_health(game_dt);
+
+        // Кадр для повтора записывает `render`, теми же кубами, что рисует
+        if let Some(replay) = &mut self.replay {
+            replay.tick(dt);
+        } else {
+            self.unrecorded += game_dt;
+        }
+        if self.health <= 0 && !self.game_over {
+            self.game_over = true;
+            self.replay = Some(self.kill_cam.replay());
+        }
+        if self.game_over {
+            self.overlay = Some(self.options.locale.get("game_over").to_string());
+        }
+    }
+
+    /// Fire the weapon in hand if it has been drawn and is loaded. Returns
+    /// the kind of weapon that fired.
+    pub fn fire(&mut self) -> Option<WeaponKind> {
+        let inventory = &mut self.player.inventory;
+        if !inventory.ready() {
+            return None;
+        }
+        let weapon = inventory.active_mut()?;
+        if !weapon.ammo.try_fire() {
+            return None;
+        }
+        let kind = weapon.kind;
+        let (stray_pitch, stray_yaw) = weapon.stray();
+        let (pitch, yaw) = weapon.next_kick();
+        self.view_kick.kick(pitch, yaw);
+        self.viewmodel.fire();
+        let stray = Quat::from_euler(EulerRot::YXZ, stray_yaw, stray_pitch, 0.0);
+        let forward = self.player.rotation * stray * -Vec3::Z;
+        match kind {
+            WeaponKind::Pistol { speed, penetration } => {
+                self.launch_projectile(forward * speed, 0.05, 0.1, penetration)
+            }
+            WeaponKind::Rifle(rifle) => {
+                self.fire_hitscan_along(rifle, forward);
+            }
+            WeaponKind::Grenade { speed, lift } => self.launch_projectile(
+                forward * speed + Vec3::Y * lift,
+                GRENADE_MASS,
+                GRENADE_SIZE,
+                None,
+            ),
+        }
+        Some(kind)
+    }
+
+    /// Spawn a player-owned projectile at the muzzle.
+    fn launch_projectile(
+        &mut self,
+        velocity: Vec3,
+        mass: f32,
+        size: f32,
+        penetration: Option<Penetration>,
+    ) {
+        let mut projectile = Projectile::new(Some(self.player_id)).with_arming(BULLET_ARMING_TIME);
+        projectile.penetration = penetration;
+        let muzzle = self.player.position + self.player.rotation * MUZZLE_OFFSET;
+        let mut body = RigidBody::new(mass, muzzle);
+        body.velocity = velocity;
+        self.world.spawn(
+            Entity::new(Tag::Bullet)
+                .with_body(body)
+                .with_collider(Projectile::collider(
+                    size,
+                    layers::PLAYER_BULLET,
+                    layers::PLAYER,
+                ))
+                .with_renderable(Renderable {
+                    size,
+                    color: self.options.palette.bullet,
+                    tracer: BULLET_TRACER,
+                })
+                .with_projectile(projectile),
+        );
+    }
+
+    /// Fire `rifle` along the view without spending ammo. Returns `true` if
+    /// the enemy, a practice target or a barrel was hit.
+    pub fn fire_hitscan(&mut self, rifle: Hitscan) -> bool {
+        self.fire_hitscan_along(rifle, self.player.rotation * -Vec3::Z)
+    }
+
+    fn fire_hitscan_along(&mut self, rifle: Hitscan, direction: Vec3) -> bool {
+        let origin = self.player.position;
+        // Мишени и бочки живут в мире, враг — отдельно
+        let props: Vec<(EntityId, Aabb)> = self
+            .world
+            .target_boxes()
+            .into_iter()
+            .filter(|(id, _)| matches!(self.world.tag(*id), Some(Tag::Target | Tag::Barrel)))
+            .collect();
+        let targets: Vec<Aabb> = self
+            .enemy
+            .iter()
+            .map(|(_, e)| Aabb::around(e.body.position, &e.collider))
+            .chain(props.iter().map(|(_, b)| *b))
+            .collect();
+        let shot = rifle.fire(origin, direction, &self.static_obstacles(), &targets);
+        let muzzle = origin + self.player.rotation * MUZZLE_OFFSET;
+        self.tracers.push(Tracer::new(muzzle, shot.to));
+
+        let Some(index) = shot.target else {
+            return false;
+        };
+        // Враг, если он есть, идёт в списке первым
+        let enemies = self.enemy.iter().count();
+        if index >= enemies {
+            let id = props[index - enemies].0;
+            if self.world.tag(id) == Some(Tag::Target) {
+                self.hit_target(id, rifle.damage, true);
+                self.break_targets();
+            } else {
+                // Взрывается бочка при следующем обновлении, вместе с цепочкой
+                self.world.damage(id, rifle.damage);
+                if let Some(body) = self.world.get_mut(id).and_then(|e| e.body.as_mut()) {
+                    body.apply_impulse(direction * rifle.knockback);
+                }
+            }
+            return true;
+        }
+        if let Some((_, e)) = &mut self.enemy {
+            e.health -= rifle.damage;
+            e.body.apply_impulse(direction * rifle.knockback);
+            let kind = if e.health > 0 {
+                HitKind::Hit
+            } else {
+                HitKind::Kill
+            };
+            self.confirm_hit(kind);
+        }
+        self.remove_dead_enemy();
+        true
+    }
+
+    /// Take `amount`, scaled by the difficulty, off the player's health
+    /// unless they are invulnerable or in god mode, flashing the vignette.
+    /// Returns whether the damage applied.
+    pub fn damage_player(&mut self, amount: i32) -> bool {
+        if self.health <= 0 || self.player.invulnerable() || self.god_mode {
+            return false;
+        }
+        let scale = self.options.scenario.difficulty.damage_scale();
+        self.health -= (amount as f32 * scale).round() as i32;
+        self.vignette.flash();
+        self.regen_delay = Timer::new(self.health_regen.map_or(0.0, |r| r.delay));
+        self.regen_carry = 0.0;
+        true
+    }
+
+    /// Heal by `health_regen` once the delay since the last hit has run out.
+    /// A dead player doesn't heal.
+    pub fn tick_health(&mut self, dt: f32) {
+        let Some(regen) = self.health_regen else {
+            return;
+        };
+        self.regen_delay.tick(dt);
+        if self.health <= 0 || self.health >= MAX_HEALTH || !self.regen_delay.finished() {
+            return;
+        }
+        self.regen_carry += regen.rate * dt;
+        let whole = self.regen_carry.floor();
+        self.regen_carry -= whole;
+        self.health = (self.health + whole as i32).min(MAX_HEALTH);
+    }
+
+    /// Place an explosive barrel standing on the floor at `position`.
+    pub fn spawn_barrel(&mut self, position: Vec3) -> EntityId {
+        self.world.spawn(
+            Entity::new(Tag::Barrel)
+                .with_body(RigidBody::new(BARREL_MASS, position))
+                .with_collider(
+                    Collider::new(Vec3::splat(BARREL_SIZE * 0.5)).with_material(Material::Metal),
+                )
+                .with_renderable(Renderable {
+                    size: BARREL_SIZE,
+                    color: BARREL_COLOR,
+                    tracer: 0.0,
+                })
+                .with_health(BARREL_HEALTH)
+                .with_explosive(BARREL_BLAST),
+        )
+    }
+
+    /// Place a practice target standing on the floor at `position`.
+    pub fn spawn_target(&mut self, position: Vec3) -> EntityId {
+        self.world.spawn(
+            Entity::new(Tag::Target)
+                .with_body(RigidBody::new(TARGET_MASS, position))
+                .with_collider(Collider::new(Vec3::splat(TARGET_SIZE * 0.5)))
+                .with_renderable(Renderable {
+                    size: TARGET_SIZE,
+                    color: TARGET_COLOR,
+                    tracer: 0.0,
+                })
+                .with_health(TARGET_HEALTH),
+        )
+    }
+
+    /// Put up a fresh row of practice targets.
+    pub fn setup_practice_range(&mut self) {
+        for position in PRACTICE_TARGETS {
+            self.spawn_target(position);
+        }
+    }
+
+    /// Advance physics by exactly one step on the next update while it is
+    /// frozen. Does nothing extra when it isn't.
+    pub fn request_step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// Enemy behavior and one physics step, with the hits it produced.
+    fn simulate(&mut self, static_obs: &[Aabb], dt: f32) {
+        let player = &mut self.player;
+        if let Some((enemy_id, e)) = &mut self.enemy {
+            e.steer(player.body.position);
+            e.update(dt);
+            let interval = self.options.scenario.difficulty.fire_interval();
+            if self.tech_unlocked && e.in_aggro_range(player.body.position) && e.trigger(interval) {
+                let dir = (player.body.position - e.body.position).normalize();
+                let spawn_pos = e.body.position + Vec3::new(dir.x * 0.7, 0.6, dir.z * 0.7);
+                let target = e.aim_point(
+                    spawn_pos,
+                    player.body.position,
+                    player.body.velocity,
+                    ENEMY_BULLET_SPEED,
+                );
+                let bdir = e.aim(spawn_pos, target) * ENEMY_BULLET_SPEED;
+                let mut body = RigidBody::new(0.05, spawn_pos);
+                body.velocity = bdir;
+                self.world.spawn(
+                    Entity::new(Tag::Bullet)
+                        .with_body(body)
+                        .with_collider(Projectile::collider(
+                            0.1,
+                            layers::ENEMY_BULLET,
+                            layers::ENEMY,
+                        ))
+                        .with_renderable(Renderable {
+                            size: e.bullet_size,
+                            color: e.bullet_color,
+                            tracer: BULLET_TRACER,
+                        })
+                        .with_projectile(
+                            Projectile::new(Some(*enemy_id)).with_arming(BULLET_ARMING_TIME),
+                        ),
+                );
+            }
+        }
+
+        // Physics simulation step
+        // Сохраняем prev_y ДО добавления player.body в objs
+        let prev_y = player.body.velocity.y;
+        // ids[i] — сущность, которой принадлежит objs[i]
+        let mut ids = vec![self.player_id];
+        let mut objs = vec![PhysicsObject {
+            body: &mut player.body,
+            collider: player.collider,
+        }];
+        if let Some((id, e)) = &mut self.enemy {
+            ids.push(*id);
+            objs.push(PhysicsObject {
+                body: &mut e.body,
+                collider: e.collider,
+            });
+        }
+        self.world.tick_projectiles(dt);
+        self.world.tick_lifetimes(dt);
+        self.world.physics_objects(&mut ids, &mut objs);
+
+        let started = Instant::now();
+        let (contacts, stats) = self.physics.step_stats(&mut objs, static_obs, dt);
+        self.physics_step_time = started.elapsed();
+        self.physics_stats = stats;
+        self.physics_steps += 1;
+        let pairs: Vec<_> = contacts.iter().map(|c| (c.a, c.b)).collect();
+
+        if player.body.on_ground && prev_y < 0.0 {
+            let speed = -prev_y;
+            let safe = 6.0;
+            if speed > safe {
+                let dmg = ((speed - safe) * player.body.mass / 4.0) as i32;
+                self.damage_player(dmg);
+            }
+        }
+
+        let mut hits = self.world.bullet_hits(&ids, &pairs);
+        for hit in &hits {
+            self.world.mark_hit(hit.bullet);
+        }
+        // Пробивающие снаряды сами решают, где остановиться
+        let targets = self.target_boxes();
+        hits.extend(self.world.penetrating_hits(&targets, dt));
+        for hit in hits {
+            self.apply_hit(hit);
+        }
+        self.contacts = contacts;
+        self.world.mark_static_hits(static_obs);
+        self.apply_blasts();
+        self.apply_kill_plane();
+        self.remove_dead_enemy();
+        self.break_targets();
+    }
+
+    /// Damage practice target `id`, counting and confirming the hit if the
+    /// shot was `by_player`. Targets are stationary, so the hit doesn't push
+    /// them.
+    fn hit_target(&mut self, id: EntityId, damage: i32, by_player: bool) {
+        self.world.damage(id, damage);
+        if !by_player {
+            return;
+        }
+        self.practice_hits += 1;
+        let broken = self
+            .world
+            .get(id)
+            .and_then(|e| e.health)
+            .is_some_and(|h| h <= 0);
+        self.confirm_hit(if broken { HitKind::Kill } else { HitKind::Hit });
+    }
+
+    /// Whether `bullet` is a projectile the player fired.
+    fn fired_by_player(&self, bullet: EntityId) -> bool {
+        self.world
+            .get(bullet)
+            .and_then(|b| b.projectile)
+            .is_some_and(|p| p.owner == Some(self.player_id))
+    }
+
+    /// Show a hitmarker and queue its tone for a shot of the player's
+    /// that landed. A kill isn't overridden by a plain hit right after.
+    pub fn confirm_hit(&mut self, kind: HitKind) {
+        if kind == HitKind::Kill || self.hitmarker.is_none_or(|m| m.kind == HitKind::Hit) {
+            self.hitmarker = Some(Hitmarker::new(kind));
+        }
+        if self.hit_sound != Some(HitKind::Kill) {
+            self.hit_sound = Some(kind);
+        }
+    }
+
+    /// Remove broken practice targets. Once the last one breaks the range
+    /// is set up again.
+    fn break_targets(&mut self) {
+        let broken = self.world.remove_destroyed(Tag::Target);
+        self.targets_destroyed += broken as u32;
+        if broken > 0 && self.world.with_tag(Tag::Target).next().is_none() {
+            self.setup_practice_range();
+        }
+    }
+
+    /// Set off destroyed barrels and apply their blasts to the player and
+    /// the enemy, which live outside the world.
+    fn apply_blasts(&mut self) {
+        for blast in self.world.detonate() {
+            let damage = blast.damage_at(self.player.body.position);
+            if damage > 0 && self.damage_player(damage) {
+                self.indicate_damage(blast.explosion.center);
+            }
+            blast.explosion.push(&mut self.player.body);
+            if let Some((_, e)) = &mut self.enemy {
+                e.health -= blast.damage_at(e.body.position);
+                blast.explosion.push(&mut e.body);
+            }
+        }
+    }
+
+    /// Show a damage indicator pointing toward `source`.
+    pub fn indicate_damage(&mut self, source: Vec3) {
+        self.damage_indicators.push(DamageIndicator::new(source));
+    }
+
+    /// Apply a projectile hit to its target: damage, knockback along the
+    /// bullet's momentum and, for the player, a damage indicator.
+    pub fn apply_hit(&mut self, hit: Hit) {
+        let by_player = self.fired_by_player(hit.bullet);
+        if hit.target == self.player_id {
+            if self.damage_player(hit.damage) {
+                let source = self.hit_source(hit.bullet, hit.impulse);
+                self.indicate_damage(source);
+            }
+            self.player.body.apply_impulse(hit.impulse);
+        } else if self.world.tag(hit.target) == Some(Tag::Target) {
+            self.hit_target(hit.target, hit.damage, by_player);
+        } else if self.world.tag(hit.target) == Some(Tag::Barrel) {
+            self.world.damage(hit.target, hit.damage);
+            if let Some(body) = self.world.get_mut(hit.target).and_then(|e| e.body.as_mut()) {
+                body.apply_impulse(hit.impulse);
+            }
+        } else if let Some((id, e)) = &mut self.enemy {
+            if hit.target == *id {
+                e.health -= hit.damage;
+                e.body.apply_impulse(hit.impulse);
+                let kind = if e.health > 0 {
+                    HitKind::Hit
+                } else {
+                    HitKind::Kill
+                };
+                if by_player {
+                    self.confirm_hit(kind);
+                }
+            }
+        }
+    }
+
+    /// Boxes of every body projectiles can hit: the player, the enemy and
+    /// the world's targets.
+    fn target_boxes(&self) -> Vec<(EntityId, Aabb)> {
+        let mut targets = self.world.target_boxes();
+        targets.push((
+            self.player_id,
+            Aabb::around(self.player.body.position, &self.player.collider),
+        ));
+        if let Some((id, e)) = &self.enemy {
+            targets.push((*id, Aabb::around(e.body.position, &e.collider)));
+        }
+        targets
+    }
+
+    /// The enemy or prop the player is looking straight at, within
+    /// `TARGET_RANGE`. Level geometry in the way hides it.
+    pub fn body_under_crosshair(&self) -> Option<EntityId> {
+        let obstacles = self.static_obstacles();
+        let targets: Vec<_> = self
+            .target_boxes()
+            .into_iter()
+            .filter(|(id, _)| *id != self.player_id)
+            .collect();
+        let boxes: Vec<Aabb> = obstacles
+            .iter()
+            .copied()
+            .chain(targets.iter().map(|(_, b)| *b))
+            .collect();
+        let forward = self.player.rotation * Vec3::NEG_Z;
+        let hit = raycast(self.player.position, forward, TARGET_RANGE, &boxes)?;
+        let index = hit.index.checked_sub(obstacles.len())?;
+        Some(targets[index].0)
+    }
+
+    /// Where `bullet` was fired from: the enemy's position if it fired it,
+    /// otherwise a point back along the bullet's `impulse`.
+    fn hit_source(&self, bullet: EntityId, impulse: Vec3) -> Vec3 {
+        let owner = self
+            .world
+            .get(bullet)
+            .and_then(|e| e.projectile)
+            .and_then(|p| p.owner);
+        match &self.enemy {
+            Some((id, e)) if owner == Some(*id) => e.body.position,
+            _ => self.player.body.position - impulse.normalize_or_zero(),
+        }
+    }
+
+    /// Fade the screen out, respawn the player and fade back in. Does
+    /// nothing while a fade is already running.
+    pub fn respawn_with_fade(&mut self) {
+        if self.fade.is_none() {
+            self.fade = Some(ScreenFade::new(RESPAWN_FADE_OUT, RESPAWN_FADE_IN));
+        }
+    }
+
+    /// Put the player back at the level start, at rest and clear of the
+    /// level, protected for `spawn_protection` seconds.
+    pub fn respawn_player(&mut self) {
+        let start = self.options.scenario.level.player_start();
+        let obstacles = self.static_obstacles();
+        let player = &mut self.player;
+        teleport(&mut player.body, &player.collider, start, &obstacles);
+        player.position = player.body.position;
+        player.protect(self.spawn_protection);
+    }
+
+    /// Deal with every body below `kill_plane`.
+    fn apply_kill_plane(&mut self) {
+        self.world.remove_below(self.kill_plane);
+        if let Some((_, e)) = &mut self.enemy {
+            if e.body.position.y < self.kill_plane {
+                e.health = 0;
+            }
+        }
+        if self.player.body.position.y < self.kill_plane {
+            if self.god_mode {
+                self.respawn_with_fade();
+            } else {
+                self.health = 0;
+            }
+        }
+    }
+
+    fn remove_dead_enemy(&mut self) {
+        let Some((id, e)) = &self.enemy else {
+            return;
+        };
+        if e.health > 0 {
+            return;
+        }
+        self.world.despawn(*id);
+        // Части тела разлетаются от центра и гаснут
+        let center = e.body.position;
+        for (position, size) in e.parts() {
+            let outward =
+                Vec3::new(position.x - center.x, 0.0, position.z - center.z).normalize_or_zero();
+            let mut body = RigidBody::new(DEBRIS_MASS, position);
+            body.velocity = e.body.velocity + (outward + Vec3::Y) * DEBRIS_SPEED;
+            self.world.spawn(
+                Entity::new(Tag::Debris)
+                    .with_body(body)
+                    .with_collider(
+                        Collider::new(Vec3::splat(size * 0.5)).with_layer(layers::DEBRIS, 0),
+                    )
+                    .with_renderable(Renderable {
+                        size,
+                        color: e.color,
+                        tracer: 0.0,
+                    })
+                    .with_lifetime(DEBRIS_LIFETIME),
+            );
+        }
+        self.enemy = None;
+    }
+
+    /// Player position, velocity, speed and ground contact, one per line,
+    /// for tuning movement.
+    pub fn debug_text(&self) -> String {
+        let body = &self.player.body;
+        let (p, v) = (body.position, body.velocity);
+        format!(
+            "pos: {:.2} {:.2} {:.2}\nvel: {:.2} {:.2} {:.2}\nspeed: {:.2}\non_ground: {}",
+            p.x,
+            p.y,
+            p.z,
+            v.x,
+            v.y,
+            v.z,
+            v.length(),
+            body.on_ground
+        )
+    }
+
+    /// Readout of the last physics step for profiling.
+    pub fn physics_text(&self) -> String {
+        let stats = &self.physics_stats;
+        let frozen = if self.physics_frozen { " (frozen)" } else { "" };
+        format!(
+            "physics: {} objects, {} pairs, {} contacts\nstep {}{frozen}: {:.3} ms",
+            stats.objects,
+            stats.pairs,
+            stats.contacts,
+            self.physics_steps,
+            self.physics_step_time.as_secs_f64() * 1000.0
+        )
+    }
+
+    /// Whether the player is currently activating the beacon.
+    pub fn beacon_active(&self) -> bool {
+        self.activated
+    }
+
+    /// How many times the activation sound and message have played.
+    pub fn activation_count(&self) -> u32 {
+        self.activations
+    }
+
+    /// Light beam rising from the beacon while it is unlocked, so it can be
+    /// found from anywhere in the arena. Pulses with the artifact glow.
+    pub fn beacon_beam(&self) -> Option<CubeInstance> {
+        if !self.tech_unlocked {
+            return None;
+        }
+        let intensity = self.glow.intensity();
+        let base = self.beacon_trigger.center;
+        Some(
+            CubeInstance::new(
+                Vec3::new(base.x, BEAM_HEIGHT * 0.5, base.z),
+                BEAM_WIDTH,
+                BEAM_COLOR.map(|c| c * intensity),
+            )
+            .with_size(Vec3::new(BEAM_WIDTH, BEAM_HEIGHT, BEAM_WIDTH)),
+        )
+    }
+
+    /// Contacts found by the last physics step.
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Markers for every contact from the last physics step.
+    pub fn contact_cubes(&self) -> Vec<CubeInstance> {
+        self.contacts
+            .iter()
+            .flat_map(|c| contact_markers(c.point, c.normal, CONTACT_COLOR))
+            .collect()
+    }
+
+    /// Cubes for the enemy, its pistol, the beacon beam and every
+    /// renderable entity, plus contact markers when `show_contacts` is on.
+    pub fn cubes(&self) -> Vec<CubeInstance> {
+        let mut cubes = Vec::new();
+        cubes.extend(self.beacon_beam());
+        let target = self.body_under_crosshair();
+        if let Some((id, e)) = &self.enemy {
+            let start = cubes.len();
+            e.append_cubes(&mut cubes);
+            if target == Some(*id) {
+                for c in &mut cubes[start..] {
+                    c.highlight = true;
+                }
+            }
+            // Пистолет
+            let dir = (self.player.body.position - e.body.position).normalize_or_zero();
+            let pistol_pos = e.body.position + Vec3::new(dir.x * 0.7, 0.38, dir.z * 0.7);
+            cubes.push(CubeInstance::new(
+                pistol_pos,
+                0.11,
+                self.options.palette.pistol,
+            ));
+        }
+        self.world.append_cubes_highlighting(&mut cubes, target);
+        for tracer in &self.tracers {
+            cubes.push(tracer.cube(self.options.palette.bullet));
+        }
+        if self.show_contacts {
+            cubes.extend(self.contact_cubes());
+        }
+        cubes
+    }
+
+    /// The artifact ring and the floor.
+    pub fn static_obstacles(&self) -> Vec<Aabb> {
+        let mut obstacles = Player::artifact_aabbs(self.options.artifact_blocks);
+        obstacles.push(
+            Aabb::new(
+                Vec3::new(0.0, -0.5, 0.0),
+                Vec3::new(FLOOR_HALF_SIZE, 0.5, FLOOR_HALF_SIZE),
+            )
+            .with_material(Material::Floor),
+        );
+        obstacles
+    }
+
+    /// Draw the frame and, while the player lives, record it in `kill_cam`
+    /// along with the game time since the last one.
+    pub fn render(&mut self, engine: &mut Engine) {
+        // Геометрия кольца перестраивается, только если число блоков сменилось
+        if engine.renderer.artifact_blocks() != self.options.artifact_blocks {
+            engine
+                .renderer
+                .set_artifact_blocks(self.options.artifact_blocks);
+        }
+        // Пока идёт повтор, показываем записанные кадры с камеры у убийцы
+        let replay = self
+            .replay
+            .as_ref()
+            .and_then(|r| Some((r.frame()?, r.view()?)));
+        let cubes = match replay {
+            Some((frame, view)) => {
+                engine
+                    .renderer
+                    .update_camera(&(projection(&engine.renderer) * view));
+                frame.cubes.clone()
+            }
+            None => self.cubes(),
+        };
+        engine.renderer.viewmodel = if replay.is_some() {
+            Vec::new()
+        } else {
+            self.viewmodel.cubes()
+        };
+        engine.renderer.hud_ammo = self
+            .player
+            .inventory
+            .active()
+            .map(|w| (w.ammo.loaded, w.ammo.reserve));
+        let stamina = self.player.stamina;
+        engine.renderer.hud_stamina = Some(stamina.current / stamina.max);
+        let protection = self.player.protection_remaining();
+        engine.renderer.hud_protection = (protection > 0.0).then_some(protection);
+        // Замер времени GPU ждёт конца кадра, поэтому идёт, только пока
+        // отладочный вывод на экране
+        engine.renderer.profile_gpu = self.debug_hud;
+        let debug: Vec<String> = [
+            self.debug_hud.then(|| self.debug_text()),
+            self.debug_hud
+                .then(|| engine.renderer.gpu_profiler.report()),
+            self.show_physics_stats.then(|| self.physics_text()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        engine.renderer.hud_debug = (!debug.is_empty()).then(|| debug.join("\n"));
+        let size = engine.renderer.size;
+        let screen = Vec2::new(size.width as f32, size.height as f32);
+        let forward = self.player.rotation * Vec3::NEG_Z;
+        // Виньетка лежит под индикаторами урона, чтобы их было видно
+        engine.renderer.overlay_quads = self
+            .vignette
+            .quad(screen, self.health, MAX_HEALTH)
+            .into_iter()
+            .chain(
+                self.damage_indicators
+                    .iter()
+                    .map(|i| i.quad(self.player.position, forward, screen)),
+            )
+            .collect();
+        let velocity = self.player.body.velocity;
+        let speed = Vec2::new(velocity.x, velocity.z).length();
+        let spread = self
+            .player
+            .inventory
+            .active()
+            .map_or(0.0, |w| w.spread.current());
+        engine.renderer.fade_quad = self.fade.map(|f| f.quad(screen));
+        engine.renderer.crosshair_quads = if replay.is_some() {
+            Vec::new()
+        } else {
+            let mut quads = self.options.crosshair.quads(screen, speed, spread);
+            quads.extend(self.hitmarker.iter().flat_map(|m| m.quads(screen)));
+            quads
+        };
+        engine
+            .renderer
+            .render(self.overlay.as_deref(), self.health, &cubes);
+        if self.replay.is_none() {
+            self.kill_cam.record(ReplayFrame {
+                dt: std::mem::take(&mut self.unrecorded),
+                eye: self.player.position,
+                killer: self.enemy.as_ref().map(|(_, e)| e.body.position),
+                cubes,
+            });
+        }
+    }
+}
+
+/// Perspective of the game camera for the renderer's size and depth mode.
+fn projection(renderer: &Renderer) -> Mat4 {
+    renderer.perspective(60f32.to_radians(), 0.1, 100.0)
+}
@@ -0,0 +1,95 @@
+use crate::engine::overlay::OverlayQuad;
+use crate::engine::timer::Timer;
+use glam::Vec2;
+use std::f32::consts::FRAC_PI_4;
+
+/// Seconds a hitmarker stays up, fading as it goes.
+pub const HIT_TIME: f32 = 0.2;
+pub const KILL_TIME: f32 = 0.4;
+/// Distance from the screen center to the inner end of each arm, and arm
+/// length and thickness, in pixels.
+const GAP: f32 = 6.0;
+const LENGTH: f32 = 8.0;
+const THICKNESS: f32 = 2.0;
+/// Kill markers are drawn this much bigger.
+const KILL_SCALE: f32 = 1.5;
+const HIT_COLOR: [f32; 3] = [1.0, 1.0, 1.0];
+const KILL_COLOR: [f32; 3] = [1.0, 0.15, 0.1];
+/// Tone of the hit and kill sounds: frequency in Hz and length in seconds.
+const HIT_TONE: (f32, f32) = (1200.0, 0.05);
+const KILL_TONE: (f32, f32) = (600.0, 0.15);
+
+/// What a player's shot did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HitKind {
+    /// Damaged the target.
+    Hit,
+    /// Took the last of its health.
+    Kill,
+}
+
+impl HitKind {
+    /// Frequency in Hz and length in seconds of the tone confirming it.
+    pub fn tone(self) -> (f32, f32) {
+        match self {
+            Self::Hit => HIT_TONE,
+            Self::Kill => KILL_TONE,
+        }
+    }
+}
+
+/// X around the crosshair confirming that a shot landed, fading out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hitmarker {
+    pub kind: HitKind,
+    fade: Timer,
+}
+
+impl Hitmarker {
+    pub fn new(kind: HitKind) -> Self {
+        let time = match kind {
+            HitKind::Hit => HIT_TIME,
+            HitKind::Kill => KILL_TIME,
+        };
+        Self {
+            kind,
+            fade: Timer::new(time),
+        }
+    }
+
+    /// Advance the fade. Returns `false` once the marker is gone.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.fade.tick(dt);
+        !self.fade.finished()
+    }
+
+    /// Seconds until the marker is gone.
+    pub fn remaining(&self) -> f32 {
+        self.fade.remaining()
+    }
+
+    /// Opacity, from 1 right after the hit down to 0.
+    pub fn alpha(&self) -> f32 {
+        1.0 - self.fade.progress()
+    }
+
+    /// Four diagonal arms around the center of a `screen` sized in pixels.
+    pub fn quads(&self, screen: Vec2) -> Vec<OverlayQuad> {
+        let (scale, [r, g, b]) = match self.kind {
+            HitKind::Hit => (1.0, HIT_COLOR),
+            HitKind::Kill => (KILL_SCALE, KILL_COLOR),
+        };
+        let offset = (GAP + LENGTH * 0.5) * scale;
+        [1.0f32, 3.0, 5.0, 7.0]
+            .map(|i| {
+                let angle = i * FRAC_PI_4;
+                OverlayQuad::new(
+                    screen * 0.5 + Vec2::from_angle(angle) * offset,
+                    Vec2::new(LENGTH, THICKNESS) * scale,
+                    [r, g, b, self.alpha()],
+                )
+                .with_rotation(angle)
+            })
+            .to_vec()
+    }
+}
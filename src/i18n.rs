@@ -0,0 +1,52 @@
+/// Languages the UI strings are translated into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    Russian,
+    English,
+}
+
+impl Language {
+    /// Parse a short language code such as `ru` or `en`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_lowercase().as_str() {
+            "ru" => Some(Language::Russian),
+            "en" => Some(Language::English),
+            _ => None,
+        }
+    }
+}
+
+/// String id, Russian text, English text.
+const STRINGS: &[(&str, &str, &str)] = &[
+    (
+        "beacon_unlocked",
+        "Технология разблокирована: энергетический маяк",
+        "Technology unlocked: energy beacon",
+    ),
+    ("game_over", "Вы погибли", "You died"),
+    ("practice_hits", "Попаданий:", "Hits:"),
+];
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Locale {
+    pub language: Language,
+}
+
+impl Locale {
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// Look up `key` in the current language. Unknown keys come back
+    /// unchanged so a missing entry shows up on screen instead of crashing.
+    pub fn get<'a>(&self, key: &'a str) -> &'a str {
+        match STRINGS.iter().find(|(id, _, _)| *id == key) {
+            Some((_, ru, en)) => match self.language {
+                Language::Russian => ru,
+                Language::English => en,
+            },
+            None => key,
+        }
+    }
+}
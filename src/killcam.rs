@@ -0,0 +1,146 @@
+use crate::engine::renderer::CubeInstance;
+use glam::{Mat4, Vec3};
+use std::collections::VecDeque;
+
+/// How far behind the killer the replay camera sits, and how far above.
+const CAMERA_DISTANCE: f32 = 4.0;
+const CAMERA_HEIGHT: f32 = 1.5;
+
+/// What one game frame looked like, enough to draw it again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// Game time the frame covered.
+    pub dt: f32,
+    /// Where the player's eye was.
+    pub eye: Vec3,
+    /// Where whoever killed the player was, if they were around.
+    pub killer: Option<Vec3>,
+    pub cubes: Vec<CubeInstance>,
+}
+
+/// Rolling record of the last `length` seconds of frames, turned into a
+/// replay when the player dies.
+#[derive(Clone, Debug)]
+pub struct KillCam {
+    pub length: f32,
+    frames: VecDeque<ReplayFrame>,
+    recorded: f32,
+}
+
+impl KillCam {
+    pub fn new(length: f32) -> Self {
+        Self {
+            length,
+            frames: VecDeque::new(),
+            recorded: 0.0,
+        }
+    }
+
+    /// Add a frame, dropping the oldest ones that no longer fit in
+    /// `length`. A frame that covered no game time, as while time stands
+    /// still, is not kept.
+    pub fn record(&mut self, frame: ReplayFrame) {
+        if frame.dt <= 0.0 {
+            return;
+        }
+        self.recorded += frame.dt;
+        self.frames.push_back(frame);
+        while let Some(oldest) = self.frames.front() {
+            if self.recorded - oldest.dt < self.length {
+                break;
+            }
+            self.recorded -= oldest.dt;
+            self.frames.pop_front();
+        }
+    }
+
+    /// Number of frames held.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Game time covered by the frames held.
+    pub fn duration(&self) -> f32 {
+        self.recorded
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.recorded = 0.0;
+    }
+
+    /// Replay of everything recorded so far, from the oldest frame.
+    pub fn replay(&self) -> Replay {
+        Replay {
+            frames: self.frames.iter().cloned().collect(),
+            index: 0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Recorded frames played back at the pace they were recorded.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    frames: Vec<ReplayFrame>,
+    index: usize,
+    /// Time spent on the current frame.
+    elapsed: f32,
+}
+
+impl Replay {
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Index of the frame being shown.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Frame being shown, or `None` once the replay has finished.
+    pub fn frame(&self) -> Option<&ReplayFrame> {
+        self.frames.get(self.index)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.index >= self.frames.len()
+    }
+
+    /// Advance playback by `dt`, moving past every frame whose time is up.
+    pub fn tick(&mut self, dt: f32) {
+        self.elapsed += dt;
+        while let Some(frame) = self.frames.get(self.index) {
+            if self.elapsed < frame.dt {
+                break;
+            }
+            self.elapsed -= frame.dt;
+            self.index += 1;
+        }
+    }
+
+    /// Start over from the first frame.
+    pub fn rewind(&mut self) {
+        self.index = 0;
+        self.elapsed = 0.0;
+    }
+
+    /// View matrix of the current frame: from behind the killer looking at
+    /// them, with the player beyond. Without a killer it looks at where
+    /// the player stood.
+    pub fn view(&self) -> Option<Mat4> {
+        let frame = self.frame()?;
+        let target = frame.killer.unwrap_or(frame.eye);
+        let back = (target - frame.eye).try_normalize().unwrap_or(Vec3::Z);
+        let eye = target + back * CAMERA_DISTANCE + Vec3::Y * CAMERA_HEIGHT;
+        Some(Mat4::look_at_rh(eye, target, Vec3::Y))
+    }
+}
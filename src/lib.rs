@@ -1,2 +1,15 @@
+pub mod config;
+pub mod crosshair;
+pub mod damage_indicator;
 pub mod engine;
+pub mod framedump;
+pub mod game;
+pub mod hitmarker;
+pub mod i18n;
+pub mod killcam;
+pub mod palette;
 pub mod player;
+pub mod viewmodel;
+pub mod vignette;
+pub mod weapon;
+pub mod world;
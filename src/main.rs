@@ -1,297 +1,169 @@
-use astroforge::engine;
-use astroforge::engine::renderer::CubeInstance;
+use astroforge::config::LaunchConfig;
+use astroforge::crosshair::{Crosshair, CrosshairStyle};
+use astroforge::engine::artifact::RING_BLOCKS;
+use astroforge::engine::hud::HudTheme;
+use astroforge::engine::input::{parse_script, InputFrame};
+use astroforge::engine::renderer::DepthMode;
+use astroforge::engine::screenshot;
+use astroforge::engine::timer::RateLimiter;
 use astroforge::engine::Engine;
-use astroforge::player::{Enemy, Player};
-use base64::Engine as _;
-use glam::{Mat4, Vec2, Vec3};
-use image::{ImageBuffer, Rgba};
+use astroforge::framedump;
+use astroforge::game::{Game, GameOptions};
+use astroforge::i18n::{Language, Locale};
+use astroforge::palette::Palette;
 use std::env;
-use std::time::Instant;
+use std::path::Path;
+use std::time::SystemTime;
+use winit::event::VirtualKeyCode;
 
-const ACTIVATION_B64: &str = include_str!("../assets/activation.ogg.b64");
-
-struct Bullet {
-    position: Vec3,
-    body: engine::physics::RigidBody,
-    collider: engine::physics::Collider,
-    alive: bool,
-}
-
-fn save_screenshot(buffer: &[u8], width: u32, height: u32, path: &str) {
-    let img = ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, buffer.to_vec())
-        .expect("Failed to create image buffer");
-    img.save(path).expect("Failed to save screenshot");
-}
+/// Больше обновлений игры в секунду не делаем, даже на мониторе 240 Гц.
+const MAX_UPDATE_RATE: f32 = 120.0;
 
 fn main() {
     println!("🚀 AstroForge запуск собственного движка...");
     let args: Vec<String> = env::args().collect();
-    let selftest = args.iter().any(|a| a == "--selftest");
-    let screenshot = args.iter().any(|a| a == "--screenshot");
-    let is_wayland = env::var("WAYLAND_DISPLAY").is_ok();
-    let window_title = if is_wayland {
-        "AstroForge"
+    let value_of = |flag: &str| {
+        args.windows(2)
+            .find(|w| w[0] == flag)
+            .map(|w| w[1].as_str())
+    };
+    // --level, --difficulty, --no-enemies, --god, --practice, --selftest,
+    // --screenshot
+    let config = LaunchConfig::from_args(&args[1..]).unwrap_or_else(|e| panic!("{e}"));
+    // --colorblind: палитра, различимая при красно-зелёной слепоте
+    let colorblind = args.iter().any(|a| a == "--colorblind");
+    let palette = if colorblind {
+        Palette::colorblind()
     } else {
-        "Технология разблокирована: энергетический маяк"
+        Palette::default()
     };
-    let mut engine = Engine::new(window_title, 1024, 768);
-    let mut player = Player::new();
-    let default_title = window_title;
-    let mut tech_unlocked = false;
-    let mut message_timer = 0.0f32;
-
-    let mut enemy: Option<Enemy> = None;
-    let mut bullets: Vec<Bullet> = Vec::new();
-    let mut spawn_timer = 0.0f32;
-    let mut spawn_started = false;
-    let mut health: i32 = 100;
-    let mut game_over = false;
-
-    let b64_clean: String = ACTIVATION_B64
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric() || *c == '+' || *c == '/' || *c == '=')
-        .collect();
-    let bytes = base64::engine::general_purpose::STANDARD
-        .decode(b64_clean)
-        .expect("valid base64");
-
-    let mut last = Instant::now();
-    let mut activated = false;
-    let mut pulse = 0.0f32;
-    let mut overlay_tested = false;
-    let overlay_text_cyr = "Технология разблокирована: энергетический маяк";
-
-    engine.run(move |engine| {
-        let now = Instant::now();
-        let dt = now.duration_since(last).as_secs_f32();
-        last = now;
-
-        player.update(&engine.input, dt);
-        let view =
-            Mat4::from_quat(player.rotation).inverse() * Mat4::from_translation(-player.position);
-        let aspect = engine.renderer.size.width as f32 / engine.renderer.size.height as f32;
-        let proj = Mat4::perspective_rh(60f32.to_radians(), aspect, 0.1, 100.0);
-        engine.renderer.update_camera(&(proj * view));
-
-        let dist = Vec2::new(player.position.x, player.position.z).length();
-        let mut overlay_text: Option<&str> = None;
-
-        if selftest {
-            overlay_text = Some(overlay_text_cyr);
-            if !overlay_tested {
-                // Попытка отрисовать overlay, вывод результата после первого кадра
-                // (если не упало, считаем успехом)
-                println!("Overlay Cyrillic test: OK");
-                overlay_tested = true;
-                // Можно завершить игру после теста, если нужно:
-                // std::process::exit(0);
-            }
+    // Цвета здоровья в теме HUD подменяются только в режиме для дальтоников
+    let hud_theme = move |theme: HudTheme| {
+        if colorblind {
+            palette.apply_to_hud(theme)
         } else {
-            if dist < 3.0 {
-                if !activated && player.body.on_ground {
-                    activated = true;
-                    engine.audio.play_bytes(&bytes);
-                    if !tech_unlocked {
-                        tech_unlocked = true;
-                        message_timer = 3.0;
-                    }
-                }
-                pulse += dt * 3.0;
-                let intensity = 0.2 + 0.8 * (0.5 + 0.5 * (pulse).sin());
-                engine.renderer.update_artifact(intensity);
-            } else {
-                if activated {
-                    activated = false;
-                    pulse = 0.0;
-                }
-                engine.renderer.update_artifact(0.2);
-            }
-
-            if message_timer > 0.0 {
-                message_timer -= dt;
-                overlay_text = Some(overlay_text_cyr);
-                if message_timer <= 0.0 {
-                    overlay_text = None;
-                }
-            }
+            theme
         }
+    };
+    // --reversed-z: буфер глубины очищается в 0 и сравнивается через Greater
+    let depth_mode = if args.iter().any(|a| a == "--reversed-z") {
+        DepthMode::Reversed
+    } else {
+        DepthMode::Standard
+    };
+    // --lang en|ru, по умолчанию русский
+    let language = value_of("--lang")
+        .and_then(Language::from_code)
+        .unwrap_or_default();
+    let locale = Locale::new(language);
+    // --artifact-blocks N: сколько блоков в кольце артефакта
+    let artifact_blocks = value_of("--artifact-blocks")
+        .map(|n| n.parse().expect("--artifact-blocks takes a block count"))
+        .unwrap_or(RING_BLOCKS);
+    // --crosshair dot|cross|circle|dynamic, по умолчанию крест
+    let crosshair = Crosshair::new(
+        value_of("--crosshair")
+            .and_then(CrosshairStyle::from_name)
+            .unwrap_or_default(),
+    );
+    let options = GameOptions {
+        locale,
+        palette,
+        selftest: config.selftest,
+        artifact_blocks,
+        crosshair,
+        scenario: config.scenario,
+    };
 
-        // enemy spawn logic after tech unlock
-        if tech_unlocked && !spawn_started {
-            spawn_timer = 5.0;
-            spawn_started = true;
-        }
-        if spawn_started && spawn_timer > 0.0 {
-            spawn_timer -= dt;
-            if spawn_timer <= 0.0 {
-                enemy = Some(Enemy::new());
-            }
-        }
+    if config.screenshot {
+        // Один кадр без окна
+        let (width, height) = (1024u32, 768u32);
+        let mut engine = Engine::new_headless(width, height);
+        engine.renderer.hud_theme = hud_theme(HudTheme::default());
+        engine.renderer.set_depth_mode(depth_mode);
+        Game::new(options).frame(&mut engine, framedump::FRAME_DT);
+        let buffer = engine.renderer.get_frame_rgba8();
+        screenshot::save_png(&buffer, width, height, Path::new("screenshot.png"))
+            .expect("Failed to save screenshot");
+        println!("Screenshot saved to screenshot.png");
+        return;
+    }
 
-        if let Some(e) = &mut enemy {
-            let dir = Vec3::new(
-                player.body.position.x - e.body.position.x,
-                0.0,
-                player.body.position.z - e.body.position.z,
-            );
-            if dir.length_squared() > 0.0001 {
-                let dir = dir.normalize();
-                e.body.apply_force(dir * 200.0);
+    // --dump-frames N [--dump-script file] [--dump-dir dir]: прогнать N кадров
+    // без окна по сценарию ввода и сохранить каждый кадр в PNG
+    if let Some(count) = value_of("--dump-frames") {
+        let count: usize = count.parse().expect("--dump-frames takes a frame count");
+        let mut script = match value_of("--dump-script") {
+            Some(path) => {
+                let text = std::fs::read_to_string(path).expect("read input script");
+                parse_script(&text).unwrap_or_else(|e| panic!("{path}: {e}"))
             }
-            e.body.apply_force(-e.body.velocity * 5.0 * e.body.mass);
-            e.update(dt);
-            if tech_unlocked && e.bullet_timer <= 0.0 {
-                e.bullet_timer = 2.0;
-                let dir = (player.body.position - e.body.position).normalize();
-                let spawn_pos = e.body.position + Vec3::new(dir.x * 0.7, 0.6, dir.z * 0.7);
-                let bdir = (player.body.position - spawn_pos).normalize() * 5.0;
-                bullets.push(Bullet {
-                    position: spawn_pos,
-                    body: engine::physics::RigidBody::new(0.05, spawn_pos),
-                    collider: engine::physics::Collider {
-                        half_extents: Vec3::splat(0.1),
-                    },
-                    alive: true,
-                });
-            }
-        }
-
-        // Physics simulation step
-        let mut static_obs = Player::artifact_aabbs();
-        static_obs.push(engine::physics::Aabb {
-            center: Vec3::new(0.0, -0.5, 0.0),
-            half_extents: Vec3::new(50.0, 0.5, 50.0),
-        });
-        // Сохраняем prev_y ДО добавления player.body в objs
-        let prev_y = player.body.velocity.y;
-        let mut objs: Vec<engine::physics::PhysicsObject> = Vec::new();
-        let player_idx = objs.len();
-        objs.push(engine::physics::PhysicsObject {
-            body: &mut player.body,
-            collider: player.collider,
-        });
-        let enemy_idx = if let Some(e) = &mut enemy {
-            let idx = objs.len();
-            objs.push(engine::physics::PhysicsObject {
-                body: &mut e.body,
-                collider: e.collider,
-            });
-            Some(idx)
-        } else {
-            None
+            // По умолчанию игрок просто идёт вперёд
+            None => vec![InputFrame::holding(&[VirtualKeyCode::W])],
         };
-        let mut bullet_indices = Vec::new();
-        for b in &mut bullets {
-            let idx = objs.len();
-            bullet_indices.push(idx);
-            objs.push(engine::physics::PhysicsObject {
-                body: &mut b.body,
-                collider: b.collider,
-            });
-        }
+        let last = script.last().cloned().unwrap_or_default();
+        script.resize(count, last);
+        let dir = Path::new(value_of("--dump-dir").unwrap_or("frames"));
+        let mut engine = Engine::new_headless(1024, 768);
+        engine.renderer.hud_theme = hud_theme(HudTheme::default());
+        engine.renderer.set_depth_mode(depth_mode);
+        let mut game = Game::new(options);
+        let paths = framedump::dump_frames(&mut game, &mut engine, &script, dir)
+            .expect("Failed to dump frames");
+        println!("Сохранено кадров: {} в {}", paths.len(), dir.display());
+        return;
+    }
 
-        let pairs = engine::physics::step(&mut objs, &static_obs, dt);
+    let is_wayland = env::var("WAYLAND_DISPLAY").is_ok();
+    let window_title = if is_wayland {
+        "AstroForge"
+    } else {
+        locale.get("beacon_unlocked")
+    };
+    let mut engine = Engine::new(window_title, 1024, 768);
+    engine.renderer.hud_theme = hud_theme(HudTheme::default());
+    engine.renderer.set_depth_mode(depth_mode);
+    engine.update_limit = Some(RateLimiter::new(MAX_UPDATE_RATE));
+    // --fps-cap N: не больше N кадров в секунду, 0 — без ограничения
+    engine.frame_cap = value_of("--fps-cap")
+        .map(|fps| fps.parse::<f32>().expect("--fps-cap takes a frame rate"))
+        .filter(|&fps| fps > 0.0);
+    let mut game = Game::new(options);
 
-        if player.body.on_ground && prev_y < 0.0 {
-            let speed = -prev_y;
-            let safe = 6.0;
-            if speed > safe {
-                let dmg = ((speed - safe) * player.body.mass / 4.0) as i32;
-                if health > 0 {
-                    health -= dmg;
-                }
-            }
-        }
+    engine.run(move |engine| {
+        let dt = engine.dt;
 
-        for (a, b) in pairs {
-            // bullet hitting player or enemy
-            if let Some(bullet_i) = bullet_indices.iter().position(|&x| x == a) {
-                let bullet = &mut bullets[bullet_i];
-                if a == player_idx || b == player_idx {
-                    bullet.alive = false;
-                    let momentum = bullet.body.velocity.length() * bullet.body.mass;
-                    if health > 0 {
-                        health -= (momentum * 50.0) as i32;
-                    }
-                    player
-                        .body
-                        .apply_impulse(bullet.body.velocity * bullet.body.mass);
-                } else if let Some(e_idx) = enemy_idx {
-                    if a == e_idx || b == e_idx {
-                        bullet.alive = false;
-                    }
-                }
-            } else if let Some(bullet_i) = bullet_indices.iter().position(|&x| x == b) {
-                let bullet = &mut bullets[bullet_i];
-                if a == player_idx || b == player_idx {
-                    bullet.alive = false;
-                    let momentum = bullet.body.velocity.length() * bullet.body.mass;
-                    if health > 0 {
-                        health -= (momentum * 50.0) as i32;
-                    }
-                    player
-                        .body
-                        .apply_impulse(bullet.body.velocity * bullet.body.mass);
-                } else if let Some(e_idx) = enemy_idx {
-                    if a == e_idx || b == e_idx {
-                        bullet.alive = false;
-                    }
-                }
-            }
+        // F2 переключает контрастную тему HUD
+        if engine.input.just_pressed(VirtualKeyCode::F2) {
+            let standard = hud_theme(HudTheme::default());
+            engine.renderer.hud_theme = if engine.renderer.hud_theme == standard {
+                hud_theme(HudTheme::high_contrast())
+            } else {
+                standard
+            };
         }
-
-        for b in &mut bullets {
-            if b.body.velocity.length_squared() == 0.0 {
-                b.alive = false;
-            }
+        // F1 прячет и возвращает весь HUD
+        if engine.input.just_pressed(VirtualKeyCode::F1) {
+            engine.renderer.hud_visible = !engine.renderer.hud_visible;
         }
-        bullets.retain(|b| b.alive);
-
-        let mut cubes: Vec<CubeInstance> = Vec::new();
-        if let Some(e) = &enemy {
-            e.append_cubes(&mut cubes);
-            // Пистолет
-            let dir = (player.body.position - e.body.position).normalize_or_zero();
-            let pistol_pos = e.body.position + Vec3::new(dir.x * 0.7, 0.38, dir.z * 0.7);
-            cubes.push(CubeInstance {
-                position: pistol_pos,
-                size: 0.11,
-                color: [0.0, 1.0, 0.0],
-            });
+        // F7 переключает каркасный режим, если адаптер его умеет
+        if engine.input.just_pressed(VirtualKeyCode::F7) {
+            let on = !engine.renderer.wireframe();
+            engine.renderer.set_wireframe(on);
         }
-        for b in &bullets {
-            cubes.push(CubeInstance {
-                position: b.position,
-                size: 0.1,
-                color: [1.0, 1.0, 0.0],
-            });
+        // F12 сохраняет текущий кадр, не прерывая игру
+        if engine.input.just_pressed(VirtualKeyCode::F12) {
+            engine.renderer.request_capture();
         }
-
-        if health <= 0 && !game_over {
-            overlay_text = Some("Вы погибли");
-            game_over = true;
+        game.frame(engine, dt);
+        if let Some(frame) = engine.renderer.take_capture() {
+            let size = engine.renderer.size;
+            let path = screenshot::timestamped_path(Path::new("."), SystemTime::now());
+            match screenshot::save_png(&frame, size.width, size.height, &path) {
+                Ok(()) => println!("Скриншот сохранён: {}", path.display()),
+                Err(e) => eprintln!("[ERROR] Не удалось сохранить скриншот: {e}"),
+            }
         }
-
-        engine.renderer.render(overlay_text, health, &cubes);
         engine.input.reset();
     });
-
-    if screenshot {
-        // Headless/one-frame screenshot mode
-        let width = 1024u32;
-        let height = 768u32;
-        let mut engine = Engine::new(window_title, width, height);
-        let mut player = Player::new();
-        let mut bullets: Vec<Bullet> = Vec::new();
-        // ...инициализация сцены, как обычно...
-        // Рендерим один кадр
-        let mut cubes: Vec<CubeInstance> = Vec::new();
-        // ...добавьте сюда нужные объекты для теста...
-        engine.renderer.render(None, 100, &cubes);
-        // Получаем буфер кадра (пример, зависит от вашей реализации renderer)
-        let buffer = engine.renderer.get_frame_rgba8(); // реализуйте этот метод
-        save_screenshot(&buffer, width, height, "screenshot.png");
-        println!("Screenshot saved to screenshot.png");
-        return;
-    }
 }
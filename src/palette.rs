@@ -0,0 +1,51 @@
+use crate::engine::hud::HudTheme;
+
+/// Colors of gameplay-critical objects. `colorblind` swaps the red/green
+/// pairs for a blue/orange scheme that stays distinct under red-green
+/// color blindness.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub enemy: [f32; 3],
+    /// The player's bullets and rifle tracers.
+    pub bullet: [f32; 3],
+    pub enemy_bullet: [f32; 3],
+    pub pistol: [f32; 3],
+    /// Health readout at full and at zero health.
+    pub health_high: [f32; 4],
+    pub health_low: [f32; 4],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            enemy: [1.0, 0.0, 0.0],
+            bullet: [1.0, 1.0, 0.0],
+            enemy_bullet: [1.0, 0.35, 0.1],
+            pistol: [0.0, 1.0, 0.0],
+            health_high: [0.0, 1.0, 0.0, 1.0],
+            health_low: [1.0, 0.0, 0.0, 1.0],
+        }
+    }
+}
+
+impl Palette {
+    pub fn colorblind() -> Self {
+        Self {
+            enemy: [0.9, 0.6, 0.0],
+            bullet: [0.95, 0.9, 0.25],
+            enemy_bullet: [0.9, 0.45, 0.0],
+            pistol: [0.35, 0.7, 0.9],
+            health_high: [0.35, 0.7, 0.9, 1.0],
+            health_low: [0.9, 0.6, 0.0, 1.0],
+        }
+    }
+
+    /// `theme` with its health colors taken from this palette.
+    pub fn apply_to_hud(&self, theme: HudTheme) -> HudTheme {
+        HudTheme {
+            health_high: self.health_high,
+            health_low: self.health_low,
+            ..theme
+        }
+    }
+}
@@ -1,8 +1,128 @@
+use crate::engine::artifact::ring_positions;
 use crate::engine::input::InputState;
-use crate::engine::physics::{Aabb, Collider, RigidBody};
-use glam::{Quat, Vec3};
+use crate::engine::physics::{layers, Aabb, Collider, Material, RigidBody};
+use crate::engine::renderer::CubeInstance;
+use crate::engine::rng::Rng;
+use crate::engine::timer::Timer;
+use crate::palette::Palette;
+use crate::weapon::Inventory;
+use glam::{Quat, Vec2, Vec3};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
 use winit::event::VirtualKeyCode;
 
+/// Секунд между рывками.
+const DASH_COOLDOWN: f32 = 1.0;
+/// Сколько секунд после рывка игрок неуязвим.
+const DASH_IFRAMES: f32 = 0.25;
+/// Расход выносливости: за рывок и за секунду бега.
+const DASH_STAMINA: f32 = 30.0;
+const SPRINT_STAMINA_PER_SEC: f32 = 25.0;
+/// Радиан поворота на пиксель движения мыши.
+const LOOK_SENSITIVITY: f32 = 0.002;
+/// Частота кадров, для которой задана доля `look_smoothing`: при другой
+/// частоте доля пересчитывается, и сглаживание ощущается так же.
+const LOOK_SMOOTHING_FPS: f32 = 60.0;
+/// Наклон взгляда вверх и вниз по умолчанию, в радианах.
+const DEFAULT_PITCH_LIMIT: f32 = 1.54;
+/// Больше наклонить нельзя ни при каком пределе: у вертикали вид
+/// переворачивается.
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+/// Resource spent by sprinting and dashing. It refills whenever neither
+/// is happening.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stamina {
+    pub current: f32,
+    pub max: f32,
+    /// Refill per second.
+    pub regen: f32,
+}
+
+impl Stamina {
+    /// Full stamina.
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            regen: 15.0,
+        }
+    }
+
+    /// Spend `amount` only if all of it is available.
+    pub fn try_spend(&mut self, amount: f32) -> bool {
+        if self.current < amount {
+            return false;
+        }
+        self.current -= amount;
+        true
+    }
+
+    /// Spend up to `amount`. Returns `false` if stamina was already empty.
+    pub fn drain(&mut self, amount: f32) -> bool {
+        if self.current <= 0.0 {
+            return false;
+        }
+        self.current = (self.current - amount).max(0.0);
+        true
+    }
+
+    pub fn recover(&mut self, dt: f32) {
+        self.current = (self.current + self.regen * dt).min(self.max);
+    }
+}
+
+/// How movement force builds up while a direction is held.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AccelerationProfile {
+    /// Full force from the first frame.
+    #[default]
+    Instant,
+    /// Force grows evenly to full over `ramp` seconds.
+    Linear { ramp: f32 },
+    /// Force grows quadratically to full over `ramp` seconds, so the start
+    /// is slow and the end quick.
+    EaseIn { ramp: f32 },
+}
+
+impl AccelerationProfile {
+    /// Fraction of the movement force after moving for `held` seconds.
+    pub fn factor(&self, held: f32) -> f32 {
+        let progress = |ramp: f32| {
+            if ramp > 0.0 {
+                (held / ramp).clamp(0.0, 1.0)
+            } else {
+                1.0
+            }
+        };
+        match *self {
+            Self::Instant => 1.0,
+            Self::Linear { ramp } => progress(ramp),
+            Self::EaseIn { ramp } => progress(ramp).powi(2),
+        }
+    }
+}
+
+/// Soft aim assist for controllers: while the target is within `cone` of
+/// the view direction, look speed drops to `slowdown` of normal and the
+/// view is pulled toward it at up to `pull` radians per second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AimAssist {
+    /// Half-angle around the view direction, in radians.
+    pub cone: f32,
+    pub slowdown: f32,
+    pub pull: f32,
+}
+
+impl Default for AimAssist {
+    fn default() -> Self {
+        Self {
+            cone: 0.12,
+            slowdown: 0.5,
+            pull: 0.6,
+        }
+    }
+}
+
 pub struct Player {
     pub position: Vec3,
     pub rotation: Quat,
@@ -10,9 +130,45 @@ pub struct Player {
     pitch: f32,
     pub body: RigidBody,
     pub movement_force: f32,
+    /// How `movement_force` ramps up after starting to move.
+    pub acceleration: AccelerationProfile,
     pub jump_impulse: f32,
     pub friction: f32,
+    /// Horizontal impulse of a dash (Left Shift).
+    pub dash_impulse: f32,
+    /// Movement force multiplier while sprinting (Left Ctrl).
+    pub sprint_multiplier: f32,
+    pub stamina: Stamina,
+    /// Look smoothing in `[0, 1)`: the share of mouse motion still to
+    /// apply that is held back each 1/60 s. `0.0` applies mouse motion as
+    /// is.
+    pub look_smoothing: f32,
+    /// Furthest the view tilts up or down, in radians. Capped just short of
+    /// straight up and down, where the view would flip over.
+    pub pitch_limit: f32,
+    /// Off unless set.
+    pub aim_assist: Option<AimAssist>,
+    /// What aim assist locks onto, usually the enemy.
+    pub aim_target: Option<Vec3>,
     pub collider: Collider,
+    pub inventory: Inventory,
+    dash_cooldown: Timer,
+    iframes: Timer,
+    /// Invulnerability granted by `protect`, separate from the dash
+    /// i-frames so the HUD can show it.
+    protection: Timer,
+    /// Seconds a movement direction has been held without a break.
+    move_time: f32,
+    /// Mouse motion after smoothing, applied to the look last frame.
+    look_delta: Vec2,
+    /// Mouse motion smoothing has held back so far.
+    look_pending: Vec2,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Player {
@@ -25,34 +181,109 @@ impl Player {
             pitch: 0.0,
             body: RigidBody::new(80.0, start_pos),
             movement_force: 300.0,
+            acceleration: AccelerationProfile::default(),
             jump_impulse: 500.0,
             friction: 5.0,
-            collider: Collider {
-                half_extents: Vec3::new(0.5, 0.75, 0.5),
-            },
-        }
-    }
-
-    pub fn artifact_aabbs() -> Vec<Aabb> {
-        const COUNT: usize = 28;
-        const RADIUS: f32 = 3.0;
-        let mut blocks = Vec::with_capacity(COUNT);
-        for i in 0..COUNT {
-            let angle = i as f32 / COUNT as f32 * std::f32::consts::TAU;
-            let x = RADIUS * angle.cos();
-            let z = RADIUS * angle.sin();
-            blocks.push(Aabb {
-                center: Vec3::new(x, 0.5, z),
-                half_extents: Vec3::splat(0.5),
-            });
+            dash_impulse: 1200.0,
+            sprint_multiplier: 1.8,
+            stamina: Stamina::new(100.0),
+            look_smoothing: 0.0,
+            pitch_limit: DEFAULT_PITCH_LIMIT,
+            aim_assist: None,
+            aim_target: None,
+            // Капсула не цепляется за углы блоков артефакта
+            collider: Collider::capsule(0.5, 1.5)
+                .with_layer(layers::PLAYER, layers::ALL & !layers::PLAYER_BULLET),
+            inventory: Inventory::default(),
+            dash_cooldown: Timer::new(0.0),
+            iframes: Timer::new(0.0),
+            protection: Timer::new(0.0),
+            move_time: 0.0,
+            look_delta: Vec2::ZERO,
+            look_pending: Vec2::ZERO,
         }
-        blocks
+    }
+
+    /// Start at `position`, body included.
+    pub fn with_position(mut self, position: Vec3) -> Self {
+        self.position = position;
+        self.body.position = position;
+        self
+    }
+
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.body.mass = mass;
+        self
+    }
+
+    pub fn with_movement_force(mut self, force: f32) -> Self {
+        self.movement_force = force;
+        self
+    }
+
+    pub fn with_jump_impulse(mut self, impulse: f32) -> Self {
+        self.jump_impulse = impulse;
+        self
+    }
+
+    pub fn with_pitch_limit(mut self, limit: f32) -> Self {
+        self.pitch_limit = limit;
+        self
+    }
+
+    /// Current up or down tilt of the view, in radians; positive is up.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Resize the capsule, keeping its collision layers and material.
+    pub fn with_capsule(mut self, radius: f32, height: f32) -> Self {
+        let old = self.collider;
+        self.collider = Collider::capsule(radius, height)
+            .with_layer(old.layer, old.mask)
+            .with_material(old.material);
+        self
+    }
+
+    /// Collision boxes of an artifact ring made of `count` blocks.
+    pub fn artifact_aabbs(count: usize) -> Vec<Aabb> {
+        ring_positions(count)
+            .into_iter()
+            .map(|base| {
+                Aabb::new(base + Vec3::new(0.0, 0.5, 0.0), Vec3::splat(0.5))
+                    .with_material(Material::Artifact)
+            })
+            .collect()
     }
 
     pub fn update(&mut self, input: &InputState, dt: f32) {
-        let sensitivity = 0.002;
-        self.yaw -= input.mouse_delta.0 * sensitivity;
-        self.pitch = (self.pitch - input.mouse_delta.1 * sensitivity).clamp(-1.54, 1.54);
+        self.inventory.handle_input(input);
+        self.inventory.tick(dt);
+        self.dash_cooldown.tick(dt);
+        self.iframes.tick(dt);
+        self.protection.tick(dt);
+
+        // Сглаживание — экспоненциальный фильтр: рывок мыши растягивается
+        // на несколько кадров, но суммарный поворот тот же. Доля зависит
+        // от dt, чтобы поворот шёл одинаково при любой частоте кадров
+        let raw = Vec2::new(input.mouse_delta.0, input.mouse_delta.1);
+        let smoothing = self.look_smoothing.clamp(0.0, 0.99);
+        let keep = if smoothing > 0.0 {
+            smoothing.powf(dt * LOOK_SMOOTHING_FPS)
+        } else {
+            0.0
+        };
+        self.look_pending += raw;
+        self.look_delta = self.look_pending * (1.0 - keep);
+        self.look_pending -= self.look_delta;
+        let sensitivity = self.look_sensitivity();
+        self.yaw -= self.look_delta.x * sensitivity;
+        self.pitch -= self.look_delta.y * sensitivity;
+        if self.look_delta != Vec2::ZERO {
+            self.pull_toward_target(dt);
+        }
+        let limit = self.pitch_limit.clamp(0.0, MAX_PITCH);
+        self.pitch = self.pitch.clamp(-limit, limit);
         self.rotation =
             Quat::from_axis_angle(Vec3::Y, self.yaw) * Quat::from_axis_angle(Vec3::X, self.pitch);
 
@@ -71,6 +302,15 @@ impl Player {
         if input.pressed(VirtualKeyCode::D) {
             direction += right;
         }
+        let mut exerted = false;
+        if input.just_pressed(VirtualKeyCode::LShift) {
+            // Без клавиш движения рывок идёт туда, куда смотрит игрок
+            exerted = self.dash(if direction == Vec3::ZERO {
+                forward
+            } else {
+                direction
+            });
+        }
         if input.pressed(VirtualKeyCode::Space) && self.body.on_ground {
             self.body.apply_impulse(Vec3::Y * self.jump_impulse);
             self.body.on_ground = false;
@@ -81,7 +321,20 @@ impl Player {
         // influence the player.
         if direction.length_squared() > 0.0 {
             direction = direction.normalize();
-            self.body.apply_force(direction * self.movement_force);
+            self.move_time += dt;
+            let mut force = self.movement_force * self.acceleration.factor(self.move_time);
+            if input.pressed(VirtualKeyCode::LControl)
+                && self.stamina.drain(SPRINT_STAMINA_PER_SEC * dt)
+            {
+                force *= self.sprint_multiplier;
+                exerted = true;
+            }
+            self.body.apply_force(direction * force);
+        } else {
+            self.move_time = 0.0;
+        }
+        if !exerted {
+            self.stamina.recover(dt);
         }
 
         // Простое затухание скорости через силу трения
@@ -91,63 +344,413 @@ impl Player {
         // Синхронизируем позицию игрока с физическим телом
         self.position = self.body.position;
     }
+
+    /// Radians of turn per pixel of mouse motion, lowered by aim assist
+    /// while the target is near the crosshair.
+    pub fn look_sensitivity(&self) -> f32 {
+        match self.aim_assist {
+            Some(assist) if self.target_in_cone(assist) => LOOK_SENSITIVITY * assist.slowdown,
+            _ => LOOK_SENSITIVITY,
+        }
+    }
+
+    fn target_in_cone(&self, assist: AimAssist) -> bool {
+        let Some(target) = self.aim_target else {
+            return false;
+        };
+        let forward = self.rotation * Vec3::NEG_Z;
+        let to_target = target - self.position;
+        to_target != Vec3::ZERO && forward.angle_between(to_target) <= assist.cone
+    }
+
+    /// Turn yaw and pitch toward the aim target by at most `pull * dt`.
+    fn pull_toward_target(&mut self, dt: f32) {
+        let (Some(assist), Some(target)) = (self.aim_assist, self.aim_target) else {
+            return;
+        };
+        if !self.target_in_cone(assist) {
+            return;
+        }
+        let d = target - self.position;
+        let yaw = (-d.x).atan2(-d.z);
+        let pitch = d.y.atan2(Vec2::new(d.x, d.z).length());
+        let step = assist.pull * dt;
+        // Разница углов по кратчайшему пути
+        let yaw_diff = (yaw - self.yaw + PI).rem_euclid(TAU) - PI;
+        self.yaw += yaw_diff.clamp(-step, step);
+        self.pitch += (pitch - self.pitch).clamp(-step, step);
+    }
+
+    /// Dash horizontally along `direction` and become briefly invulnerable.
+    /// Returns `false` while the previous dash is cooling down or without
+    /// enough stamina.
+    pub fn dash(&mut self, direction: Vec3) -> bool {
+        if !self.dash_cooldown.finished() {
+            return false;
+        }
+        let horizontal = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+        if horizontal == Vec3::ZERO || !self.stamina.try_spend(DASH_STAMINA) {
+            return false;
+        }
+        self.body.apply_impulse(horizontal * self.dash_impulse);
+        self.dash_cooldown = Timer::new(DASH_COOLDOWN);
+        self.iframes = Timer::new(DASH_IFRAMES);
+        true
+    }
+
+    /// Ignore damage for the next `seconds`, e.g. right after spawning.
+    /// A longer protection that is already running is kept.
+    pub fn protect(&mut self, seconds: f32) {
+        if seconds > self.protection.remaining() {
+            self.protection = Timer::new(seconds);
+        }
+    }
+
+    /// Seconds of protection from `protect` left.
+    pub fn protection_remaining(&self) -> f32 {
+        self.protection.remaining()
+    }
+
+    /// Whether damage is ignored right now, just after a dash or while
+    /// protected.
+    pub fn invulnerable(&self) -> bool {
+        !self.iframes.finished() || !self.protection.finished()
+    }
+}
+
+/// How an enemy moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnemyBehavior {
+    /// Run toward the target.
+    #[default]
+    Chase,
+    /// Stay put. Used by tests that need the enemy to hold still.
+    Static,
 }
 
 pub struct Enemy {
     pub bullet_timer: f32,
+    pub health: i32,
     pub body: RigidBody,
     pub collider: Collider,
+    pub color: [f32; 3],
+    /// Edge length and color of the cubes drawn for this enemy's bullets.
+    pub bullet_size: f32,
+    pub bullet_color: [f32; 3],
+    pub behavior: EnemyBehavior,
+    /// Only a target within this horizontal distance is chased and shot
+    /// at; farther away the enemy idles.
+    pub aggro_range: f32,
+    /// Running force toward the target or the next waypoint.
+    pub movement_force: f32,
+    /// Velocity damping while chasing or patrolling, per second.
+    pub friction: f32,
+    /// Points walked between in order, looping, while no target is in
+    /// aggro range. Empty means the enemy stands still instead.
+    pub patrol: Vec<Vec3>,
+    waypoint: usize,
+    /// Half-angle, in radians, of the cone shots scatter over around the
+    /// target. Zero fires straight at it.
+    pub spread: f32,
+    /// Aim where a moving target will be when the shot gets there instead
+    /// of where it is now.
+    pub lead: bool,
+    /// Picks where in the cone each shot goes.
+    rng: Rng,
+    /// Shots fired each time `bullet_timer` runs out, `burst_delay`
+    /// seconds apart.
+    pub burst: u32,
+    pub burst_delay: f32,
+    /// Shots of the current burst still to fire, and the wait before the
+    /// next one.
+    burst_left: u32,
+    burst_timer: Timer,
 }
 
-const ENEMY_COLOR: [f32; 3] = [1.0, 0.0, 0.0];
+/// Пули врага крупнее пуль игрока, чтобы их было легче заметить.
+const ENEMY_BULLET_SIZE: f32 = 0.14;
+/// Пауза между выстрелами очереди, пока не задана другая.
+const BURST_DELAY: f32 = 0.12;
+/// Начальное состояние генератора разброса, пока не задано другое.
+const ENEMY_SEED: u64 = 0x5eed_a11e;
+/// На каком расстоянии по горизонтали точка патруля считается достигнутой.
+const WAYPOINT_RADIUS: f32 = 0.5;
+/// Ближе этого по горизонтали погоня расталкивает врагов друг от друга.
+const SEPARATION_RADIUS: f32 = 3.0;
+/// Сила расталкивания вплотную к соседу, к краю радиуса она спадает до нуля.
+/// Сильнее бега к цели, поэтому враги окружают игрока, а не сбиваются в кучу.
+const SEPARATION_FORCE: f32 = 600.0;
+
+/// Туловище, голова, ноги и руки: смещение от позиции тела и размер.
+const ENEMY_PARTS: [(Vec3, f32); 6] = [
+    (Vec3::new(0.0, 0.3, 0.0), 0.4),
+    (Vec3::new(0.0, 0.65, 0.0), 0.22),
+    (Vec3::new(-0.12, 0.08, 0.0), 0.16),
+    (Vec3::new(0.12, 0.08, 0.0), 0.16),
+    (Vec3::new(-0.23, 0.38, 0.0), 0.13),
+    (Vec3::new(0.23, 0.38, 0.0), 0.13),
+];
+
+impl Default for Enemy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Enemy {
     pub fn new() -> Self {
         Self {
             bullet_timer: 2.0,
+            health: 100,
             body: RigidBody::new(80.0, Vec3::new(8.0, 0.75, -8.0)),
-            collider: Collider {
-                half_extents: Vec3::new(0.5, 0.75, 0.5),
-            },
+            collider: Collider::new(Vec3::new(0.5, 0.75, 0.5))
+                .with_layer(layers::ENEMY, layers::ALL & !layers::ENEMY_BULLET),
+            color: Palette::default().enemy,
+            bullet_size: ENEMY_BULLET_SIZE,
+            bullet_color: Palette::default().enemy_bullet,
+            behavior: EnemyBehavior::default(),
+            aggro_range: f32::INFINITY,
+            movement_force: 200.0,
+            friction: 5.0,
+            patrol: Vec::new(),
+            waypoint: 0,
+            spread: 0.0,
+            lead: false,
+            rng: Rng::new(ENEMY_SEED),
+            burst: 1,
+            burst_delay: BURST_DELAY,
+            burst_left: 0,
+            burst_timer: Timer::new(0.0),
+        }
+    }
+
+    /// Start at `position`.
+    pub fn with_position(mut self, position: Vec3) -> Self {
+        self.body.position = position;
+        self
+    }
+
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.body.mass = mass;
+        self
+    }
+
+    pub fn with_health(mut self, health: i32) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_movement_force(mut self, force: f32) -> Self {
+        self.movement_force = force;
+        self
+    }
+
+    /// Resize the collision box, keeping its layers.
+    pub fn with_collider_size(mut self, half_extents: Vec3) -> Self {
+        self.collider.half_extents = half_extents;
+        self
+    }
+
+    /// Draw this enemy's bullets as cubes of `size` in `color`. Their
+    /// collision box stays the same.
+    pub fn with_bullet(mut self, size: f32, color: [f32; 3]) -> Self {
+        self.bullet_size = size;
+        self.bullet_color = color;
+        self
+    }
+
+    pub fn with_patrol(mut self, waypoints: Vec<Vec3>) -> Self {
+        self.patrol = waypoints;
+        self.waypoint = 0;
+        self
+    }
+
+    /// Waypoint the enemy is walking to while idle.
+    pub fn current_waypoint(&self) -> Option<Vec3> {
+        self.patrol.get(self.waypoint).copied()
+    }
+
+    pub fn with_aggro_range(mut self, range: f32) -> Self {
+        self.aggro_range = range;
+        self
+    }
+
+    /// Whether `target` is close enough to chase and shoot at.
+    pub fn in_aggro_range(&self, target: Vec3) -> bool {
+        let offset = target - self.body.position;
+        Vec2::new(offset.x, offset.z).length() <= self.aggro_range
+    }
+
+    pub fn with_behavior(mut self, behavior: EnemyBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_lead(mut self, lead: bool) -> Self {
+        self.lead = lead;
+        self
+    }
+
+    /// Fire `shots` at a time, `delay` seconds apart.
+    pub fn with_burst(mut self, shots: u32, delay: f32) -> Self {
+        self.burst = shots;
+        self.burst_delay = delay;
+        self
+    }
+
+    /// Whether to fire a shot now. Starts a burst once `bullet_timer` has
+    /// run out, setting it to `interval` for the next one, and then lets
+    /// the rest of the burst through `burst_delay` apart.
+    pub fn trigger(&mut self, interval: f32) -> bool {
+        if self.burst_left > 0 {
+            if !self.burst_timer.finished() {
+                return false;
+            }
+            self.burst_left -= 1;
+        } else if self.bullet_timer <= 0.0 {
+            self.bullet_timer = interval;
+            self.burst_left = self.burst.max(1) - 1;
+        } else {
+            return false;
+        }
+        self.burst_timer = Timer::new(self.burst_delay);
+        true
+    }
+
+    /// Point to shoot at with shots of `speed` fired from `from` at a
+    /// target at `target` moving at `velocity`. Without `lead`, or when the
+    /// shot can't catch the target, that is the target itself.
+    pub fn aim_point(&self, from: Vec3, target: Vec3, velocity: Vec3, speed: f32) -> Vec3 {
+        if !self.lead {
+            return target;
+        }
+        intercept(from, target, velocity, speed).unwrap_or(target)
+    }
+
+    /// Restart the shot scatter from `seed`, so the same seed repeats the
+    /// same shots.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Direction of a shot fired from `from` at `target`, scattered within
+    /// `spread`.
+    pub fn aim(&mut self, from: Vec3, target: Vec3) -> Vec3 {
+        self.rng.in_cone(target - from, self.spread)
+    }
+
+    /// Apply this frame's movement forces toward `target`. Out of aggro
+    /// range the enemy walks its patrol, or only brakes without one.
+    pub fn steer(&mut self, target: Vec3) {
+        match self.behavior {
+            EnemyBehavior::Chase => {
+                if self.in_aggro_range(target) {
+                    self.push_toward(target);
+                } else if let Some(waypoint) = self.current_waypoint() {
+                    let offset = waypoint - self.body.position;
+                    if Vec2::new(offset.x, offset.z).length() <= WAYPOINT_RADIUS {
+                        self.waypoint = (self.waypoint + 1) % self.patrol.len();
+                    }
+                    if let Some(next) = self.current_waypoint() {
+                        self.push_toward(next);
+                    }
+                }
+                self.body
+                    .apply_force(-self.body.velocity * self.friction * self.body.mass);
+            }
+            EnemyBehavior::Static => {
+                self.body.velocity.x = 0.0;
+                self.body.velocity.z = 0.0;
+            }
+        }
+    }
+
+    /// Push away from the other enemies at `neighbors` while chasing, so a
+    /// group spreads around its target.
+    pub fn separate(&mut self, neighbors: &[Vec3]) {
+        if self.behavior == EnemyBehavior::Chase {
+            let away = separation(self.body.position, neighbors, SEPARATION_RADIUS);
+            self.body.apply_force(away * SEPARATION_FORCE);
+        }
+    }
+
+    /// Horizontal running force toward `point`.
+    fn push_toward(&mut self, point: Vec3) {
+        let dir = Vec3::new(
+            point.x - self.body.position.x,
+            0.0,
+            point.z - self.body.position.z,
+        );
+        if dir.length_squared() > 0.0001 {
+            self.body.apply_force(dir.normalize() * self.movement_force);
         }
     }
 
     pub fn update(&mut self, dt: f32) {
-        // Для тестов враг остаётся на месте, но обновляем таймер выстрела
+        // Движение задаёт steer, здесь только таймеры выстрелов
         self.bullet_timer -= dt;
+        self.burst_timer.tick(dt);
     }
 
-    pub fn append_cubes(&self, cubes: &mut Vec<crate::engine::renderer::CubeInstance>) {
-        let base = self.body.position;
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(0.0, 0.3, 0.0),
-            size: 0.4,
-            color: ENEMY_COLOR,
-        });
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(0.0, 0.65, 0.0),
-            size: 0.22,
-            color: ENEMY_COLOR,
-        });
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(-0.12, 0.08, 0.0),
-            size: 0.16,
-            color: ENEMY_COLOR,
-        });
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(0.12, 0.08, 0.0),
-            size: 0.16,
-            color: ENEMY_COLOR,
-        });
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(-0.23, 0.38, 0.0),
-            size: 0.13,
-            color: ENEMY_COLOR,
-        });
-        cubes.push(crate::engine::renderer::CubeInstance {
-            position: base + Vec3::new(0.23, 0.38, 0.0),
-            size: 0.13,
-            color: ENEMY_COLOR,
-        });
+    /// World position and size of every body part cube.
+    pub fn parts(&self) -> impl Iterator<Item = (Vec3, f32)> + '_ {
+        ENEMY_PARTS
+            .iter()
+            .map(|&(offset, size)| (self.body.position + offset, size))
     }
+
+    pub fn append_cubes(&self, cubes: &mut Vec<CubeInstance>) {
+        for (position, size) in self.parts() {
+            cubes.push(CubeInstance::new(position, size, self.color).with_outline());
+        }
+    }
+}
+
+/// Horizontal steering away from every neighbor within `radius` of
+/// `position`. Each one adds a unit push weighted from 1 when on top of
+/// `position` down to 0 at `radius`. A neighbor exactly at `position`, such
+/// as the body itself, adds nothing.
+pub fn separation(position: Vec3, neighbors: &[Vec3], radius: f32) -> Vec3 {
+    neighbors
+        .iter()
+        .map(|&other| {
+            let offset = Vec3::new(position.x - other.x, 0.0, position.z - other.z);
+            let distance = offset.length();
+            if distance <= f32::EPSILON || distance >= radius {
+                return Vec3::ZERO;
+            }
+            offset / distance * (1.0 - distance / radius)
+        })
+        .sum()
+}
+
+/// Where a shot of `speed` fired from `from` meets a target at `target`
+/// moving in a straight line at `velocity`, if it can catch it at all.
+pub fn intercept(from: Vec3, target: Vec3, velocity: Vec3, speed: f32) -> Option<Vec3> {
+    // |d + v t| = speed t, решаем относительно времени полёта t
+    let d = target - from;
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * d.dot(velocity);
+    let c = d.length_squared();
+    let t = if a.abs() < 1e-6 {
+        // Цель не быстрее пули и уравнение линейное
+        (b < 0.0).then(|| -c / b)?
+    } else {
+        let disc = b * b - 4.0 * a * c;
+        if disc < 0.0 {
+            return None;
+        }
+        let root = disc.sqrt();
+        [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+            .into_iter()
+            .filter(|&t| t > 0.0)
+            .reduce(f32::min)?
+    };
+    Some(target + velocity * t)
 }
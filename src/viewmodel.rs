@@ -0,0 +1,71 @@
+use crate::engine::renderer::CubeInstance;
+use glam::{Quat, Vec2, Vec3};
+
+/// Where the weapon rests in camera space: lower right, in front of the eye.
+const REST: Vec3 = Vec3::new(0.28, -0.25, -0.6);
+const COLOR: [f32; 3] = [0.18, 0.18, 0.22];
+/// Sway per pixel of mouse motion and its limit, in camera units.
+const SWAY_PER_PIXEL: f32 = 0.0015;
+const MAX_SWAY: f32 = 0.05;
+/// How quickly the sway follows the mouse, per second.
+const SWAY_RATE: f32 = 10.0;
+/// Bob height and steps per unit of walking speed.
+const BOB_AMOUNT: f32 = 0.012;
+const BOB_RATE: f32 = 2.0;
+/// How far a shot pushes the weapon back toward the camera, and the share
+/// of the kick left after one second.
+const KICK_BACK: f32 = 0.08;
+const KICK_RECOVERY: f32 = 0.0005;
+
+/// Weapon drawn in the player's hands, in camera space. It lags behind
+/// mouse motion, bobs while walking and jumps back on every shot.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Viewmodel {
+    sway: Vec2,
+    bob_phase: f32,
+    kick: f32,
+}
+
+impl Viewmodel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance sway, bob and recovery. `speed` is the player's horizontal
+    /// speed.
+    pub fn update(&mut self, mouse_delta: (f32, f32), speed: f32, dt: f32) {
+        let target = (Vec2::new(-mouse_delta.0, mouse_delta.1) * SWAY_PER_PIXEL)
+            .clamp(Vec2::splat(-MAX_SWAY), Vec2::splat(MAX_SWAY));
+        self.sway += (target - self.sway) * (SWAY_RATE * dt).min(1.0);
+        self.bob_phase = (self.bob_phase + speed * BOB_RATE * dt) % std::f32::consts::TAU;
+        self.kick *= KICK_RECOVERY.powf(dt);
+    }
+
+    /// Start the fire animation.
+    pub fn fire(&mut self) {
+        self.kick = 1.0;
+    }
+
+    /// Offset from the rest position.
+    pub fn offset(&self) -> Vec3 {
+        Vec3::new(
+            self.sway.x,
+            self.sway.y + self.bob_phase.sin().abs() * BOB_AMOUNT,
+            self.kick * KICK_BACK,
+        )
+    }
+
+    /// Receiver and barrel, tilted up by the kick.
+    pub fn cubes(&self) -> Vec<CubeInstance> {
+        let base = REST + self.offset();
+        let tilt = Quat::from_rotation_x(self.kick * 0.2);
+        vec![
+            CubeInstance::new(base, 0.1, COLOR)
+                .with_size(Vec3::new(0.08, 0.1, 0.3))
+                .with_rotation(tilt),
+            CubeInstance::new(base + tilt * Vec3::new(0.0, 0.03, -0.22), 0.04, COLOR)
+                .with_size(Vec3::new(0.04, 0.04, 0.2))
+                .with_rotation(tilt),
+        ]
+    }
+}
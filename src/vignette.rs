@@ -0,0 +1,64 @@
+use crate::engine::overlay::OverlayQuad;
+use crate::engine::timer::Timer;
+use glam::Vec2;
+
+/// Seconds the flash after a hit takes to fade out.
+pub const FLASH_TIME: f32 = 0.5;
+/// Opacity at the screen edges right after a hit.
+const FLASH_ALPHA: f32 = 0.6;
+/// Below this share of health the edges stay tinted, more the lower it
+/// gets, up to `LOW_HEALTH_ALPHA` at zero.
+const LOW_HEALTH: f32 = 0.3;
+const LOW_HEALTH_ALPHA: f32 = 0.35;
+/// Share of the half-screen around the center left clear.
+const INNER: f32 = 0.4;
+const COLOR: [f32; 3] = [0.8, 0.0, 0.0];
+
+/// Red tint around the screen edges that flashes when the player is hurt
+/// and lingers while health is low.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DamageVignette {
+    fade: Timer,
+}
+
+impl Default for DamageVignette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DamageVignette {
+    /// Vignette with no flash showing.
+    pub fn new() -> Self {
+        Self {
+            fade: Timer::new(0.0),
+        }
+    }
+
+    /// Start the flash over at full strength.
+    pub fn flash(&mut self) {
+        self.fade = Timer::new(FLASH_TIME);
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.fade.tick(dt);
+    }
+
+    /// Opacity at the edges with `health` left out of `max_health`: the
+    /// stronger of the fading flash and the low health tint.
+    pub fn alpha(&self, health: i32, max_health: i32) -> f32 {
+        let flash = (1.0 - self.fade.progress()) * FLASH_ALPHA;
+        let share = (health as f32 / max_health as f32).clamp(0.0, 1.0);
+        let low = (1.0 - share / LOW_HEALTH).max(0.0) * LOW_HEALTH_ALPHA;
+        flash.max(low)
+    }
+
+    /// Quad over the whole of a `screen` sized in pixels, or `None` while
+    /// there's nothing to show.
+    pub fn quad(&self, screen: Vec2, health: i32, max_health: i32) -> Option<OverlayQuad> {
+        let alpha = self.alpha(health, max_health);
+        let [r, g, b] = COLOR;
+        (alpha > 0.0)
+            .then(|| OverlayQuad::new(screen * 0.5, screen, [r, g, b, alpha]).with_radial(INNER))
+    }
+}
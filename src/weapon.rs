@@ -0,0 +1,450 @@
+use crate::engine::input::InputState;
+use crate::engine::physics::{raycast, Aabb, Material};
+use crate::engine::renderer::CubeInstance;
+use crate::engine::timer::Timer;
+use crate::world::Penetration;
+use glam::{Quat, Vec3};
+use winit::event::VirtualKeyCode;
+
+/// How long a tracer stays on screen, in seconds.
+const TRACER_LIFETIME: f32 = 0.08;
+const TRACER_WIDTH: f32 = 0.02;
+/// Seconds after switching weapons before the new one can fire.
+const SWITCH_DELAY: f32 = 0.3;
+/// Number keys that select inventory slots, in slot order.
+const SLOT_KEYS: [VirtualKeyCode; 9] = [
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+];
+
+/// Instant-hit weapon: the shot is a ray instead of a physical bullet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hitscan {
+    pub damage: i32,
+    pub range: f32,
+    /// Impulse along the ray given to the body that was hit.
+    pub knockback: f32,
+}
+
+impl Default for Hitscan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where a hitscan shot went.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shot {
+    pub from: Vec3,
+    /// Hit point, or the end of the range on a miss.
+    pub to: Vec3,
+    /// Index into `targets` of the body hit, unless an obstacle was closer.
+    pub target: Option<usize>,
+    /// Material of the surface hit, `None` on a miss.
+    pub material: Option<Material>,
+}
+
+impl Hitscan {
+    pub fn new() -> Self {
+        Self {
+            damage: 25,
+            range: 60.0,
+            knockback: 40.0,
+        }
+    }
+
+    /// Trace a shot from `origin`. Obstacles stop the ray without taking
+    /// damage; `targets` are the bodies that can be hit.
+    pub fn fire(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        obstacles: &[Aabb],
+        targets: &[Aabb],
+    ) -> Shot {
+        let boxes: Vec<Aabb> = obstacles.iter().chain(targets).copied().collect();
+        match raycast(origin, direction, self.range, &boxes) {
+            Some(hit) => Shot {
+                from: origin,
+                to: hit.point,
+                target: hit.index.checked_sub(obstacles.len()),
+                material: Some(hit.material),
+            },
+            None => Shot {
+                from: origin,
+                to: origin + direction.normalize_or_zero() * self.range,
+                target: None,
+                material: None,
+            },
+        }
+    }
+}
+
+/// Rounds in the magazine and in reserve. Reloading takes `reload_time`
+/// seconds, and the weapon can't fire meanwhile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ammo {
+    pub magazine_size: u32,
+    pub loaded: u32,
+    pub reserve: u32,
+    pub reload_time: f32,
+    reload: Option<Timer>,
+}
+
+impl Ammo {
+    /// Full magazine plus `reserve` spare rounds.
+    pub fn new(magazine_size: u32, reserve: u32) -> Self {
+        Self {
+            magazine_size,
+            loaded: magazine_size,
+            reserve,
+            reload_time: 1.5,
+            reload: None,
+        }
+    }
+
+    pub fn with_reload_time(mut self, seconds: f32) -> Self {
+        self.reload_time = seconds;
+        self
+    }
+
+    /// Spend a round. Returns `false`, and fires nothing, when the magazine
+    /// is empty or a reload is under way.
+    pub fn try_fire(&mut self) -> bool {
+        if self.reload.is_some() || self.loaded == 0 {
+            return false;
+        }
+        self.loaded -= 1;
+        true
+    }
+
+    /// Start reloading unless the magazine is full, the reserve is empty or
+    /// a reload is already running.
+    pub fn reload(&mut self) -> bool {
+        if self.reload.is_some() || self.loaded == self.magazine_size || self.reserve == 0 {
+            return false;
+        }
+        self.reload = Some(Timer::new(self.reload_time));
+        true
+    }
+
+    pub fn reloading(&self) -> bool {
+        self.reload.is_some()
+    }
+
+    /// Advance a running reload; the magazine fills when it finishes.
+    pub fn tick(&mut self, dt: f32) {
+        let Some(timer) = &mut self.reload else {
+            return;
+        };
+        timer.tick(dt);
+        if timer.finished() {
+            let moved = (self.magazine_size - self.loaded).min(self.reserve);
+            self.loaded += moved;
+            self.reserve -= moved;
+            self.reload = None;
+        }
+    }
+}
+
+/// What a weapon does when fired.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeaponKind {
+    /// Physical bullet fired along the view at `speed`, passing through
+    /// bodies if it has `penetration`.
+    Pistol {
+        speed: f32,
+        penetration: Option<Penetration>,
+    },
+    Rifle(Hitscan),
+    /// Heavier projectile thrown forward at `speed` and upward at `lift`,
+    /// so it flies in an arc.
+    Grenade {
+        speed: f32,
+        lift: f32,
+    },
+}
+
+/// View kick per shot, in radians. The yaw part alternates left and
+/// right from shot to shot.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RecoilPattern {
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+impl RecoilPattern {
+    pub fn new(pitch: f32, yaw: f32) -> Self {
+        Self { pitch, yaw }
+    }
+
+    /// Kick for the `shot`-th shot, counting from zero.
+    pub fn kick(&self, shot: u32) -> (f32, f32) {
+        let side = if shot.is_multiple_of(2) { 1.0 } else { -1.0 };
+        (self.pitch, self.yaw * side)
+    }
+}
+
+/// Half-angle of the cone shots stray within, in radians. Each shot keeps
+/// the spread growing at `growth` per second for `sustain` seconds;
+/// otherwise it shrinks back to `base` at `recovery` per second. Both rates
+/// are integrated over the time passed to `tick`, so the spread after a
+/// burst doesn't depend on the frame rate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Spread {
+    pub base: f32,
+    pub max: f32,
+    pub growth: f32,
+    pub recovery: f32,
+    pub sustain: f32,
+    current: f32,
+    /// Seconds of growth left from the last shot.
+    heating: f32,
+}
+
+impl Spread {
+    pub fn new(base: f32, max: f32, growth: f32, recovery: f32) -> Self {
+        Self {
+            base,
+            max,
+            growth,
+            recovery,
+            sustain: 0.2,
+            current: base,
+            heating: 0.0,
+        }
+    }
+
+    pub fn with_sustain(mut self, seconds: f32) -> Self {
+        self.sustain = seconds;
+        self
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    /// A shot was fired: keep growing for another `sustain` seconds.
+    pub fn fire(&mut self) {
+        self.heating = self.sustain;
+    }
+
+    /// Grow for the part of `dt` still covered by the last shot and
+    /// recover for the rest.
+    pub fn tick(&mut self, dt: f32) {
+        let hot = self.heating.min(dt);
+        self.heating -= hot;
+        self.current = (self.current + self.growth * hot).min(self.max);
+        self.current = (self.current - self.recovery * (dt - hot)).max(self.base);
+    }
+
+    /// Offset (pitch, yaw) of the `shot`-th shot within the current cone.
+    /// Successive shots walk a golden-angle spiral, so they cover the cone
+    /// evenly without a random source.
+    pub fn offset(&self, shot: u32) -> (f32, f32) {
+        const GOLDEN_ANGLE: f32 = 2.399_963;
+        let angle = shot as f32 * GOLDEN_ANGLE;
+        let radius = self.current * ((shot % 8) as f32 / 7.0).sqrt();
+        (radius * angle.sin(), radius * angle.cos())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub ammo: Ammo,
+    pub recoil: RecoilPattern,
+    pub spread: Spread,
+    shots: u32,
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponKind, ammo: Ammo) -> Self {
+        Self {
+            kind,
+            ammo,
+            recoil: RecoilPattern::default(),
+            spread: Spread::default(),
+            shots: 0,
+        }
+    }
+
+    pub fn with_recoil(mut self, recoil: RecoilPattern) -> Self {
+        self.recoil = recoil;
+        self
+    }
+
+    pub fn with_spread(mut self, spread: Spread) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Direction offset (pitch, yaw) of the shot about to be fired, within
+    /// the current spread. The shot then feeds the spread; call before
+    /// `next_kick`.
+    pub fn stray(&mut self) -> (f32, f32) {
+        let offset = self.spread.offset(self.shots);
+        self.spread.fire();
+        offset
+    }
+
+    /// Recoil for the next shot; advances the pattern.
+    pub fn next_kick(&mut self) -> (f32, f32) {
+        let kick = self.recoil.kick(self.shots);
+        self.shots = self.shots.wrapping_add(1);
+        kick
+    }
+
+    pub fn pistol() -> Self {
+        Self::new(
+            WeaponKind::Pistol {
+                speed: 20.0,
+                penetration: None,
+            },
+            Ammo::new(12, 48),
+        )
+        .with_recoil(RecoilPattern::new(0.02, 0.005))
+        .with_spread(Spread::new(0.0, 0.04, 0.1, 0.08))
+    }
+
+    pub fn rifle() -> Self {
+        Self::new(WeaponKind::Rifle(Hitscan::new()), Ammo::new(8, 32))
+            .with_recoil(RecoilPattern::new(0.05, 0.01))
+            .with_spread(Spread::new(0.0, 0.05, 0.15, 0.1))
+    }
+
+    pub fn grenade() -> Self {
+        Self::new(
+            WeaponKind::Grenade {
+                speed: 10.0,
+                lift: 4.0,
+            },
+            Ammo::new(1, 3).with_reload_time(0.8),
+        )
+        .with_recoil(RecoilPattern::new(0.01, 0.0))
+    }
+}
+
+/// Weapons the player carries and which one is in hand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inventory {
+    pub weapons: Vec<Weapon>,
+    active: usize,
+    switching: Timer,
+}
+
+impl Default for Inventory {
+    /// Rifle, pistol and grenades on keys 1-3.
+    fn default() -> Self {
+        Self::new(vec![Weapon::rifle(), Weapon::pistol(), Weapon::grenade()])
+    }
+}
+
+impl Inventory {
+    /// Inventory holding `weapons`, with the first one ready in hand.
+    pub fn new(weapons: Vec<Weapon>) -> Self {
+        Self {
+            weapons,
+            active: 0,
+            switching: Timer::new(0.0),
+        }
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> Option<&Weapon> {
+        self.weapons.get(self.active)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut Weapon> {
+        self.weapons.get_mut(self.active)
+    }
+
+    /// Take out the weapon in `slot`. Returns `false` if the slot is empty
+    /// or already in hand.
+    pub fn select(&mut self, slot: usize) -> bool {
+        if slot >= self.weapons.len() || slot == self.active {
+            return false;
+        }
+        self.active = slot;
+        self.switching = Timer::new(SWITCH_DELAY);
+        true
+    }
+
+    /// Move `step` slots along, wrapping around.
+    pub fn cycle(&mut self, step: i32) -> bool {
+        if self.weapons.is_empty() {
+            return false;
+        }
+        let slot = (self.active as i32 + step).rem_euclid(self.weapons.len() as i32);
+        self.select(slot as usize)
+    }
+
+    /// Whether the weapon in hand has been drawn and can fire.
+    pub fn ready(&self) -> bool {
+        self.switching.finished()
+    }
+
+    /// Number keys pick a slot; scrolling up goes to the previous slot and
+    /// down to the next.
+    pub fn handle_input(&mut self, input: &InputState) {
+        if let Some(slot) = SLOT_KEYS.iter().position(|&k| input.just_pressed(k)) {
+            self.select(slot);
+        } else if input.scroll_delta > 0.0 {
+            self.cycle(-1);
+        } else if input.scroll_delta < 0.0 {
+            self.cycle(1);
+        }
+    }
+
+    /// Advance the switch delay, every running reload and every weapon's
+    /// spread.
+    pub fn tick(&mut self, dt: f32) {
+        self.switching.tick(dt);
+        for weapon in &mut self.weapons {
+            weapon.ammo.tick(dt);
+            weapon.spread.tick(dt);
+        }
+    }
+}
+
+/// Thin streak drawn along a hitscan shot for a moment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tracer {
+    pub from: Vec3,
+    pub to: Vec3,
+    life: Timer,
+}
+
+impl Tracer {
+    pub fn new(from: Vec3, to: Vec3) -> Self {
+        Self {
+            from,
+            to,
+            life: Timer::new(TRACER_LIFETIME),
+        }
+    }
+
+    /// Advance the tracer. Returns `false` once it has faded.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.life.tick(dt);
+        !self.life.finished()
+    }
+
+    pub fn cube(&self, color: [f32; 3]) -> CubeInstance {
+        let span = self.to - self.from;
+        let rotation = Quat::from_rotation_arc(Vec3::Z, span.try_normalize().unwrap_or(Vec3::Z));
+        CubeInstance::new((self.from + self.to) * 0.5, TRACER_WIDTH, color)
+            .with_size(Vec3::new(TRACER_WIDTH, TRACER_WIDTH, span.length()))
+            .with_rotation(rotation)
+    }
+}
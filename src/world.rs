@@ -0,0 +1,584 @@
+use crate::engine::ecs::{EntityId, Registry};
+use crate::engine::physics::{layers, sweep, Aabb, Collider, Explosion, PhysicsObject, RigidBody};
+use crate::engine::renderer::CubeInstance;
+use crate::engine::timer::Timer;
+use glam::{Quat, Vec3};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    Player,
+    Enemy,
+    Bullet,
+    /// Short-lived piece of something that was destroyed.
+    Debris,
+    /// Prop that explodes when shot to pieces.
+    Barrel,
+    /// Practice range target that breaks when shot to pieces.
+    Target,
+}
+
+/// Single cube drawn at the entity's body position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Renderable {
+    pub size: f32,
+    pub color: [f32; 3],
+    /// Extra length along the velocity per unit of speed, for tracers.
+    /// `0.0` keeps the cube uniform.
+    pub tracer: f32,
+}
+
+impl Renderable {
+    /// Render scale for a body moving at `velocity`, in the cube's own
+    /// axes: `size` across and lengthened along local z in proportion to
+    /// speed. `rotation` lines local z up with the velocity.
+    pub fn scale(&self, velocity: Vec3) -> Vec3 {
+        let length = self.size + velocity.length() * self.tracer;
+        Vec3::new(self.size, self.size, length)
+    }
+
+    /// Orientation that turns local z along `velocity`. Cubes without a
+    /// tracer, and bodies at rest, aren't rotated.
+    pub fn rotation(&self, velocity: Vec3) -> Quat {
+        match velocity.try_normalize() {
+            Some(direction) if self.tracer > 0.0 => Quat::from_rotation_arc(Vec3::Z, direction),
+            _ => Quat::IDENTITY,
+        }
+    }
+}
+
+/// Seconds a projectile lives unless it is given another lifetime.
+const PROJECTILE_LIFETIME: f32 = 5.0;
+
+/// Lets a projectile pass through the bodies it hits instead of stopping
+/// at the first one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Penetration {
+    /// Most bodies one projectile can hit; it stops inside the last.
+    pub targets: u32,
+    /// Total depth of bodies it can pass through before it stops.
+    pub thickness: f32,
+    /// Share of the damage and impulse left after each body passed.
+    pub falloff: f32,
+}
+
+impl Penetration {
+    pub fn new(targets: u32) -> Self {
+        Self {
+            targets,
+            thickness: f32::INFINITY,
+            falloff: 0.7,
+        }
+    }
+
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+}
+
+/// Marks an entity as a projectile. Contacts with `owner` never count as
+/// hits, so a bullet can't damage whoever fired it. Until `arming` runs
+/// out the projectile passes through every other body. It is removed once
+/// it has hit something or `lifetime` runs out, whichever comes first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projectile {
+    pub owner: Option<EntityId>,
+    pub arming: Timer,
+    pub lifetime: Timer,
+    /// Set when the projectile strikes a body or the level.
+    pub hit: bool,
+    /// Penetrating projectiles find their hits by sweeping their path in
+    /// `World::penetrating_hits` and pass through bodies in the solver.
+    pub penetration: Option<Penetration>,
+    /// Bodies passed through so far and their total depth.
+    pierced: u32,
+    pierced_depth: f32,
+    /// Velocity the projectile entered the current step with. Hits are
+    /// read after the solver has already slowed it against the target.
+    incoming: Vec3,
+}
+
+impl Projectile {
+    /// Projectile that is armed from the moment it spawns.
+    pub fn new(owner: Option<EntityId>) -> Self {
+        Self {
+            owner,
+            arming: Timer::new(0.0),
+            lifetime: Timer::new(PROJECTILE_LIFETIME),
+            hit: false,
+            penetration: None,
+            pierced: 0,
+            pierced_depth: 0.0,
+            incoming: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_penetration(mut self, penetration: Penetration) -> Self {
+        self.penetration = Some(penetration);
+        self
+    }
+
+    pub fn with_arming(mut self, seconds: f32) -> Self {
+        self.arming = Timer::new(seconds);
+        self
+    }
+
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Timer::new(seconds);
+        self
+    }
+
+    pub fn armed(&self) -> bool {
+        self.arming.finished()
+    }
+
+    pub fn expired(&self) -> bool {
+        self.lifetime.finished()
+    }
+
+    /// Whether the projectile is done and should be removed.
+    pub fn spent(&self) -> bool {
+        self.hit || self.expired()
+    }
+
+    /// Collider for a projectile of `size` on `layer`. It passes through
+    /// bodies on the `shooter` layer and through other projectiles, so
+    /// bullets crossing in mid-air don't stop each other.
+    pub fn collider(size: f32, layer: u32, shooter: u32) -> Collider {
+        Collider::new(Vec3::splat(size))
+            .with_layer(layer, layers::ALL & !shooter & !layers::BULLETS)
+    }
+}
+
+/// Makes an entity explode once its health runs out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Explosive {
+    pub radius: f32,
+    /// Impulse at the center of the blast.
+    pub impulse: f32,
+    /// Damage at the center of the blast.
+    pub damage: i32,
+}
+
+/// An explosive entity going off, as reported by `World::detonate`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blast {
+    pub explosion: Explosion,
+    pub damage: i32,
+}
+
+impl Blast {
+    /// Damage dealt at `position`, falling off like the impulse.
+    pub fn damage_at(&self, position: Vec3) -> i32 {
+        (self.damage as f32 * self.explosion.falloff(position)).round() as i32
+    }
+}
+
+/// A projectile touching a player or an enemy during a physics step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    pub bullet: EntityId,
+    pub target: EntityId,
+    pub damage: i32,
+    pub impulse: Vec3,
+}
+
+/// Damage dealt per unit of projectile momentum.
+const DAMAGE_PER_MOMENTUM: f32 = 50.0;
+/// How close to an obstacle a projectile has to be to count as touching
+/// it. The resolver leaves bodies exactly flush, which `Aabb::overlaps`
+/// doesn't count.
+const CONTACT_EPSILON: f32 = 1e-3;
+
+/// Bag of optional components. The player and enemies keep their bodies
+/// in their own controllers, so their entities mostly just carry a tag.
+pub struct Entity {
+    pub tag: Tag,
+    pub body: Option<RigidBody>,
+    pub collider: Option<Collider>,
+    pub renderable: Option<Renderable>,
+    pub projectile: Option<Projectile>,
+    /// The entity is despawned when this runs out, fading as it goes.
+    pub lifetime: Option<Timer>,
+    /// Hit points of props that can be destroyed.
+    pub health: Option<i32>,
+    pub explosive: Option<Explosive>,
+}
+
+impl Entity {
+    pub fn new(tag: Tag) -> Self {
+        Self {
+            tag,
+            body: None,
+            collider: None,
+            renderable: None,
+            projectile: None,
+            lifetime: None,
+            health: None,
+            explosive: None,
+        }
+    }
+
+    pub fn with_body(mut self, body: RigidBody) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn with_collider(mut self, collider: Collider) -> Self {
+        self.collider = Some(collider);
+        self
+    }
+
+    pub fn with_renderable(mut self, renderable: Renderable) -> Self {
+        self.renderable = Some(renderable);
+        self
+    }
+
+    pub fn with_projectile(mut self, projectile: Projectile) -> Self {
+        self.projectile = Some(projectile);
+        self
+    }
+
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Some(Timer::new(seconds));
+        self
+    }
+
+    pub fn with_health(mut self, health: i32) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    pub fn with_explosive(mut self, explosive: Explosive) -> Self {
+        self.explosive = Some(explosive);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct World {
+    pub entities: Registry<Entity>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, entity: Entity) -> EntityId {
+        self.entities.insert(entity)
+    }
+
+    pub fn despawn(&mut self, id: EntityId) -> Option<Entity> {
+        self.entities.remove(id)
+    }
+
+    pub fn get(&self, id: EntityId) -> Option<&Entity> {
+        self.entities.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: EntityId) -> Option<&mut Entity> {
+        self.entities.get_mut(id)
+    }
+
+    pub fn tag(&self, id: EntityId) -> Option<Tag> {
+        self.get(id).map(|e| e.tag)
+    }
+
+    pub fn with_tag(&self, tag: Tag) -> impl Iterator<Item = (EntityId, &Entity)> {
+        self.entities.iter().filter(move |(_, e)| e.tag == tag)
+    }
+
+    /// Entities that have both a body and a collider.
+    pub fn with_physics(&self) -> impl Iterator<Item = (EntityId, &Entity)> {
+        self.entities
+            .iter()
+            .filter(|(_, e)| e.body.is_some() && e.collider.is_some())
+    }
+
+    /// Advance the arming and lifetime timers of all projectiles.
+    pub fn tick_projectiles(&mut self, dt: f32) {
+        for (_, e) in self.entities.iter_mut() {
+            if let Some(p) = &mut e.projectile {
+                p.arming.tick(dt);
+                p.lifetime.tick(dt);
+            }
+        }
+    }
+
+    /// Advance entity lifetimes and despawn the entities whose lifetime
+    /// ran out.
+    pub fn tick_lifetimes(&mut self, dt: f32) {
+        self.entities.retain(|_, e| match &mut e.lifetime {
+            Some(timer) => {
+                timer.tick(dt);
+                !timer.finished()
+            }
+            None => true,
+        });
+    }
+
+    /// Take `amount` off the health of `id`, if it has any.
+    pub fn damage(&mut self, id: EntityId, amount: i32) {
+        if let Some(health) = self.get_mut(id).and_then(|e| e.health.as_mut()) {
+            *health -= amount;
+        }
+    }
+
+    /// Set off every explosive entity whose health has run out. Each blast
+    /// despawns its entity, pushes nearby bodies and damages nearby
+    /// entities, which can set off more explosives in turn. Returns the
+    /// blasts in the order they went off, so the caller can apply them to
+    /// bodies kept outside the world.
+    pub fn detonate(&mut self) -> Vec<Blast> {
+        let mut blasts = Vec::new();
+        loop {
+            let ready: Vec<EntityId> = self
+                .entities
+                .iter()
+                .filter(|(_, e)| e.explosive.is_some() && e.health.is_some_and(|h| h <= 0))
+                .map(|(id, _)| id)
+                .collect();
+            if ready.is_empty() {
+                return blasts;
+            }
+            for id in ready {
+                let Some(e) = self.despawn(id) else {
+                    continue;
+                };
+                let (Some(explosive), Some(body)) = (e.explosive, e.body) else {
+                    continue;
+                };
+                let blast = Blast {
+                    explosion: Explosion::new(body.position, explosive.radius, explosive.impulse),
+                    damage: explosive.damage,
+                };
+                for (_, other) in self.entities.iter_mut() {
+                    let Some(body) = &mut other.body else {
+                        continue;
+                    };
+                    if let Some(health) = &mut other.health {
+                        *health -= blast.damage_at(body.position);
+                    }
+                    blast.explosion.push(body);
+                }
+                blasts.push(blast);
+            }
+        }
+    }
+
+    /// Flag the projectile `id` as having hit something.
+    pub fn mark_hit(&mut self, id: EntityId) {
+        if let Some(p) = self.get_mut(id).and_then(|e| e.projectile.as_mut()) {
+            p.hit = true;
+        }
+    }
+
+    /// Flag armed projectiles touching any of `obstacles`.
+    pub fn mark_static_hits(&mut self, obstacles: &[Aabb]) {
+        for (_, e) in self.entities.iter_mut() {
+            let (Some(body), Some(collider), Some(p)) = (&e.body, &e.collider, &mut e.projectile)
+            else {
+                continue;
+            };
+            if !p.armed() {
+                continue;
+            }
+            let mut bounds = Aabb::around(body.position, collider);
+            bounds.half_extents += Vec3::splat(CONTACT_EPSILON);
+            if obstacles.iter().any(|obs| obs.overlaps(&bounds)) {
+                p.hit = true;
+            }
+        }
+    }
+
+    /// Despawn the entities tagged `tag` whose health has run out. Returns
+    /// how many were removed.
+    pub fn remove_destroyed(&mut self, tag: Tag) -> usize {
+        let before = self.entities.len();
+        self.entities
+            .retain(|_, e| e.tag != tag || e.health.is_none_or(|h| h > 0));
+        before - self.entities.len()
+    }
+
+    /// Despawn every entity whose body has fallen below height `y`.
+    /// Returns how many were removed.
+    pub fn remove_below(&mut self, y: f32) -> usize {
+        let before = self.entities.len();
+        self.entities
+            .retain(|_, e| e.body.as_ref().is_none_or(|b| b.position.y >= y));
+        before - self.entities.len()
+    }
+
+    /// Despawn projectiles that have hit something or outlived their
+    /// lifetime.
+    pub fn remove_spent_projectiles(&mut self) {
+        self.entities
+            .retain(|_, e| !e.projectile.is_some_and(|p| p.spent()));
+    }
+
+    /// Append a `PhysicsObject` for every entity with a body and a collider,
+    /// recording its id in the parallel `ids` list so contact pairs returned
+    /// by `physics::step` can be mapped back to entities. Projectiles
+    /// remember the velocity they go into the step with, for `bullet_hits`.
+    pub fn physics_objects<'a>(
+        &'a mut self,
+        ids: &mut Vec<EntityId>,
+        objs: &mut Vec<PhysicsObject<'a>>,
+    ) {
+        for (id, e) in self.entities.iter_mut() {
+            if let (Some(body), Some(mut collider)) = (e.body.as_mut(), e.collider) {
+                if let Some(p) = &mut e.projectile {
+                    p.incoming = body.velocity;
+                }
+                // Невзведённый снаряд ни с кем не сталкивается
+                if e.projectile.is_some_and(|p| !p.armed()) {
+                    collider.mask = 0;
+                }
+                // Пробивающий снаряд пролетает сквозь тела, попадания ищет
+                // `penetrating_hits`
+                if e.projectile.is_some_and(|p| p.penetration.is_some()) {
+                    collider.mask &= !(layers::DEFAULT | layers::PLAYER | layers::ENEMY);
+                }
+                ids.push(id);
+                objs.push(PhysicsObject { body, collider });
+            }
+        }
+    }
+
+    /// Turn contact pairs from `physics::step` into projectile hits.
+    ///
+    /// `ids` is the list filled alongside the physics objects. Only contacts
+    /// between a projectile and a player, an enemy, a barrel or a practice
+    /// target count, and contacts with the projectile's owner or by a
+    /// projectile that isn't armed yet are skipped.
+    pub fn bullet_hits(&self, ids: &[EntityId], pairs: &[(usize, usize)]) -> Vec<Hit> {
+        let mut hits = Vec::new();
+        for &(a, b) in pairs {
+            let (a, b) = (ids[a], ids[b]);
+            let (bullet, target) = if self.is_projectile(a) {
+                (a, b)
+            } else if self.is_projectile(b) {
+                (b, a)
+            } else {
+                continue;
+            };
+            if !matches!(
+                self.tag(target),
+                Some(Tag::Player | Tag::Enemy | Tag::Barrel | Tag::Target)
+            ) {
+                continue;
+            }
+            let Some(e) = self.get(bullet) else {
+                continue;
+            };
+            let (Some(body), Some(projectile)) = (e.body, e.projectile) else {
+                continue;
+            };
+            if !projectile.armed() || projectile.owner == Some(target) {
+                continue;
+            }
+            let momentum = projectile.incoming.length() * body.mass;
+            hits.push(Hit {
+                bullet,
+                target,
+                damage: (momentum * DAMAGE_PER_MOMENTUM) as i32,
+                impulse: projectile.incoming * body.mass,
+            });
+        }
+        hits
+    }
+
+    /// Boxes of the world entities projectiles can hit, for
+    /// `penetrating_hits`.
+    pub fn target_boxes(&self) -> Vec<(EntityId, Aabb)> {
+        self.with_physics()
+            .filter(|(_, e)| matches!(e.tag, Tag::Player | Tag::Enemy | Tag::Barrel | Tag::Target))
+            .map(|(id, e)| {
+                (
+                    id,
+                    Aabb::around(e.body.unwrap().position, &e.collider.unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    /// Hits of penetrating projectiles over the last `dt`, in the order
+    /// each projectile met its targets. Each one sweeps the path it moved
+    /// along during the step against `targets` and passes through bodies
+    /// until it has hit `Penetration::targets` of them or gone through
+    /// `thickness` of them, losing `falloff` of its damage and impulse
+    /// with every body. The projectile is marked as hit once it stops.
+    pub fn penetrating_hits(&mut self, targets: &[(EntityId, Aabb)], dt: f32) -> Vec<Hit> {
+        let boxes: Vec<Aabb> = targets.iter().map(|(_, b)| *b).collect();
+        let mut hits = Vec::new();
+        for (bullet, e) in self.entities.iter_mut() {
+            let (Some(body), Some(p)) = (&e.body, &mut e.projectile) else {
+                continue;
+            };
+            let Some(penetration) = p.penetration else {
+                continue;
+            };
+            if !p.armed() || p.hit {
+                continue;
+            }
+            let direction = body.velocity.normalize_or_zero();
+            let start = body.position - body.velocity * dt;
+            let path = sweep(start, direction, body.velocity.length() * dt, &boxes);
+            for ray_hit in path {
+                let (target, aabb) = targets[ray_hit.index];
+                if p.owner == Some(target) {
+                    continue;
+                }
+                let share = penetration.falloff.powi(p.pierced as i32);
+                let momentum = body.velocity.length() * body.mass;
+                hits.push(Hit {
+                    bullet,
+                    target,
+                    damage: (momentum * DAMAGE_PER_MOMENTUM * share) as i32,
+                    impulse: body.velocity * body.mass * share,
+                });
+                let (near, far) = aabb.ray_span(start, direction).unwrap_or_default();
+                p.pierced += 1;
+                p.pierced_depth += far - near;
+                if p.pierced >= penetration.targets || p.pierced_depth >= penetration.thickness {
+                    p.hit = true;
+                    break;
+                }
+            }
+        }
+        hits
+    }
+
+    fn is_projectile(&self, id: EntityId) -> bool {
+        self.get(id).is_some_and(|e| e.projectile.is_some())
+    }
+
+    pub fn append_cubes(&self, cubes: &mut Vec<CubeInstance>) {
+        self.append_cubes_highlighting(cubes, None);
+    }
+
+    /// Like `append_cubes`, with the cube of `target` highlighted.
+    pub fn append_cubes_highlighting(
+        &self,
+        cubes: &mut Vec<CubeInstance>,
+        target: Option<EntityId>,
+    ) {
+        for (id, e) in self.entities.iter() {
+            if let (Some(body), Some(r)) = (&e.body, e.renderable) {
+                // Сущности с конечным сроком жизни гаснут к его концу
+                let fade = e.lifetime.map_or(1.0, |t| 1.0 - t.progress());
+                let mut cube = CubeInstance::new(body.position, r.size, r.color.map(|c| c * fade))
+                    .with_size(r.scale(body.velocity))
+                    .with_rotation(r.rotation(body.velocity));
+                cube.highlight = target == Some(id);
+                cubes.push(cube);
+            }
+        }
+    }
+}
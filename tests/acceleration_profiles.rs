@@ -0,0 +1,60 @@
+// Автотест: при плавном разгоне сила движения в начале меньше, чем при
+// линейном, а через время разгона обе доходят до полной
+// Запуск: cargo test --test acceleration_profiles
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::player::{AccelerationProfile, Player};
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+const DT: f32 = 1.0 / 60.0;
+const RAMP: f32 = 0.5;
+
+/// Движущие силы за `frames` кадров с зажатой W.
+fn forces(profile: AccelerationProfile, frames: usize) -> Vec<f32> {
+    let mut player = Player::new();
+    player.acceleration = profile;
+    // Трение считается от скорости, её обнуляем, чтобы мерить только разгон
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::W]));
+    (0..frames)
+        .map(|_| {
+            player.body.force = Vec3::ZERO;
+            player.body.velocity = Vec3::ZERO;
+            player.update(&input, DT);
+            player.body.force.length()
+        })
+        .collect()
+}
+
+#[test]
+fn ease_in_starts_slower_than_linear() {
+    let linear = forces(AccelerationProfile::Linear { ramp: RAMP }, 60);
+    let ease_in = forces(AccelerationProfile::EaseIn { ramp: RAMP }, 60);
+    let instant = forces(AccelerationProfile::Instant, 1);
+    let full = Player::new().movement_force;
+
+    assert!((instant[0] - full).abs() < 1e-3);
+    assert!(linear[0] > 0.0 && linear[0] < full);
+    assert!(ease_in[0] < linear[0], "{} vs {}", ease_in[0], linear[0]);
+    // Первые кадры плавного разгона слабее линейного весь путь до полной силы
+    assert!(ease_in[10] < linear[10]);
+    assert!((linear[59] - full).abs() < 1e-3);
+    assert!((ease_in[59] - full).abs() < 1e-3);
+}
+
+#[test]
+fn releasing_the_keys_restarts_the_ramp() {
+    let mut player = Player::new();
+    player.acceleration = AccelerationProfile::Linear { ramp: RAMP };
+    let mut walk = InputState::default();
+    walk.apply_frame(&InputFrame::holding(&[VirtualKeyCode::W]));
+    for _ in 0..60 {
+        player.update(&walk, DT);
+    }
+    player.update(&InputState::default(), DT);
+    player.body.force = Vec3::ZERO;
+    player.body.velocity = Vec3::ZERO;
+    player.update(&walk, DT);
+    assert!(player.body.force.length() < player.movement_force * 0.1);
+}
@@ -0,0 +1,76 @@
+// Автотест: игрока и врага можно собрать с нужными характеристиками через
+// with_*, а Default совпадает с new
+// Запуск: cargo test --test actor_builders
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::engine::physics::{layers, ColliderShape};
+use astroforge::player::{Enemy, Player};
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn player_builder_sets_the_stats() {
+    let start = Vec3::new(3.0, 2.0, -4.0);
+    let player = Player::new()
+        .with_mass(60.0)
+        .with_jump_impulse(900.0)
+        .with_movement_force(450.0)
+        .with_position(start)
+        .with_capsule(0.3, 1.2);
+    assert_eq!(player.body.mass, 60.0);
+    assert_eq!(player.jump_impulse, 900.0);
+    assert_eq!(player.movement_force, 450.0);
+    assert_eq!(player.position, start);
+    assert_eq!(player.body.position, start);
+    assert_eq!(player.collider.half_extents, Vec3::new(0.3, 0.6, 0.3));
+    assert!(matches!(
+        player.collider.shape,
+        ColliderShape::Capsule { radius, .. } if radius == 0.3
+    ));
+    // Слои столкновений остаются игроковыми
+    assert_eq!(player.collider.layer, layers::PLAYER);
+    assert_eq!(player.collider.mask, Player::new().collider.mask);
+}
+
+#[test]
+fn a_lighter_player_jumps_faster() {
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::Space]));
+    let jump = |mut player: Player| {
+        player.body.on_ground = true;
+        player.update(&input, 1.0 / 60.0);
+        player.body.velocity.y
+    };
+    let normal = jump(Player::new());
+    let light = jump(Player::new().with_mass(40.0));
+    assert!((light - 2.0 * normal).abs() < 1e-3, "{light} vs {normal}");
+}
+
+#[test]
+fn enemy_builder_sets_the_stats() {
+    let start = Vec3::new(-5.0, 0.75, 5.0);
+    let enemy = Enemy::new()
+        .with_mass(120.0)
+        .with_health(250)
+        .with_movement_force(350.0)
+        .with_position(start)
+        .with_collider_size(Vec3::new(0.7, 1.0, 0.7));
+    assert_eq!(enemy.body.mass, 120.0);
+    assert_eq!(enemy.health, 250);
+    assert_eq!(enemy.movement_force, 350.0);
+    assert_eq!(enemy.body.position, start);
+    assert_eq!(enemy.collider.half_extents, Vec3::new(0.7, 1.0, 0.7));
+    assert_eq!(enemy.collider.layer, layers::ENEMY);
+}
+
+#[test]
+fn defaults_match_new() {
+    let (a, b) = (Player::default(), Player::new());
+    assert_eq!(a.body.mass, b.body.mass);
+    assert_eq!(a.jump_impulse, b.jump_impulse);
+    assert_eq!(a.position, b.position);
+    let (a, b) = (Enemy::default(), Enemy::new());
+    assert_eq!(a.body.mass, b.body.mass);
+    assert_eq!(a.movement_force, b.movement_force);
+    assert_eq!(a.health, b.health);
+}
@@ -0,0 +1,49 @@
+// Автотест: с помощью прицеливания обзор у врага под прицелом медленнее,
+// чем на пустом месте, и слегка подтягивается к врагу
+// Запуск: cargo test --test aim_assist
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::player::{AimAssist, Player};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn enemy_near_the_reticle_slows_the_look() {
+    let mut player = Player::new();
+    let free = player.look_sensitivity();
+    // Игрок смотрит вдоль -Z
+    let ahead = player.position + Vec3::new(0.0, 0.0, -8.0);
+    player.aim_target = Some(ahead);
+    assert_eq!(player.look_sensitivity(), free, "assist is off by default");
+
+    player.aim_assist = Some(AimAssist::default());
+    let near = player.look_sensitivity();
+    assert!(near < free, "{near} vs {free}");
+
+    player.aim_target = Some(player.position + Vec3::new(8.0, 0.0, 0.0));
+    assert_eq!(player.look_sensitivity(), free);
+    player.aim_target = None;
+    assert_eq!(player.look_sensitivity(), free);
+}
+
+#[test]
+fn looking_near_an_enemy_pulls_toward_it() {
+    let mut player = Player::new();
+    player.aim_assist = Some(AimAssist::default());
+    // Враг чуть правее прицела
+    player.aim_target = Some(player.position + Vec3::new(0.4, 0.0, -8.0));
+    // Мышь едва двигается вверх, по горизонтали подтягивает только помощь
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame {
+        mouse_delta: (0.0, -0.01),
+        ..InputFrame::default()
+    });
+    let to_target =
+        |p: &Player| (p.rotation * Vec3::NEG_Z).angle_between(p.aim_target.unwrap() - p.position);
+    let before = to_target(&player);
+    for _ in 0..10 {
+        player.update(&input, DT);
+    }
+    assert!(to_target(&player) < before * 0.5, "view was not pulled");
+}
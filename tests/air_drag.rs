@@ -0,0 +1,42 @@
+// Автотест: сопротивление воздуха замедляет тело без других сил
+// Запуск: cargo test --test air_drag
+
+use astroforge::engine::physics::{integrate, RigidBody};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn drag_slows_a_moving_body_every_frame() {
+    let mut body = RigidBody::new(1.0, Vec3::ZERO).with_drag(0.5);
+    body.velocity = Vec3::new(20.0, 0.0, -5.0);
+    let start_dir = body.velocity.normalize();
+    let mut speed = body.velocity.length();
+    for _ in 0..120 {
+        integrate(&mut body, DT);
+        let now = body.velocity.length();
+        assert!(now < speed, "speed must drop: {now} >= {speed}");
+        speed = now;
+    }
+    assert!(
+        speed < 10.0,
+        "about 1/e of the start after two seconds: {speed}"
+    );
+    assert!((body.velocity.normalize() - start_dir).length() < 1e-5);
+}
+
+#[test]
+fn huge_drag_never_reverses_velocity() {
+    let mut body = RigidBody::new(1.0, Vec3::ZERO).with_drag(1000.0);
+    body.velocity = Vec3::new(3.0, 0.0, 0.0);
+    integrate(&mut body, DT);
+    assert!(body.velocity.x > 0.0 && body.velocity.x < 3.0);
+}
+
+#[test]
+fn no_drag_by_default() {
+    let mut body = RigidBody::new(1.0, Vec3::ZERO);
+    body.velocity = Vec3::new(4.0, 0.0, 0.0);
+    integrate(&mut body, DT);
+    assert_eq!(body.velocity, Vec3::new(4.0, 0.0, 0.0));
+}
@@ -0,0 +1,60 @@
+// Автотест: выстрел тратит патрон, при пустом магазине не стреляет, перезарядка наполняет магазин после задержки
+// Запуск: cargo test --test ammo
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::weapon::Ammo;
+use winit::event::MouseButton;
+
+#[test]
+fn firing_spends_a_round() {
+    let mut ammo = Ammo::new(3, 6);
+    assert!(ammo.try_fire());
+    assert_eq!((ammo.loaded, ammo.reserve), (2, 6));
+}
+
+#[test]
+fn firing_on_empty_fails() {
+    let mut ammo = Ammo::new(2, 0);
+    assert!(ammo.try_fire());
+    assert!(ammo.try_fire());
+    assert!(!ammo.try_fire());
+    assert_eq!(ammo.loaded, 0);
+    assert!(!ammo.reload(), "nothing in reserve");
+}
+
+#[test]
+fn reload_refills_after_the_delay() {
+    let mut ammo = Ammo::new(5, 3).with_reload_time(1.0);
+    for _ in 0..4 {
+        ammo.try_fire();
+    }
+    assert!(ammo.reload());
+    assert!(!ammo.try_fire(), "can't fire while reloading");
+    ammo.tick(0.5);
+    assert_eq!(ammo.loaded, 1);
+    ammo.tick(0.5);
+    assert!(!ammo.reloading());
+    // Запас меньше недостающего — переносим всё, что есть
+    assert_eq!((ammo.loaded, ammo.reserve), (4, 0));
+    assert!(!Ammo::new(5, 3).reload(), "full magazine");
+}
+
+#[test]
+fn game_shows_and_spends_rifle_ammo() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(320, 240);
+    let mut game = Game::new(GameOptions::default());
+    let ammo = |game: &Game| game.player.inventory.active().unwrap().ammo;
+    let loaded = ammo(&game).loaded;
+    engine.input.click(MouseButton::Left);
+    game.frame(&mut engine, 1.0 / 60.0);
+    let reserve = ammo(&game).reserve;
+    assert_eq!(ammo(&game).loaded, loaded - 1);
+    assert_eq!(engine.renderer.hud_ammo, Some((loaded - 1, reserve)));
+    let section = engine.renderer.ammo_section(loaded - 1, reserve);
+    assert_eq!(
+        section.text[1].text,
+        format!("{} / {}", loaded - 1, reserve)
+    );
+}
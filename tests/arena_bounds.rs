@@ -0,0 +1,68 @@
+// Автотест: граница арены возвращает вылетевшее тело внутрь и гасит скорость наружу
+// Запуск: cargo test --test arena_bounds
+
+use astroforge::engine::physics::{Arena, Collider, PhysicsObject, PhysicsWorld, RigidBody};
+use glam::{Vec2, Vec3};
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn body_past_the_boundary_is_clamped_back() {
+    let world = PhysicsWorld::new().with_arena(Arena::new(Vec2::splat(-10.0), Vec2::splat(10.0)));
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut body = RigidBody::new(1.0, Vec3::new(9.4, 5.0, -9.4));
+    body.velocity = Vec3::new(30.0, 1.0, -30.0);
+    let mut objs = vec![PhysicsObject {
+        body: &mut body,
+        collider,
+    }];
+    world.step(&mut objs, &[], DT);
+
+    assert_eq!(body.position.x, 9.5, "collider edge rests on the wall");
+    assert_eq!(body.position.z, -9.5);
+    assert_eq!(body.velocity.x, 0.0, "outward velocity removed");
+    assert_eq!(body.velocity.z, 0.0);
+    assert!(body.velocity.y != 0.0, "vertical motion untouched");
+}
+
+#[test]
+fn inward_velocity_is_kept() {
+    let world = PhysicsWorld::new().with_arena(Arena::new(Vec2::splat(-10.0), Vec2::splat(10.0)));
+    let mut body = RigidBody::new(1.0, Vec3::new(-12.0, 5.0, 0.0));
+    body.velocity = Vec3::new(2.0, 0.0, 0.0);
+    let mut objs = vec![PhysicsObject {
+        body: &mut body,
+        collider: Collider::new(Vec3::splat(0.5)),
+    }];
+    world.step(&mut objs, &[], DT);
+
+    assert_eq!(body.position.x, -9.5);
+    assert_eq!(body.velocity.x, 2.0);
+}
+
+#[test]
+fn bodies_inside_are_unaffected() {
+    let arena = PhysicsWorld::new().with_arena(Arena::new(Vec2::splat(-10.0), Vec2::splat(10.0)));
+    let mut a = RigidBody::new(1.0, Vec3::new(1.0, 5.0, 2.0));
+    let mut b = a;
+    a.velocity = Vec3::new(3.0, 0.0, -1.0);
+    b.velocity = a.velocity;
+    arena.step(
+        &mut [PhysicsObject {
+            body: &mut a,
+            collider: Collider::new(Vec3::splat(0.5)),
+        }],
+        &[],
+        DT,
+    );
+    PhysicsWorld::new().step(
+        &mut [PhysicsObject {
+            body: &mut b,
+            collider: Collider::new(Vec3::splat(0.5)),
+        }],
+        &[],
+        DT,
+    );
+    assert_eq!(a.position, b.position);
+    assert_eq!(a.velocity, b.velocity);
+}
@@ -0,0 +1,44 @@
+// Автотест: число блоков кольца артефакта задаётся настройкой, и столько же
+// блоков получают и столкновения, и геометрия рендера
+// Запуск: cargo test --test artifact_blocks
+
+use astroforge::engine::artifact::RING_BLOCKS;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::Player;
+
+/// Индексов на один куб кольца: 6 граней по 2 треугольника.
+const INDICES_PER_BLOCK: u32 = 36;
+
+#[test]
+fn twelve_block_ring_collides_and_renders_twelve_blocks() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(200, 150);
+    assert_eq!(engine.renderer.artifact_blocks(), RING_BLOCKS);
+
+    let mut game = Game::new(GameOptions {
+        artifact_blocks: 12,
+        ..GameOptions::default()
+    });
+    // Кольцо и пол
+    assert_eq!(game.static_obstacles().len(), 12 + 1);
+    game.render(&mut engine);
+    assert_eq!(engine.renderer.artifact_blocks(), 12);
+    assert_eq!(engine.renderer.artifact_indices, 12 * INDICES_PER_BLOCK);
+
+    // Смена числа блоков на лету перестраивает и то и другое
+    game.options.artifact_blocks = 20;
+    game.render(&mut engine);
+    assert_eq!(game.static_obstacles().len(), 20 + 1);
+    assert_eq!(engine.renderer.artifact_indices, 20 * INDICES_PER_BLOCK);
+}
+
+#[test]
+fn ring_blocks_sit_on_the_floor_around_the_origin() {
+    let blocks = Player::artifact_aabbs(12);
+    assert_eq!(blocks.len(), 12);
+    for b in &blocks {
+        assert!((b.center.y - 0.5).abs() < 1e-6);
+        assert!((b.center.x.hypot(b.center.z) - 3.0).abs() < 1e-4);
+    }
+}
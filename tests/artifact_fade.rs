@@ -0,0 +1,30 @@
+// Автотест: яркость артефакта плавно идёт к новой цели, а не прыгает
+// Запуск: cargo test --test artifact_fade
+
+use astroforge::engine::Engine;
+
+#[test]
+fn intensity_approaches_the_target_gradually() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let renderer = &mut engine.renderer;
+    renderer.update_artifact(0.2);
+
+    let mut last = renderer.artifact_intensity();
+    renderer.fade_artifact(1.0, 1.0 / 60.0);
+    let first = renderer.artifact_intensity();
+    assert!(first > last && first < 0.5, "no jump: {first}");
+    for _ in 0..10 {
+        renderer.fade_artifact(1.0, 1.0 / 60.0);
+        let now = renderer.artifact_intensity();
+        assert!(now > last && now < 1.0);
+        last = now;
+    }
+    for _ in 0..120 {
+        renderer.fade_artifact(1.0, 1.0 / 60.0);
+    }
+    assert!((renderer.artifact_intensity() - 1.0).abs() < 1e-3);
+
+    renderer.update_artifact(0.2);
+    assert_eq!(renderer.artifact_intensity(), 0.2, "update_artifact snaps");
+}
@@ -0,0 +1,49 @@
+// Автотест: яркость свечения артефакта не выходит за [min, max] и повторяется с заданной частотой
+// Запуск: cargo test --test artifact_glow
+
+use astroforge::engine::artifact::{ArtifactGlow, Waveform};
+
+#[test]
+fn intensity_stays_in_range_for_every_waveform() {
+    for waveform in [Waveform::Sine, Waveform::Triangle, Waveform::Pulse] {
+        let glow = ArtifactGlow::new()
+            .with_frequency(2.0)
+            .with_range(0.3, 0.9)
+            .with_waveform(waveform);
+        let samples: Vec<f32> = (0..400)
+            .map(|i| glow.intensity_at(i as f32 * 0.01))
+            .collect();
+        for v in &samples {
+            assert!((0.3..=0.9).contains(v), "{waveform:?}: {v}");
+        }
+        let max = samples.iter().cloned().fold(f32::MIN, f32::max);
+        let min = samples.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(max > 0.85 && min < 0.35, "{waveform:?} spans the range");
+    }
+}
+
+#[test]
+fn completes_a_cycle_at_the_configured_frequency() {
+    for waveform in [Waveform::Sine, Waveform::Triangle, Waveform::Pulse] {
+        let glow = ArtifactGlow::new()
+            .with_frequency(4.0)
+            .with_waveform(waveform);
+        let period = 0.25;
+        for i in 0..20 {
+            let t = i as f32 * 0.0113;
+            let a = glow.intensity_at(t);
+            let b = glow.intensity_at(t + period);
+            assert!((a - b).abs() < 1e-3, "{waveform:?} at {t}: {a} vs {b}");
+        }
+        // Полпериода — другая фаза
+        assert!((glow.intensity_at(0.05) - glow.intensity_at(0.05 + period / 2.0)).abs() > 0.1);
+    }
+}
+
+#[test]
+fn tick_advances_the_pulse() {
+    let mut glow = ArtifactGlow::new().with_frequency(1.0);
+    let first = glow.tick(0.25);
+    assert_eq!(first, glow.intensity_at(0.25));
+    assert_eq!(glow.idle(), glow.min);
+}
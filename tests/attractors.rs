@@ -0,0 +1,58 @@
+// Автотест: гравитационный колодец притягивает тела в радиусе и не трогает тела снаружи
+// Запуск: cargo test --test attractors
+
+use astroforge::engine::physics::{
+    Attractor, Collider, PhysicsObject, PhysicsWorld, RigidBody, ATTRACTOR_MIN_DISTANCE,
+};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn step_once(world: &PhysicsWorld, body: &mut RigidBody) {
+    let mut objs = vec![PhysicsObject {
+        body,
+        collider: Collider::new(Vec3::splat(0.5)),
+    }];
+    world.step(&mut objs, &[], DT);
+}
+
+#[test]
+fn body_in_range_accelerates_toward_the_attractor() {
+    let mut world = PhysicsWorld::new();
+    world.add_attractor(Vec3::new(0.0, 5.0, 0.0), 20.0, 10.0);
+    let mut body = RigidBody::new(2.0, Vec3::new(4.0, 5.0, 0.0));
+    body.on_ground = true; // без гравитации
+    step_once(&world, &mut body);
+    assert!(body.velocity.x < 0.0, "pulled toward the center");
+    // a = strength / d², независимо от массы
+    let expected = 20.0 / 16.0 * DT;
+    assert!(
+        (body.velocity.x + expected).abs() < 1e-5,
+        "{}",
+        body.velocity.x
+    );
+    assert_eq!(body.velocity.z, 0.0);
+}
+
+#[test]
+fn body_out_of_range_is_unaffected() {
+    let mut world = PhysicsWorld::new();
+    world.add_attractor(Vec3::new(0.0, 5.0, 0.0), 20.0, 3.0);
+    let mut body = RigidBody::new(2.0, Vec3::new(4.0, 5.0, 0.0));
+    body.on_ground = true;
+    step_once(&world, &mut body);
+    assert_eq!(body.velocity, Vec3::ZERO);
+    assert_eq!(body.position, Vec3::new(4.0, 5.0, 0.0));
+}
+
+#[test]
+fn pull_is_clamped_near_the_center() {
+    let attractor = Attractor::new(Vec3::ZERO, 10.0, 5.0);
+    let near = RigidBody::new(1.0, Vec3::new(0.01, 0.0, 0.0));
+    let edge = RigidBody::new(1.0, Vec3::new(ATTRACTOR_MIN_DISTANCE, 0.0, 0.0));
+    assert_eq!(
+        attractor.force_on(&near).length(),
+        attractor.force_on(&edge).length()
+    );
+    assert!(attractor.force_on(&near).length().is_finite());
+}
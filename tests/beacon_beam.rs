@@ -0,0 +1,32 @@
+// Автотест: активный маяк рисует световой луч, после деактивации луча нет
+// Запуск: cargo test --test beacon_beam
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+
+#[test]
+fn activation_adds_the_beam_and_deactivation_removes_it() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.player.body.position = Vec3::new(8.0, 0.75, 0.0);
+    game.update(&mut engine, 1.0 / 60.0);
+    assert!(game.beacon_beam().is_none());
+    let before = game.cubes().len();
+
+    game.player.body.position = Vec3::new(1.0, 0.75, 0.0);
+    game.player.body.velocity = Vec3::ZERO;
+    game.player.body.on_ground = true;
+    game.update(&mut engine, 1.0 / 60.0);
+    assert!(game.tech_unlocked);
+    let beam = game.beacon_beam().expect("beam while active");
+    assert!(beam.size.y > 10.0 * beam.size.x, "tall and thin");
+    assert_eq!((beam.position.x, beam.position.z), (0.0, 0.0));
+    assert!(game.cubes().contains(&beam));
+    assert_eq!(game.cubes().len(), before + 1);
+
+    game.tech_unlocked = false;
+    assert!(game.beacon_beam().is_none());
+    assert_eq!(game.cubes().len(), before);
+}
@@ -0,0 +1,65 @@
+// Автотест: маяк активируется внутри зоны триггера, гаснет при выходе и не повторяет активацию
+// Запуск: cargo test --test beacon_trigger
+
+use astroforge::engine::physics::Trigger;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+
+fn stand_at(game: &mut Game, engine: &mut Engine, position: Vec3) {
+    game.player.body.position = position;
+    game.player.body.velocity = Vec3::ZERO;
+    game.player.body.on_ground = true;
+    game.update(engine, 1.0 / 60.0);
+}
+
+#[test]
+fn entering_and_leaving_the_radius_toggles_activation() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+
+    stand_at(&mut game, &mut engine, Vec3::new(6.0, 0.75, 0.0));
+    assert!(!game.beacon_active());
+    stand_at(&mut game, &mut engine, Vec3::new(1.0, 0.75, 0.0));
+    assert!(game.beacon_active(), "inside the radius");
+    assert!(game.tech_unlocked);
+    stand_at(&mut game, &mut engine, Vec3::new(6.0, 0.75, 0.0));
+    assert!(!game.beacon_active(), "left the radius");
+}
+
+#[test]
+fn radius_is_configurable() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.beacon_trigger = Trigger::cylinder(Vec3::new(0.0, 1.0, 0.0), 0.5, 2.0);
+
+    stand_at(&mut game, &mut engine, Vec3::new(1.0, 0.75, 0.0));
+    assert!(!game.beacon_active());
+    stand_at(&mut game, &mut engine, Vec3::new(0.2, 0.75, 0.0));
+    assert!(game.beacon_active());
+}
+
+#[test]
+fn box_trigger_contains() {
+    let trigger = Trigger::aabb(Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 2.0));
+    assert!(trigger.contains(Vec3::new(0.9, 0.5, 1.9)));
+    assert!(!trigger.contains(Vec3::new(1.1, 0.5, 0.0)));
+    assert!(!trigger.contains(Vec3::new(0.0, 2.5, 0.0)));
+}
+
+#[test]
+fn reentering_does_not_replay_activation() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+
+    stand_at(&mut game, &mut engine, Vec3::new(1.0, 0.75, 0.0));
+    assert_eq!(game.activation_count(), 1);
+    stand_at(&mut game, &mut engine, Vec3::new(6.0, 0.75, 0.0));
+    assert!(game.tech_unlocked, "unlock is latched");
+    stand_at(&mut game, &mut engine, Vec3::new(1.0, 0.75, 0.0));
+    assert!(game.beacon_active());
+    assert_eq!(game.activation_count(), 1);
+}
@@ -0,0 +1,77 @@
+// Автотест: пулю убирает попадание или конец срока жизни, а не остановка
+// Запуск: cargo test --test bullet_cleanup
+
+use astroforge::engine::ecs::EntityId;
+use astroforge::engine::physics::{layers, step, Aabb, RigidBody};
+use astroforge::world::{Entity, Projectile, Tag, World};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn spawn_bullet(world: &mut World, position: Vec3, velocity: Vec3, lifetime: f32) -> EntityId {
+    let mut body = RigidBody::new(0.05, position);
+    body.velocity = velocity;
+    world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_collider(Projectile::collider(
+                0.1,
+                layers::PLAYER_BULLET,
+                layers::PLAYER,
+            ))
+            .with_projectile(Projectile::new(None).with_lifetime(lifetime)),
+    )
+}
+
+/// Кадр игры для пуль: таймеры, физика, отметки попаданий и уборка.
+fn frame(world: &mut World, obstacles: &[Aabb]) {
+    world.tick_projectiles(DT);
+    let mut ids = Vec::new();
+    let mut objs = Vec::new();
+    world.physics_objects(&mut ids, &mut objs);
+    step(&mut objs, obstacles, DT);
+    world.mark_static_hits(obstacles);
+    world.remove_spent_projectiles();
+}
+
+#[test]
+fn slowed_bullet_survives_and_expired_one_is_removed() {
+    let mut world = World::new();
+    // Пуля в воздухе на миг замерла: скорость нулевая, но она жива
+    let slowed = spawn_bullet(&mut world, Vec3::new(0.0, 5.0, 0.0), Vec3::ZERO, 5.0);
+    let expiring = spawn_bullet(&mut world, Vec3::new(3.0, 5.0, 0.0), Vec3::X, 0.1);
+    frame(&mut world, &[]);
+    assert!(world.get(slowed).is_some());
+    assert!(world.get(expiring).is_some());
+    for _ in 0..6 {
+        frame(&mut world, &[]);
+    }
+    assert!(world.get(slowed).is_some(), "slowed bullet was removed");
+    assert!(
+        world.get(expiring).is_none(),
+        "expired bullet is still alive"
+    );
+}
+
+#[test]
+fn grazing_a_wall_is_a_hit() {
+    let wall = Aabb::new(Vec3::new(2.0, 5.0, 0.0), Vec3::new(0.5, 2.0, 2.0));
+    let mut world = World::new();
+    let bullet = spawn_bullet(&mut world, Vec3::new(0.0, 5.0, 0.0), Vec3::X * 30.0, 5.0);
+    let missed = spawn_bullet(&mut world, Vec3::new(0.0, 5.0, 5.0), Vec3::X * 30.0, 5.0);
+    for _ in 0..10 {
+        frame(&mut world, &[wall]);
+    }
+    assert!(world.get(bullet).is_none(), "bullet stuck at the wall");
+    assert!(world.get(missed).is_some());
+}
+
+#[test]
+fn marked_hit_removes_the_bullet() {
+    let mut world = World::new();
+    let bullet = spawn_bullet(&mut world, Vec3::new(0.0, 5.0, 0.0), Vec3::X, 5.0);
+    world.mark_hit(bullet);
+    assert!(world.get(bullet).unwrap().projectile.unwrap().spent());
+    world.remove_spent_projectiles();
+    assert!(world.get(bullet).is_none());
+}
@@ -0,0 +1,85 @@
+// Автотест: встречные пули пролетают друг сквозь друга
+// Запуск: cargo test --test bullet_crossing
+
+use astroforge::engine::physics::RigidBody;
+use astroforge::engine::physics::{layers, step};
+use astroforge::world::{Entity, Projectile, Tag, World};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn bullet(world: &mut World, position: Vec3, velocity: Vec3, layer: u32, shooter: u32) {
+    let mut body = RigidBody::new(0.05, position);
+    body.velocity = velocity;
+    world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_collider(Projectile::collider(0.1, layer, shooter))
+            .with_projectile(Projectile::new(None)),
+    );
+}
+
+fn horizontal_speeds(layer_a: u32, layer_b: u32) -> Vec<f32> {
+    let mut world = World::new();
+    // Пули летят навстречу по x и на первом шаге перекрываются в центре
+    bullet(
+        &mut world,
+        Vec3::new(-0.05, 5.0, 0.0),
+        Vec3::new(10.0, 0.0, 0.0),
+        layer_a,
+        layers::PLAYER,
+    );
+    bullet(
+        &mut world,
+        Vec3::new(0.05, 5.0, 0.0),
+        Vec3::new(-10.0, 0.0, 0.0),
+        layer_b,
+        layers::ENEMY,
+    );
+    for _ in 0..10 {
+        world.tick_projectiles(DT);
+        let mut ids = Vec::new();
+        let mut objs = Vec::new();
+        world.physics_objects(&mut ids, &mut objs);
+        let pairs = step(&mut objs, &[], DT);
+        assert!(pairs.is_empty(), "bullets collided: {pairs:?}");
+        world.remove_spent_projectiles();
+    }
+    world
+        .with_tag(Tag::Bullet)
+        .map(|(_, e)| e.body.unwrap().velocity.x)
+        .collect()
+}
+
+#[test]
+fn crossing_bullets_keep_flying() {
+    let speeds = horizontal_speeds(layers::PLAYER_BULLET, layers::ENEMY_BULLET);
+    assert_eq!(speeds.len(), 2);
+    assert!(speeds.iter().all(|v| v.abs() == 10.0), "speeds: {speeds:?}");
+}
+
+#[test]
+fn bullets_of_one_shooter_ignore_each_other() {
+    let speeds = horizontal_speeds(layers::PLAYER_BULLET, layers::PLAYER_BULLET);
+    assert_eq!(speeds.len(), 2);
+    assert!(speeds.iter().all(|v| v.abs() == 10.0), "speeds: {speeds:?}");
+}
+
+#[test]
+fn stopped_bullet_lives_until_its_lifetime_ends() {
+    let mut world = World::new();
+    let id = world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(RigidBody::new(0.05, Vec3::ZERO))
+            .with_projectile(Projectile::new(None).with_lifetime(0.5)),
+    );
+    // Скорость нулевая, но пуля не исчезает, пока не выйдет её время
+    for _ in 0..29 {
+        world.tick_projectiles(DT);
+        world.remove_spent_projectiles();
+    }
+    assert!(world.get(id).is_some());
+    world.tick_projectiles(DT * 2.0);
+    world.remove_spent_projectiles();
+    assert!(world.get(id).is_none());
+}
@@ -0,0 +1,94 @@
+// Автотест: пуля, летящая в игрока, снимает ему здоровье по своему импульсу
+// и отбрасывает его по направлению полёта; прогон детерминирован
+// Запуск: cargo test --test bullet_hit
+
+use astroforge::engine::input::InputState;
+use astroforge::engine::physics::{layers, PhysicsObject, RigidBody};
+use astroforge::game::{Game, GameOptions, MAX_HEALTH};
+use astroforge::world::{Entity, Projectile, Tag};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+const BULLET_MASS: f32 = 0.05;
+const BULLET_SPEED: f32 = 20.0;
+
+/// Пуля справа летит в игрока вдоль -X. Возвращает игру после `frames`
+/// шагов физики с разбором попаданий, как в `Game::update`, и скорость
+/// игрока сразу после попадания.
+fn run(frames: usize) -> (Game, Option<Vec3>) {
+    let mut game = Game::new(GameOptions::default());
+    // Ждём конца защиты после появления
+    game.player.update(&InputState::default(), 10.0);
+    // Подальше от кольца артефакта, чтобы пуле ничто не мешало
+    game.player.body.position = Vec3::new(10.0, 0.75, 10.0);
+    let mut body = RigidBody::new(
+        BULLET_MASS,
+        game.player.body.position + Vec3::new(4.0, 0.0, 0.0),
+    );
+    body.velocity = Vec3::new(-BULLET_SPEED, 0.0, 0.0);
+    game.world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_collider(Projectile::collider(
+                0.1,
+                layers::ENEMY_BULLET,
+                layers::ENEMY,
+            ))
+            .with_projectile(Projectile::new(None)),
+    );
+    let obstacles = game.static_obstacles();
+    let mut knockback = None;
+    for _ in 0..frames {
+        game.world.tick_projectiles(DT);
+        let mut ids = vec![game.player_id];
+        let pairs: Vec<_> = {
+            let mut objs = vec![PhysicsObject {
+                body: &mut game.player.body,
+                collider: game.player.collider,
+            }];
+            game.world.physics_objects(&mut ids, &mut objs);
+            let contacts = game.physics.step_contacts(&mut objs, &obstacles, DT);
+            contacts.iter().map(|c| (c.a, c.b)).collect()
+        };
+        for hit in game.world.bullet_hits(&ids, &pairs) {
+            game.world.mark_hit(hit.bullet);
+            game.apply_hit(hit);
+            knockback = Some(game.player.body.velocity);
+        }
+        game.world.mark_static_hits(&obstacles);
+        game.world.remove_spent_projectiles();
+    }
+    (game, knockback)
+}
+
+#[test]
+fn bullet_damages_and_knocks_back_the_player() {
+    let (before, _) = run(0);
+    assert_eq!(before.health, MAX_HEALTH);
+
+    let (game, knockback) = run(30);
+    // Урон по импульсу, с которым пуля влетела в игрока: 50 за единицу
+    let damage = (BULLET_MASS * BULLET_SPEED * 50.0) as i32;
+    assert_eq!(game.health, MAX_HEALTH - damage);
+    let knockback = knockback.expect("the bullet never hit");
+    assert!(knockback.x < 0.0, "knocked back along -X: {knockback}");
+    assert!(knockback.z.abs() < 1e-6);
+    assert_eq!(
+        game.world.with_tag(Tag::Bullet).count(),
+        0,
+        "bullet not removed"
+    );
+    // Индикатор урона показывает, что стреляли справа
+    assert_eq!(game.damage_indicators.len(), 1);
+    assert!(game.damage_indicators[0].source.x > game.player.body.position.x);
+}
+
+#[test]
+fn replay_is_deterministic() {
+    let (a, knock_a) = run(30);
+    let (b, knock_b) = run(30);
+    assert_eq!(knock_a, knock_b);
+    assert_eq!(a.health, b.health);
+    assert_eq!(a.player.body.position, b.player.body.position);
+    assert_eq!(a.player.body.velocity, b.player.body.velocity);
+}
@@ -0,0 +1,77 @@
+// Автотест: пули игрока и врага рисуются своими цветами и размерами
+// Запуск: cargo test --test bullet_looks
+
+use astroforge::config::{Level, Scenario};
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::palette::Palette;
+use astroforge::player::{Enemy, EnemyBehavior};
+use astroforge::world::{Entity, Tag};
+use glam::Vec3;
+
+fn arena(palette: Palette) -> Game {
+    Game::new(GameOptions {
+        scenario: Scenario {
+            level: Level::Arena,
+            ..Scenario::default()
+        },
+        palette,
+        ..GameOptions::default()
+    })
+}
+
+/// Кубы кадра цвета `color`.
+fn cubes_colored(game: &Game, color: [f32; 3]) -> Vec<CubeInstance> {
+    game.cubes()
+        .into_iter()
+        .filter(|c| c.color == color)
+        .collect()
+}
+
+#[test]
+fn player_and_enemy_bullets_use_their_own_colors() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let palette = Palette::default();
+    assert_ne!(palette.bullet, palette.enemy_bullet);
+    let mut game = arena(palette);
+    let enemy = Enemy::new()
+        .with_position(game.player.position + Vec3::new(4.0, -0.25, -4.0))
+        .with_behavior(EnemyBehavior::Static)
+        .with_bullet(0.2, palette.enemy_bullet);
+    game.enemy = Some((game.world.spawn(Entity::new(Tag::Enemy)), enemy));
+    // Пистолет достаётся полсекунды, враг за это время ещё не стреляет
+    assert!(game.player.inventory.select(1));
+    for _ in 0..30 {
+        game.update(&mut engine, 1.0 / 60.0);
+    }
+    assert_eq!(game.world.with_tag(Tag::Bullet).count(), 0);
+    game.enemy.as_mut().unwrap().1.bullet_timer = 0.0;
+    assert!(game.fire().is_some());
+    game.update(&mut engine, 1.0 / 60.0);
+    assert_eq!(game.world.with_tag(Tag::Bullet).count(), 2);
+
+    assert_eq!(cubes_colored(&game, palette.bullet).len(), 1);
+    assert_eq!(cubes_colored(&game, palette.enemy_bullet).len(), 1);
+    // Куб трассера вытянут по скорости, поэтому размер берём у самой пули
+    let mut sizes: Vec<_> = game
+        .world
+        .with_tag(Tag::Bullet)
+        .map(|(_, e)| e.renderable.unwrap().size)
+        .collect();
+    sizes.sort_by(f32::total_cmp);
+    assert!(sizes[0] < 0.2 && sizes[1] == 0.2, "{sizes:?}");
+}
+
+#[test]
+fn enemy_bullet_look_is_configurable() {
+    let enemy = Enemy::new();
+    assert_eq!(enemy.bullet_color, Palette::default().enemy_bullet);
+    assert!(enemy.bullet_size > 0.1, "enemy bullets are no bigger");
+    let palette = Palette::colorblind();
+    assert_ne!(palette.enemy_bullet, Palette::default().enemy_bullet);
+    let recolored = Enemy::new().with_bullet(0.3, palette.enemy_bullet);
+    assert_eq!(recolored.bullet_size, 0.3);
+    assert_eq!(recolored.bullet_color, palette.enemy_bullet);
+}
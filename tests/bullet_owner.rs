@@ -0,0 +1,47 @@
+// Автотест: пуля не наносит урон своему владельцу
+// Запуск: cargo test --test bullet_owner
+
+use astroforge::engine::physics::{step, Collider, RigidBody};
+use astroforge::world::{Entity, Projectile, Tag, World};
+use glam::Vec3;
+
+/// Игрок и пуля с заданным владельцем, перекрывающие друг друга.
+fn hits_on_player(owner_is_player: bool) -> Vec<astroforge::world::Hit> {
+    let mut world = World::new();
+    let player = world.spawn(
+        Entity::new(Tag::Player)
+            .with_body(RigidBody::new(70.0, Vec3::new(0.0, 5.0, 0.0)))
+            .with_collider(Collider::new(Vec3::new(0.5, 0.75, 0.5))),
+    );
+    let shooter = world.spawn(Entity::new(Tag::Enemy));
+    // Пуля задевает макушку: контакт по оси y, скорость вдоль x сохраняется
+    let mut bullet = RigidBody::new(0.05, Vec3::new(0.3, 5.8, 0.0));
+    bullet.velocity = Vec3::new(-5.0, 0.0, 0.0);
+    let owner = if owner_is_player { player } else { shooter };
+    world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(bullet)
+            .with_collider(Collider::new(Vec3::splat(0.1)))
+            .with_projectile(Projectile::new(Some(owner))),
+    );
+
+    let mut ids = Vec::new();
+    let mut objs = Vec::new();
+    world.physics_objects(&mut ids, &mut objs);
+    let pairs = step(&mut objs, &[], 1.0 / 60.0);
+    assert_eq!(pairs.len(), 1, "пуля должна касаться игрока");
+    world.bullet_hits(&ids, &pairs)
+}
+
+#[test]
+fn bullet_ignores_its_owner() {
+    assert!(hits_on_player(true).is_empty());
+}
+
+#[test]
+fn bullet_damages_non_owner() {
+    let hits = hits_on_player(false);
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].damage > 0);
+    assert!(hits[0].impulse.x < 0.0);
+}
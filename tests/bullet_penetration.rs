@@ -0,0 +1,110 @@
+// Автотест: пробивающая пуля ранит двух врагов на одной линии, а обычная —
+// только первого
+// Запуск: cargo test --test bullet_penetration
+
+use astroforge::engine::ecs::EntityId;
+use astroforge::engine::physics::{layers, sweep, Aabb, Collider, PhysicsWorld, RigidBody};
+use astroforge::world::{Entity, Penetration, Projectile, Tag, World};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+const HEALTH: i32 = 100;
+
+fn enemy(world: &mut World, z: f32) -> EntityId {
+    world.spawn(
+        Entity::new(Tag::Enemy)
+            .with_body(RigidBody::new(80.0, Vec3::new(0.0, 0.5, z)))
+            .with_collider(
+                Collider::new(Vec3::splat(0.5))
+                    .with_layer(layers::ENEMY, layers::ALL & !layers::ENEMY_BULLET),
+            )
+            .with_health(HEALTH),
+    )
+}
+
+fn health(world: &World, id: EntityId) -> i32 {
+    world.get(id).and_then(|e| e.health).unwrap()
+}
+
+/// Пуля летит вдоль -Z сквозь двух врагов, стоящих друг за другом.
+/// Возвращает их здоровье после полёта.
+fn shoot(projectile: Projectile) -> (i32, i32) {
+    let mut world = World::new();
+    let first = enemy(&mut world, -3.0);
+    let second = enemy(&mut world, -5.0);
+    let mut body = RigidBody::new(0.05, Vec3::new(0.0, 0.5, 0.0));
+    body.velocity = Vec3::new(0.0, 0.0, -20.0);
+    world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_collider(Projectile::collider(
+                0.1,
+                layers::PLAYER_BULLET,
+                layers::PLAYER,
+            ))
+            .with_projectile(projectile),
+    );
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(20.0, 0.5, 20.0),
+    )];
+    let physics = PhysicsWorld::new();
+    for _ in 0..30 {
+        world.tick_projectiles(DT);
+        let mut ids = Vec::new();
+        let pairs: Vec<_> = {
+            let mut objs = Vec::new();
+            world.physics_objects(&mut ids, &mut objs);
+            let contacts = physics.step_contacts(&mut objs, &floor, DT);
+            contacts.iter().map(|c| (c.a, c.b)).collect()
+        };
+        let mut hits = world.bullet_hits(&ids, &pairs);
+        for hit in &hits {
+            world.mark_hit(hit.bullet);
+        }
+        let targets = world.target_boxes();
+        hits.extend(world.penetrating_hits(&targets, DT));
+        for hit in hits {
+            world.damage(hit.target, hit.damage);
+        }
+        world.mark_static_hits(&floor);
+        world.remove_spent_projectiles();
+    }
+    (health(&world, first), health(&world, second))
+}
+
+#[test]
+fn penetrating_bullet_damages_both_enemies() {
+    let (first, second) = shoot(Projectile::new(None).with_penetration(Penetration::new(2)));
+    assert!(first < HEALTH, "first enemy untouched");
+    assert!(second < HEALTH, "second enemy untouched");
+    // Второй получает меньше: пуля теряет силу в первом
+    assert!(HEALTH - second < HEALTH - first, "{first} vs {second}");
+}
+
+#[test]
+fn plain_bullet_stops_in_the_first_enemy() {
+    let (first, second) = shoot(Projectile::new(None));
+    assert!(first < HEALTH);
+    assert_eq!(second, HEALTH);
+}
+
+#[test]
+fn thickness_limit_stops_the_bullet_early() {
+    let limited = Penetration::new(5).with_thickness(0.5);
+    let (first, second) = shoot(Projectile::new(None).with_penetration(limited));
+    assert!(first < HEALTH);
+    assert_eq!(second, HEALTH, "went through more than its thickness");
+}
+
+#[test]
+fn sweep_returns_entered_boxes_nearest_first() {
+    let near = Aabb::new(Vec3::new(0.0, 0.0, -2.0), Vec3::splat(0.5));
+    let far = Aabb::new(Vec3::new(0.0, 0.0, -6.0), Vec3::splat(0.5));
+    let around = Aabb::new(Vec3::ZERO, Vec3::splat(0.5));
+    let hits = sweep(Vec3::ZERO, Vec3::NEG_Z, 10.0, &[far, around, near]);
+    let order: Vec<_> = hits.iter().map(|h| h.index).collect();
+    assert_eq!(order, vec![2, 0], "the box the ray starts in is skipped");
+    assert!((hits[0].distance - 1.5).abs() < 1e-6);
+    assert!(sweep(Vec3::ZERO, Vec3::NEG_Z, 1.0, &[near]).is_empty());
+}
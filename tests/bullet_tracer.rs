@@ -0,0 +1,70 @@
+// Автотест: быстрая пуля вытягивается вдоль направления полёта
+// Запуск: cargo test --test bullet_tracer
+
+use astroforge::engine::physics::RigidBody;
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::world::{Entity, Renderable, Tag, World};
+use glam::Vec3;
+
+fn rendered(velocity: Vec3) -> CubeInstance {
+    let mut world = World::new();
+    let mut body = RigidBody::new(0.05, Vec3::ZERO);
+    body.velocity = velocity;
+    world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_renderable(Renderable {
+                size: 0.1,
+                color: [1.0, 1.0, 0.0],
+                tracer: 0.04,
+            }),
+    );
+    let mut cubes = Vec::new();
+    world.append_cubes(&mut cubes);
+    assert_eq!(cubes.len(), 1);
+    cubes[0]
+}
+
+fn rendered_size(velocity: Vec3) -> Vec3 {
+    rendered(velocity).size
+}
+
+#[test]
+fn fast_bullet_is_elongated_along_velocity() {
+    let size = rendered_size(Vec3::new(0.0, 0.0, -20.0));
+    assert!(size.z > 5.0 * size.x, "{size:?}");
+    assert_eq!(size.x, 0.1);
+    assert_eq!(size.y, 0.1);
+}
+
+#[test]
+fn resting_bullet_stays_cubic() {
+    assert_eq!(rendered_size(Vec3::ZERO), Vec3::splat(0.1));
+}
+
+#[test]
+fn faster_bullet_is_longer() {
+    let slow = rendered_size(Vec3::new(5.0, 0.0, 0.0));
+    let fast = rendered_size(Vec3::new(30.0, 0.0, 0.0));
+    assert!(fast.z > slow.z);
+}
+
+#[test]
+fn diagonal_bullet_streaks_along_its_velocity() {
+    let velocity = Vec3::new(12.0, -9.0, 20.0);
+    let cube = rendered(velocity);
+    // Поперёк пуля не толще, чем в покое: растянута только одна ось
+    assert_eq!(cube.size.x, 0.1);
+    assert_eq!(cube.size.y, 0.1);
+    assert!((cube.size.z - (0.1 + velocity.length() * 0.04)).abs() < 1e-4);
+    let long_axis = cube.rotation * Vec3::Z;
+    assert!(
+        long_axis.angle_between(velocity) < 1e-3,
+        "long axis {long_axis} is not along {velocity}"
+    );
+    // Куб отцентрован на теле, а не растянут вверх от него
+    let model = cube.model_matrix();
+    let ends = [Vec3::new(0.0, 0.0, 0.5), Vec3::new(0.0, 0.0, -0.5)]
+        .map(|p| model.transform_point3(p + Vec3::new(0.0, 0.5, 0.0)));
+    assert!((ends[0] + ends[1]).length() < 1e-4, "{ends:?}");
+}
@@ -0,0 +1,67 @@
+// Автотест: капсула, задевшая угол коробки, соскальзывает, а коробка застревает
+// Запуск: cargo test --test capsule_collider
+
+use astroforge::engine::physics::{step, Aabb, Collider, PhysicsObject, RigidBody};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+const SPEED: f32 = 3.0;
+
+/// Высокий столб с вертикальным ребром в точке x = 1, z = -1.
+fn pillar() -> Aabb {
+    Aabb::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(1.0, 5.0, 1.0))
+}
+
+/// Тело идёт вдоль +z так, что его край на 0.3 заходит за ребро столба.
+/// Возвращает конечную позицию.
+fn walk_past_corner(collider: Collider) -> Vec3 {
+    let mut body = RigidBody::new(80.0, Vec3::new(1.2, 5.0, -4.0));
+    for _ in 0..240 {
+        body.velocity = Vec3::new(0.0, 0.0, SPEED);
+        let mut objs = [PhysicsObject {
+            body: &mut body,
+            collider,
+        }];
+        step(&mut objs, &[pillar()], DT);
+    }
+    body.position
+}
+
+#[test]
+fn box_stops_dead_on_the_corner() {
+    let end = walk_past_corner(Collider::new(Vec3::new(0.5, 0.9, 0.5)));
+    assert!(end.z < -1.0, "box got past: {end}");
+}
+
+#[test]
+fn capsule_slides_around_the_corner() {
+    let end = walk_past_corner(Collider::capsule(0.5, 1.8));
+    assert!(end.z > 2.0, "capsule stuck at {end}");
+    // Соскальзывая, капсула отошла от столба ровно на свой радиус
+    assert!(
+        end.x >= 1.5 - 1e-4 && end.x < 1.6,
+        "x after sliding: {}",
+        end.x
+    );
+}
+
+#[test]
+fn capsule_rests_on_the_floor_at_its_half_height() {
+    let floor = Aabb::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(50.0, 0.5, 50.0));
+    let collider = Collider::capsule(0.4, 1.8);
+    assert_eq!(collider.half_extents, Vec3::new(0.4, 0.9, 0.4));
+    let mut body = RigidBody::new(80.0, Vec3::new(0.0, 3.0, 0.0));
+    for _ in 0..240 {
+        let mut objs = [PhysicsObject {
+            body: &mut body,
+            collider,
+        }];
+        step(&mut objs, &[floor], DT);
+    }
+    assert!(body.on_ground);
+    assert!(
+        (body.position.y - 0.9).abs() < 0.02,
+        "y = {}",
+        body.position.y
+    );
+}
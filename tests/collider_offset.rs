@@ -0,0 +1,86 @@
+// Автотест: смещённый коллайдер стоит на полу, а начало тела ниже его центра
+// Запуск: cargo test --test collider_offset
+
+use astroforge::engine::physics::{
+    step, teleport, Aabb, Arena, Collider, PhysicsObject, RigidBody,
+};
+use glam::{Vec2, Vec3};
+
+const HALF: Vec3 = Vec3::new(0.5, 0.75, 0.5);
+
+fn floor() -> Aabb {
+    Aabb::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(50.0, 0.5, 50.0))
+}
+
+/// Высота покоя тела, упавшего на пол.
+fn resting_height(collider: Collider) -> f32 {
+    let mut body = RigidBody::new(80.0, Vec3::new(0.0, 3.0, 0.0));
+    for _ in 0..240 {
+        let mut objs = [PhysicsObject {
+            body: &mut body,
+            collider,
+        }];
+        step(&mut objs, &[floor()], 1.0 / 60.0);
+    }
+    assert!(body.on_ground);
+    body.position.y
+}
+
+#[test]
+fn offset_collider_rests_with_origin_at_its_feet() {
+    let centered = resting_height(Collider::new(HALF));
+    let offset = resting_height(Collider::new(HALF).with_center_offset(Vec3::Y * 0.75));
+    assert!((centered - 0.75).abs() < 1e-4, "centered: {centered}");
+    assert!(offset.abs() < 1e-4, "offset: {offset}");
+    assert!((centered - offset - 0.75).abs() < 1e-4);
+}
+
+#[test]
+fn offset_applies_to_pairs_and_bounds() {
+    let collider = Collider::new(Vec3::splat(0.5)).with_center_offset(Vec3::X);
+    assert_eq!(
+        Aabb::around(Vec3::ZERO, &collider).center,
+        Vec3::X,
+        "box follows the offset"
+    );
+    // Начала тел в 2 м друг от друга, но коллайдер первого сдвинут к второму
+    let mut a = RigidBody::new(1.0, Vec3::new(0.0, 5.0, 0.0));
+    let mut b = RigidBody::new(1.0, Vec3::new(1.8, 5.0, 0.0));
+    let mut objs = [
+        PhysicsObject {
+            body: &mut a,
+            collider,
+        },
+        PhysicsObject {
+            body: &mut b,
+            collider: Collider::new(Vec3::splat(0.5)),
+        },
+    ];
+    let pairs = step(&mut objs, &[], 1.0 / 60.0);
+    assert_eq!(pairs, vec![(0, 1)]);
+}
+
+#[test]
+fn arena_and_teleport_respect_the_offset() {
+    let collider = Collider::new(Vec3::splat(0.5)).with_center_offset(Vec3::X);
+    let arena = Arena::new(Vec2::splat(-10.0), Vec2::splat(10.0));
+    let mut body = RigidBody::new(1.0, Vec3::new(20.0, 0.0, 0.0));
+    arena.confine(&mut body, &collider);
+    // Правый край коллайдера упирается в стену
+    assert!((body.position.x + 1.0 + 0.5 - 10.0).abs() < 1e-5);
+
+    let wall = Aabb::new(Vec3::ZERO, Vec3::splat(1.0));
+    let mut body = RigidBody::new(1.0, Vec3::ZERO);
+    assert!(teleport(
+        &mut body,
+        &collider,
+        Vec3::new(-1.0, 0.0, 0.0),
+        &[wall]
+    ));
+    let bounds = Aabb::around(body.position, &collider);
+    assert!(
+        !bounds.overlaps(&wall),
+        "ended inside the wall at {}",
+        body.position
+    );
+}
@@ -0,0 +1,54 @@
+// Автотест: слои и маски коллизий отсекают пары, которые не должны взаимодействовать
+// Запуск: cargo test --test collision_layers
+
+use astroforge::engine::physics::{layers, step, Collider, PhysicsObject, RigidBody};
+use glam::Vec3;
+
+fn overlapping_pair(a: Collider, b: Collider) -> (Vec<(usize, usize)>, RigidBody, RigidBody) {
+    let mut body_a = RigidBody::new(1.0, Vec3::new(0.0, 5.0, 0.0));
+    let mut body_b = RigidBody::new(1.0, Vec3::new(0.2, 5.0, 0.0));
+    let mut objs = vec![
+        PhysicsObject {
+            body: &mut body_a,
+            collider: a,
+        },
+        PhysicsObject {
+            body: &mut body_b,
+            collider: b,
+        },
+    ];
+    let pairs = step(&mut objs, &[], 1.0 / 60.0);
+    (pairs, body_a, body_b)
+}
+
+#[test]
+fn masked_out_layers_produce_no_contact() {
+    let shape = Vec3::splat(0.5);
+    let player =
+        Collider::new(shape).with_layer(layers::PLAYER, layers::ALL & !layers::PLAYER_BULLET);
+    let own_bullet =
+        Collider::new(shape).with_layer(layers::PLAYER_BULLET, layers::ALL & !layers::PLAYER);
+    let (pairs, a, b) = overlapping_pair(player, own_bullet);
+    assert!(pairs.is_empty());
+    // Тела не расталкиваются
+    assert_eq!(a.position.x, 0.0);
+    assert_eq!(b.position.x, 0.2);
+}
+
+#[test]
+fn one_sided_mask_is_enough_to_skip() {
+    let shape = Vec3::splat(0.5);
+    let enemy = Collider::new(shape).with_layer(layers::ENEMY, layers::ALL);
+    let enemy_bullet =
+        Collider::new(shape).with_layer(layers::ENEMY_BULLET, layers::ALL & !layers::ENEMY);
+    assert!(!enemy.interacts_with(&enemy_bullet));
+    assert!(overlapping_pair(enemy, enemy_bullet).0.is_empty());
+}
+
+#[test]
+fn default_layers_still_collide() {
+    let shape = Vec3::splat(0.5);
+    let (pairs, a, b) = overlapping_pair(Collider::new(shape), Collider::new(shape));
+    assert_eq!(pairs, vec![(0, 1)]);
+    assert!(b.position.x - a.position.x >= 1.0 - 1e-5);
+}
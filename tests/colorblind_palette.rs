@@ -0,0 +1,35 @@
+// Автотест: палитра для дальтоников перекрашивает врага и полоску здоровья
+// Запуск: cargo test --test colorblind_palette
+
+use astroforge::engine::hud::HudTheme;
+use astroforge::palette::Palette;
+use astroforge::player::Enemy;
+
+fn enemy_colors(enemy: &Enemy) -> Vec<[f32; 3]> {
+    let mut cubes = Vec::new();
+    enemy.append_cubes(&mut cubes);
+    cubes.iter().map(|c| c.color).collect()
+}
+
+#[test]
+fn colorblind_palette_recolors_enemy() {
+    let mut enemy = Enemy::new();
+    assert!(enemy_colors(&enemy).iter().all(|&c| c == [1.0, 0.0, 0.0]));
+
+    let palette = Palette::colorblind();
+    enemy.color = palette.enemy;
+    let colors = enemy_colors(&enemy);
+    assert!(!colors.is_empty());
+    assert!(colors.iter().all(|&c| c == palette.enemy));
+    assert_ne!(palette.enemy, Palette::default().enemy);
+}
+
+#[test]
+fn colorblind_palette_replaces_health_colors() {
+    let palette = Palette::colorblind();
+    let theme = palette.apply_to_hud(HudTheme::high_contrast());
+    assert_eq!(theme.health_high, palette.health_high);
+    assert_eq!(theme.health_low, palette.health_low);
+    // Остальные цвета темы не меняются
+    assert_eq!(theme.text, HudTheme::high_contrast().text);
+}
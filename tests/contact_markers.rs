@@ -0,0 +1,73 @@
+// Автотест: шаг физики сообщает точку и нормаль контакта, а метки контакта
+// видны в кадре там, где тела соприкоснулись
+// Запуск: cargo test --test contact_markers
+
+use astroforge::engine::physics::{Collider, Contact, PhysicsObject, PhysicsWorld, RigidBody};
+use astroforge::engine::renderer::{contact_markers, CubeInstance};
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 400;
+
+/// Два единичных куба, заходящие друг в друга по x на 0.2.
+fn overlapping_contacts() -> Vec<Contact> {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut bodies = [-0.4, 0.4].map(|x| RigidBody::new(80.0, Vec3::new(x, 5.0, 0.0)));
+    let mut objs: Vec<PhysicsObject> = bodies
+        .iter_mut()
+        .map(|body| PhysicsObject { body, collider })
+        .collect();
+    PhysicsWorld::new().step_contacts(&mut objs, &[], 0.0)
+}
+
+fn render(cubes: &[CubeInstance]) -> Vec<u8> {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    // Камера смотрит на точку контакта спереди
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    engine.renderer.render(None, 100, cubes);
+    engine.renderer.get_frame_rgba8()
+}
+
+fn lit(frame: &[u8], x: u32, y: u32) -> bool {
+    let i = ((y * SIZE + x) * 4) as usize;
+    frame[i..i + 3] != [0, 0, 0]
+}
+
+#[test]
+fn step_reports_contact_point_and_normal() {
+    let contacts = overlapping_contacts();
+    assert_eq!(contacts.len(), 1);
+    let c = contacts[0];
+    assert_eq!((c.a, c.b), (0, 1));
+    assert!(c.point.distance(Vec3::new(0.0, 5.0, 0.0)) < 1e-4, "{c:?}");
+    // Нормаль направлена от второго тела к первому
+    assert_eq!(c.normal, Vec3::NEG_X);
+    assert!((c.depth - 0.2).abs() < 1e-4, "{c:?}");
+}
+
+#[test]
+fn markers_show_up_at_the_contact() {
+    let contacts = overlapping_contacts();
+    let cubes: Vec<CubeInstance> = contacts
+        .iter()
+        .flat_map(|c| contact_markers(c.point, c.normal, [1.0, 1.0, 0.0]))
+        .collect();
+    let frame = render(&cubes);
+    let center = SIZE / 2;
+    assert!(
+        lit(&frame, center, center),
+        "no marker at the contact point"
+    );
+    // Полоска нормали уходит влево, в сторону первого тела
+    assert!(lit(&frame, center - 25, center), "no normal bar");
+    assert!(
+        !lit(&frame, center + 25, center),
+        "normal bar on the wrong side"
+    );
+
+    let empty = render(&[]);
+    assert!(!lit(&empty, center, center));
+}
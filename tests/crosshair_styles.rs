@@ -0,0 +1,79 @@
+// Автотест: у динамического прицела зазор шире, когда игрок бежит, чем когда
+// стоит, а остальные стили от движения не меняются
+// Запуск: cargo test --test crosshair_styles
+
+use astroforge::crosshair::{Crosshair, CrosshairStyle};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::{Mat4, Vec2, Vec3};
+
+const W: u32 = 400;
+const H: u32 = 300;
+
+/// Расстояние от центра экрана до первого светлого пикселя правее него.
+fn gap_on_screen(frame: &[u8]) -> u32 {
+    let y = H / 2;
+    (W / 2..W)
+        .find(|&x| {
+            let i = ((y * W + x) * 4) as usize;
+            frame[i..i + 3].iter().all(|&c| c > 200)
+        })
+        .map(|x| x - W / 2)
+        .expect("no crosshair right of the center")
+}
+
+fn render_gap(engine: &mut Engine, game: &mut Game) -> u32 {
+    game.render(engine);
+    // Нулевая матрица камеры прячет сцену: в кадре только HUD
+    engine.renderer.update_camera(&Mat4::ZERO);
+    engine.renderer.render(None, 100, &[]);
+    gap_on_screen(&engine.renderer.get_frame_rgba8())
+}
+
+#[test]
+fn dynamic_gap_widens_while_moving() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(W, H);
+    let mut game = Game::new(GameOptions {
+        crosshair: Crosshair::new(CrosshairStyle::Dynamic),
+        ..GameOptions::default()
+    });
+    let still = render_gap(&mut engine, &mut game);
+    game.player.body.velocity = Vec3::new(5.0, 0.0, 0.0);
+    let moving = render_gap(&mut engine, &mut game);
+    assert!(
+        moving > still + 5,
+        "{still} px standing, {moving} px moving"
+    );
+
+    // Обычный крест от движения не расходится
+    game.options.crosshair = Crosshair::new(CrosshairStyle::Cross);
+    let cross = render_gap(&mut engine, &mut game);
+    game.player.body.velocity = Vec3::ZERO;
+    assert_eq!(render_gap(&mut engine, &mut game), cross);
+}
+
+#[test]
+fn gap_follows_speed_and_spread() {
+    let dynamic = Crosshair::new(CrosshairStyle::Dynamic);
+    assert_eq!(dynamic.current_gap(0.0, 0.0), dynamic.gap);
+    assert!(dynamic.current_gap(3.0, 0.0) > dynamic.gap);
+    assert!(dynamic.current_gap(0.0, 0.02) > dynamic.gap);
+    let circle = Crosshair::new(CrosshairStyle::Circle);
+    assert_eq!(circle.current_gap(3.0, 0.02), circle.gap);
+
+    let screen = Vec2::new(W as f32, H as f32);
+    let center = screen * 0.5;
+    // Каждый кусок рисуется поверх своей полупрозрачной каймы
+    let dot = Crosshair::new(CrosshairStyle::Dot).quads(screen, 0.0, 0.0);
+    assert_eq!(dot.len(), 2);
+    assert!(dot[0].size.x > dot[1].size.x && dot[0].color[3] < dot[1].color[3]);
+    for quad in circle.quads(screen, 0.0, 0.0) {
+        assert!((quad.center.distance(center) - circle.gap).abs() < 1e-3);
+    }
+    assert_eq!(
+        CrosshairStyle::from_name("circle"),
+        Some(CrosshairStyle::Circle)
+    );
+    assert_eq!(CrosshairStyle::from_name("plus"), None);
+}
@@ -0,0 +1,51 @@
+// Автотест: тело под прицелом отмечается как цель и подсвечивается, а
+// остальные — нет
+// Запуск: cargo test --test crosshair_target
+
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::Player;
+use glam::{Quat, Vec3};
+
+#[test]
+fn only_the_body_under_the_crosshair_is_targeted() {
+    let mut game = Game::new(GameOptions::default());
+    assert_eq!(game.body_under_crosshair(), None);
+
+    // Игрок смотрит вдоль -Z: первая бочка прямо перед ним, вторая сбоку
+    let ahead = game.spawn_barrel(game.player.position + Vec3::new(0.0, 0.0, -2.5));
+    let aside = game.spawn_barrel(game.player.position + Vec3::new(1.5, 0.0, -2.0));
+    assert_eq!(game.body_under_crosshair(), Some(ahead));
+
+    let highlighted: Vec<_> = game.cubes().into_iter().filter(|c| c.highlight).collect();
+    assert_eq!(highlighted.len(), 1);
+    let barrel = game.world.get(ahead).and_then(|e| e.body).unwrap();
+    assert_eq!(highlighted[0].position, barrel.position);
+
+    // Повернувшись ко второй бочке, игрок целится уже в неё
+    let to_aside =
+        game.world.get(aside).and_then(|e| e.body).unwrap().position - game.player.position;
+    game.player.rotation = Quat::from_rotation_arc(Vec3::NEG_Z, to_aside.normalize());
+    assert_eq!(game.body_under_crosshair(), Some(aside));
+}
+
+#[test]
+fn level_geometry_hides_the_target() {
+    let mut game = Game::new(GameOptions::default());
+    let eye = game.player.position;
+    // Бочка за ближайшим блоком кольца артефакта
+    let block = Player::artifact_aabbs(game.options.artifact_blocks)
+        .into_iter()
+        .min_by(|a, b| a.center.distance(eye).total_cmp(&b.center.distance(eye)))
+        .unwrap();
+    let flat = Vec3::new(block.center.x - eye.x, 0.0, block.center.z - eye.z).normalize();
+    let position = block.center + flat * 2.0;
+    let barrel = game.spawn_barrel(position);
+    let dir = (position - eye).normalize();
+    game.player.rotation = Quat::from_rotation_arc(Vec3::NEG_Z, dir);
+    assert_eq!(game.body_under_crosshair(), None);
+    assert!(game.cubes().iter().all(|c| !c.highlight));
+
+    // Без кольца бочку видно
+    game.options.artifact_blocks = 0;
+    assert_eq!(game.body_under_crosshair(), Some(barrel));
+}
@@ -0,0 +1,36 @@
+// Автотест: модельная матрица инстанса учитывает масштаб по осям и поворот
+// Запуск: cargo test --test cube_instance
+
+use astroforge::engine::renderer::CubeInstance;
+use glam::{Quat, Vec3};
+
+/// Верхний угол меша единичного куба (меш стоит на y = 0).
+const MESH_CORNER: Vec3 = Vec3::new(0.5, 1.0, 0.5);
+
+#[test]
+fn rotated_instance_moves_corner_to_expected_position() {
+    let cube = CubeInstance::new(Vec3::new(10.0, 0.0, 0.0), 1.0, [1.0; 3])
+        .with_size(Vec3::new(2.0, 1.0, 4.0))
+        .with_rotation(Quat::from_rotation_y(90f32.to_radians()));
+    let corner = cube.model_matrix().transform_point3(MESH_CORNER);
+    // (0.5, 0.5, 0.5) * (2, 1, 4) = (1, 0.5, 2); поворот на 90° вокруг y
+    // переводит (x, y, z) в (z, y, -x)
+    assert!(
+        corner.abs_diff_eq(Vec3::new(12.0, 0.5, -1.0), 1e-5),
+        "{corner:?}"
+    );
+}
+
+#[test]
+fn uniform_constructor_is_axis_aligned_and_centered() {
+    let cube = CubeInstance::new(Vec3::new(1.0, 2.0, 3.0), 0.5, [1.0; 3]);
+    assert_eq!(cube.size, Vec3::splat(0.5));
+    assert_eq!(cube.rotation, Quat::IDENTITY);
+    let m = cube.model_matrix();
+    assert!(m
+        .transform_point3(MESH_CORNER)
+        .abs_diff_eq(Vec3::new(1.25, 2.25, 3.25), 1e-6));
+    assert!(m
+        .transform_point3(Vec3::new(-0.5, 0.0, -0.5))
+        .abs_diff_eq(Vec3::new(0.75, 1.75, 2.75), 1e-6));
+}
@@ -0,0 +1,65 @@
+// Автотест: попадание справа от игрока рисует индикатор урона у правого
+// края экрана, и он гаснет со временем
+// Запуск: cargo test --test damage_indicator
+
+use astroforge::damage_indicator::DamageIndicator;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::{Mat4, Vec2, Vec3};
+
+const W: u32 = 400;
+const H: u32 = 300;
+
+fn is_red(frame: &[u8], x: u32, y: u32) -> bool {
+    let i = ((y * W + x) * 4) as usize;
+    frame[i] > 200 && frame[i + 1] < 128 && frame[i + 2] < 128
+}
+
+#[test]
+fn hit_from_the_right_shows_on_the_right() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(W, H);
+    let mut game = Game::new(GameOptions::default());
+    // Игрок смотрит вдоль -Z, стреляли справа
+    let source = game.player.position + Vec3::new(5.0, 0.0, 0.0);
+    game.indicate_damage(source);
+    game.render(&mut engine);
+    assert_eq!(engine.renderer.overlay_quads.len(), 1);
+
+    // Нулевая матрица камеры прячет сцену: в кадре только HUD
+    engine.renderer.update_camera(&Mat4::ZERO);
+    engine.renderer.render(None, 100, &[]);
+    let frame = engine.renderer.get_frame_rgba8();
+    let offset = (H as f32 * 0.5 * 0.8) as u32;
+    assert!(
+        is_red(&frame, W / 2 + offset, H / 2),
+        "no indicator on the right"
+    );
+    assert!(
+        !is_red(&frame, W / 2 - offset, H / 2),
+        "indicator on the left"
+    );
+    assert!(
+        !is_red(&frame, W / 2, H / 2 - offset),
+        "indicator at the top"
+    );
+}
+
+#[test]
+fn indicator_points_around_the_screen_and_fades() {
+    let screen = Vec2::new(W as f32, H as f32);
+    let center = screen * 0.5;
+    let eye = Vec3::ZERO;
+    let forward = Vec3::NEG_Z;
+    let quad = |source: Vec3| DamageIndicator::new(source).quad(eye, forward, screen);
+    assert!(quad(Vec3::X).center.x > center.x);
+    assert!(quad(Vec3::NEG_X).center.x < center.x);
+    assert!(quad(Vec3::NEG_Z).center.y < center.y, "ahead is the top");
+    assert!(quad(Vec3::Z).center.y > center.y, "behind is the bottom");
+
+    let mut indicator = DamageIndicator::new(Vec3::X);
+    assert_eq!(indicator.alpha(), 1.0);
+    assert!(indicator.tick(0.5));
+    assert!((indicator.alpha() - 0.5).abs() < 1e-4);
+    assert!(!indicator.tick(0.6), "still visible after the fade");
+}
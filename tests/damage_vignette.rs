@@ -0,0 +1,86 @@
+// Автотест: урон вспыхивает красным по краям экрана, вспышка гаснет за
+// несколько кадров, а при малом здоровье края остаются красноватыми
+// Запуск: cargo test --test damage_vignette
+
+use astroforge::engine::input::InputState;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions, MAX_HEALTH};
+use astroforge::vignette::{DamageVignette, FLASH_TIME};
+use glam::Mat4;
+
+const W: u32 = 200;
+const H: u32 = 150;
+const DT: f32 = 1.0 / 60.0;
+
+/// Кадр только с HUD: нулевая матрица камеры прячет сцену.
+fn hud_frame(engine: &mut Engine, game: &mut Game) -> Vec<u8> {
+    game.render(engine);
+    engine.renderer.update_camera(&Mat4::ZERO);
+    engine.renderer.render(None, game.health, &[]);
+    engine.renderer.get_frame_rgba8()
+}
+
+/// Красный и зелёный канал пикселя.
+fn red_green(frame: &[u8], x: u32, y: u32) -> (u8, u8) {
+    let i = ((y * W + x) * 4) as usize;
+    (frame[i], frame[i + 1])
+}
+
+#[test]
+fn damage_flashes_edges_red_and_fades() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(W, H);
+    let mut game = Game::new(GameOptions::default());
+    // Защита после появления не даёт получить урон, ждём её конца
+    game.player.update(&InputState::default(), 10.0);
+    let before = hud_frame(&mut engine, &mut game);
+    assert_eq!(
+        red_green(&before, 1, H / 2).0,
+        0,
+        "red edges before any hit"
+    );
+
+    assert!(game.damage_player(10));
+    let frame = hud_frame(&mut engine, &mut game);
+    let (red, green) = red_green(&frame, 1, H / 2);
+    assert!(red > 100 && green < 20, "edge not red: {red} {green}");
+    assert_eq!(red_green(&frame, W / 2, H / 2).0, 0, "center is tinted");
+    let corner = red_green(&frame, 1, 1).0;
+    assert!(corner >= red, "corner {corner} weaker than edge {red}");
+
+    let mut last = red;
+    for _ in 0..3 {
+        for _ in 0..5 {
+            game.update(&mut engine, DT);
+        }
+        let now = red_green(&hud_frame(&mut engine, &mut game), 1, H / 2).0;
+        assert!(now < last, "flash not fading: {now} after {last}");
+        last = now;
+    }
+    for _ in 0..(FLASH_TIME / DT) as usize {
+        game.update(&mut engine, DT);
+    }
+    let faded = hud_frame(&mut engine, &mut game);
+    assert_eq!(red_green(&faded, 1, H / 2).0, 0, "flash never faded");
+}
+
+#[test]
+fn low_health_keeps_a_tint_after_the_flash() {
+    let mut vignette = DamageVignette::new();
+    assert_eq!(vignette.alpha(MAX_HEALTH, MAX_HEALTH), 0.0);
+    vignette.flash();
+    let flash = vignette.alpha(MAX_HEALTH, MAX_HEALTH);
+    assert!(flash > 0.0);
+    vignette.tick(FLASH_TIME);
+    assert_eq!(vignette.alpha(MAX_HEALTH / 2, MAX_HEALTH), 0.0);
+
+    let low = vignette.alpha(10, MAX_HEALTH);
+    let dying = vignette.alpha(1, MAX_HEALTH);
+    assert!(low > 0.0 && dying > low && dying < flash);
+    assert!(vignette
+        .quad(glam::Vec2::new(100.0, 100.0), 1, MAX_HEALTH)
+        .is_some());
+    assert!(vignette
+        .quad(glam::Vec2::new(100.0, 100.0), 60, MAX_HEALTH)
+        .is_none());
+}
@@ -0,0 +1,51 @@
+// Автотест: рывок даёт горизонтальный импульс, не повторяется до конца перезарядки и даёт краткую неуязвимость
+// Запуск: cargo test --test dash
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::player::Player;
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn dash_applies_the_expected_impulse() {
+    let mut player = Player::new();
+    assert!(player.dash(Vec3::new(3.0, 5.0, 0.0)));
+    let expected = player.dash_impulse / player.body.mass;
+    assert_eq!(player.body.velocity, Vec3::new(expected, 0.0, 0.0));
+}
+
+#[test]
+fn second_dash_within_cooldown_does_nothing() {
+    let mut player = Player::new();
+    assert!(player.dash(Vec3::X));
+    let velocity = player.body.velocity;
+    assert!(!player.dash(Vec3::Z));
+    assert_eq!(player.body.velocity, velocity);
+
+    // После перезарядки рывок снова доступен
+    let input = InputState::default();
+    for _ in 0..70 {
+        player.update(&input, 1.0 / 60.0);
+    }
+    assert!(player.dash(Vec3::Z));
+}
+
+#[test]
+fn dash_grants_brief_invulnerability() {
+    let mut player = Player::new();
+    assert!(!player.invulnerable());
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::LShift]));
+    player.update(&input, 1.0 / 60.0);
+    assert!(player.invulnerable());
+    assert!(
+        player.body.velocity.z < 0.0,
+        "dashes where the player looks"
+    );
+
+    input.reset();
+    for _ in 0..30 {
+        player.update(&input, 1.0 / 60.0);
+    }
+    assert!(!player.invulnerable());
+}
@@ -0,0 +1,42 @@
+// Автотест: отладочный HUD выводит позицию и скорость игрока
+// Запуск: cargo test --test debug_hud
+
+use astroforge::engine::input::InputFrame;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn debug_overlay_queues_the_player_position() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(400, 300);
+    let mut game = Game::new(GameOptions::default());
+    game.render(&mut engine);
+    assert_eq!(engine.renderer.hud_debug, None);
+
+    // F3 включает отладочный вывод
+    engine
+        .input
+        .apply_frame(&InputFrame::holding(&[VirtualKeyCode::F3]));
+    game.update(&mut engine, 1.0 / 60.0);
+    engine.input.reset();
+    assert!(game.debug_hud);
+    game.player.body.position = Vec3::new(1.25, 3.5, -7.75);
+    game.player.body.velocity = Vec3::new(3.0, 0.0, 4.0);
+    game.render(&mut engine);
+
+    let text = engine.renderer.hud_debug.clone().expect("debug text");
+    assert!(text.contains("1.25 3.50 -7.75"), "{text}");
+    assert!(text.contains("speed: 5.00"), "{text}");
+    let queued: Vec<String> = engine
+        .renderer
+        .debug_sections(&text)
+        .iter()
+        .flat_map(|s| s.text.iter().map(|t| t.text.clone()))
+        .collect();
+    assert!(queued.iter().any(|line| line.contains("1.25 3.50 -7.75")));
+    // Строки прижаты к нижнему краю кадра
+    let last = engine.renderer.debug_sections(&text).pop().unwrap();
+    assert!(last.screen_position.1 > 200.0);
+}
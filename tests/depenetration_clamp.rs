@@ -0,0 +1,58 @@
+// Автотест: с ограничением выталкивания лёгкое тело, глубоко вошедшее в
+// тяжёлое, выходит из него за несколько кадров без резкого скачка
+// Запуск: cargo test --test depenetration_clamp
+
+use astroforge::engine::physics::{Aabb, Collider, PhysicsObject, PhysicsWorld, RigidBody};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Лёгкое тело почти в центре тяжёлого. Возвращает смещение лёгкого тела
+/// за каждый шаг, пока тела не разойдутся.
+fn push_out(world: &PhysicsWorld) -> Vec<f32> {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut heavy = RigidBody::new(80.0, Vec3::new(0.0, 5.0, 0.0));
+    let mut light = RigidBody::new(0.05, Vec3::new(0.1, 5.0, 0.0));
+    let mut moves = Vec::new();
+    for _ in 0..120 {
+        let before = light.position;
+        let mut objs = [
+            PhysicsObject {
+                body: &mut heavy,
+                collider,
+            },
+            PhysicsObject {
+                body: &mut light,
+                collider,
+            },
+        ];
+        world.step(&mut objs, &[], DT);
+        // Гравитация тянет оба тела одинаково, считаем только расталкивание
+        moves.push((light.position.x - before.x).abs());
+        let apart = Aabb::around(heavy.position, &collider)
+            .penetration(&Aabb::around(light.position, &collider));
+        if apart <= 1e-4 {
+            return moves;
+        }
+    }
+    panic!("bodies never separated");
+}
+
+#[test]
+fn deep_overlap_is_worked_off_over_several_steps() {
+    let world = PhysicsWorld::new()
+        .with_solver_iterations(4)
+        .with_max_depenetration(0.2);
+    let moves = push_out(&world);
+    assert!(moves.len() >= 5, "separated in {} steps", moves.len());
+    for m in &moves {
+        assert!(*m <= 0.1 + 1e-4, "jumped {m} in one step");
+    }
+}
+
+#[test]
+fn unlimited_resolve_separates_in_one_jump() {
+    let moves = push_out(&PhysicsWorld::new());
+    assert_eq!(moves.len(), 1);
+    assert!(moves[0] > 0.4, "{}", moves[0]);
+}
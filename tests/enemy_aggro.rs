@@ -0,0 +1,33 @@
+// Автотест: враг за пределами дальности агрессии не гонится за игроком, а
+// внутри неё — гонится
+// Запуск: cargo test --test enemy_aggro
+
+use astroforge::player::Enemy;
+use glam::Vec3;
+
+#[test]
+fn enemy_chases_only_within_aggro_range() {
+    let mut enemy = Enemy::new().with_aggro_range(10.0);
+    let start = enemy.body.position;
+
+    let far = start + Vec3::new(20.0, 0.0, 0.0);
+    assert!(!enemy.in_aggro_range(far));
+    enemy.steer(far);
+    assert_eq!(enemy.body.force, Vec3::ZERO, "chased a target out of range");
+
+    let near = start + Vec3::new(6.0, 0.0, 0.0);
+    assert!(enemy.in_aggro_range(near));
+    enemy.steer(near);
+    assert!(enemy.body.force.x > 0.0, "no chase force toward the target");
+    assert_eq!(enemy.body.force.z, 0.0);
+}
+
+#[test]
+fn range_ignores_height_and_defaults_to_unlimited() {
+    let enemy = Enemy::new().with_aggro_range(5.0);
+    let above = enemy.body.position + Vec3::new(3.0, 30.0, 0.0);
+    assert!(enemy.in_aggro_range(above));
+
+    let unlimited = Enemy::new();
+    assert!(unlimited.in_aggro_range(Vec3::splat(1.0e6)));
+}
@@ -0,0 +1,37 @@
+// Автотест: «замороженный» враг не двигается, а преследующий идёт к цели
+// Запуск: cargo test --test enemy_behavior
+
+use astroforge::engine::physics::{step, Aabb, PhysicsObject};
+use astroforge::player::{Enemy, EnemyBehavior};
+use glam::Vec3;
+
+/// Несколько секунд симуляции врага, который видит цель в начале координат.
+fn simulate(mut enemy: Enemy) -> (Vec3, Vec3) {
+    let ground = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )];
+    let start = enemy.body.position;
+    for _ in 0..180 {
+        enemy.steer(Vec3::ZERO);
+        enemy.update(1.0 / 60.0);
+        let mut objs = [PhysicsObject {
+            body: &mut enemy.body,
+            collider: enemy.collider,
+        }];
+        step(&mut objs, &ground, 1.0 / 60.0);
+    }
+    (start, enemy.body.position)
+}
+
+#[test]
+fn static_enemy_keeps_its_position() {
+    let (start, end) = simulate(Enemy::new().with_behavior(EnemyBehavior::Static));
+    assert!(end.abs_diff_eq(start, 1e-4), "{start} -> {end}");
+}
+
+#[test]
+fn chasing_enemy_approaches_target() {
+    let (start, end) = simulate(Enemy::new());
+    assert!(end.length() < start.length() - 1.0, "{start} -> {end}");
+}
@@ -0,0 +1,89 @@
+// Автотест: очередь из трёх выстрелов выпускает ровно три пули с заданной
+// паузой, а следующая очередь ждёт интервала стрельбы
+// Запуск: cargo test --test enemy_burst
+
+use astroforge::config::{Difficulty, Level, Scenario};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::{Enemy, EnemyBehavior};
+use astroforge::world::{Entity, Tag};
+use glam::Vec3;
+
+const DT: f32 = 0.01;
+const DELAY: f32 = 0.1;
+
+/// Моменты выстрелов за `seconds` секунд при интервале `interval`.
+fn shot_times(enemy: &mut Enemy, interval: f32, seconds: f32) -> Vec<f32> {
+    let mut times = Vec::new();
+    for step in 0..(seconds / DT).round() as usize {
+        enemy.update(DT);
+        if enemy.trigger(interval) {
+            times.push(step as f32 * DT);
+        }
+    }
+    times
+}
+
+fn assert_spaced(times: &[f32]) {
+    for pair in times.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!((gap - DELAY).abs() <= DT + 1e-4, "gap {gap} in {times:?}");
+    }
+}
+
+#[test]
+fn burst_fires_its_shots_then_waits_for_the_interval() {
+    let mut enemy = Enemy::new().with_burst(3, DELAY);
+    enemy.bullet_timer = 0.0;
+    let times = shot_times(&mut enemy, 2.0, 1.5);
+    assert_eq!(times.len(), 3, "{times:?}");
+    assert_spaced(&times);
+
+    let next = shot_times(&mut enemy, 2.0, 1.0);
+    assert_eq!(next.len(), 3, "second burst: {next:?}");
+    assert_spaced(&next);
+}
+
+#[test]
+fn single_shot_is_the_default() {
+    let mut enemy = Enemy::new();
+    assert_eq!(enemy.burst, 1);
+    enemy.bullet_timer = 0.0;
+    assert_eq!(shot_times(&mut enemy, 2.0, 1.5).len(), 1);
+    assert!(Difficulty::Hard.enemy_burst() > Difficulty::Easy.enemy_burst());
+    assert_eq!(Difficulty::Easy.enemy_burst(), 1);
+}
+
+#[test]
+fn enemy_burst_spawns_three_spaced_bullets() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions {
+        scenario: Scenario {
+            level: Level::Arena,
+            ..Scenario::default()
+        },
+        ..GameOptions::default()
+    });
+    // Далеко от игрока: за секунду пули до него не долетают
+    let mut enemy = Enemy::new()
+        .with_position(game.player.position + Vec3::new(0.0, -0.25, -15.0))
+        .with_behavior(EnemyBehavior::Static)
+        .with_burst(3, DELAY);
+    enemy.bullet_timer = 0.0;
+    game.enemy = Some((game.world.spawn(Entity::new(Tag::Enemy)), enemy));
+
+    let mut times = Vec::new();
+    let mut count = 0;
+    for step in 0..100 {
+        game.update(&mut engine, DT);
+        let now = game.world.with_tag(Tag::Bullet).count();
+        if now > count {
+            assert_eq!(now, count + 1, "two bullets in one frame");
+            times.push(step as f32 * DT);
+        }
+        count = now;
+    }
+    assert_eq!(times.len(), 3, "{times:?}");
+    assert_spaced(&times);
+}
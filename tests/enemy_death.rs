@@ -0,0 +1,60 @@
+// Автотест: убитый враг разлетается на обломки, которые исчезают со временем
+// Запуск: cargo test --test enemy_death
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::{Enemy, EnemyBehavior};
+use astroforge::weapon::Hitscan;
+use astroforge::world::{Entity, Tag};
+use glam::Vec3;
+
+fn debris_count(game: &Game) -> usize {
+    game.world.with_tag(Tag::Debris).count()
+}
+
+#[test]
+fn killed_enemy_scatters_into_fading_debris() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.player.body.position = Vec3::new(0.0, 1.5, 2.0);
+    game.player.position = game.player.body.position;
+    let mut enemy = Enemy::new().with_behavior(EnemyBehavior::Static);
+    enemy.body.position = Vec3::new(0.0, 1.5, -6.0);
+    enemy.health = 10;
+    let id = game.world.spawn(Entity::new(Tag::Enemy));
+    game.enemy = Some((id, enemy));
+
+    assert!(game.fire_hitscan(Hitscan::new()));
+    assert!(game.enemy.is_none());
+    assert!(game.world.get(id).is_none());
+    let spawned = debris_count(&game);
+    assert!(spawned >= 4, "debris pieces: {spawned}");
+    let heights: Vec<f32> = game
+        .world
+        .with_tag(Tag::Debris)
+        .map(|(_, e)| e.body.unwrap().velocity.y)
+        .collect();
+    assert!(
+        heights.iter().all(|&vy| vy > 0.0),
+        "pieces fly up: {heights:?}"
+    );
+
+    // Обломки тускнеют, а через полторы секунды пропадают совсем
+    let brightest = |game: &Game| {
+        let mut cubes = Vec::new();
+        game.world.append_cubes(&mut cubes);
+        cubes.iter().map(|c| c.color[0]).fold(0.0, f32::max)
+    };
+    let fresh = brightest(&game);
+    for _ in 0..45 {
+        game.update(&mut engine, 1.0 / 60.0);
+    }
+    assert_eq!(debris_count(&game), spawned);
+    let faded = brightest(&game);
+    assert!(faded < fresh * 0.8, "color {fresh} -> {faded}");
+    for _ in 0..50 {
+        game.update(&mut engine, 1.0 / 60.0);
+    }
+    assert_eq!(debris_count(&game), 0);
+}
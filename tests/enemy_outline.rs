@@ -0,0 +1,71 @@
+// Автотест: у куба с флагом outline вокруг силуэта рисуется контур
+// Запуск: cargo test --test enemy_outline
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 400;
+
+fn pixel(frame: &[u8], x: u32, y: u32) -> [u8; 3] {
+    let i = ((y * SIZE + x) * 4) as usize;
+    [frame[i], frame[i + 1], frame[i + 2]]
+}
+
+fn is_outline(p: [u8; 3]) -> bool {
+    p[0] > 200 && p[1] < 50 && p[2] > 200
+}
+
+fn is_body(p: [u8; 3]) -> bool {
+    p[0] > 200 && p[1] < 50 && p[2] < 50
+}
+
+fn render(cube: CubeInstance) -> Vec<u8> {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    engine.renderer.outline_color = [1.0, 0.0, 1.0];
+    engine.renderer.outline_width = 0.1;
+    // Куб висит над сценой, камера смотрит на него сбоку. Кадр крупный,
+    // чтобы текст здоровья в углу не задевал куб
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    engine.renderer.render(None, 100, &[cube]);
+    engine.renderer.get_frame_rgba8()
+}
+
+/// Первый пиксель за краем куба, если идти от центра кадра вдоль `(dx, dy)`.
+fn edge(frame: &[u8], dx: i32, dy: i32) -> [u8; 3] {
+    let (mut x, mut y) = (SIZE as i32 / 2, SIZE as i32 / 2);
+    assert!(
+        is_body(pixel(frame, x as u32, y as u32)),
+        "center is not the cube"
+    );
+    while is_body(pixel(frame, x as u32, y as u32)) {
+        x += dx;
+        y += dy;
+    }
+    pixel(frame, x as u32, y as u32)
+}
+
+#[test]
+fn outlined_cube_has_a_ring_around_it() {
+    let cube = CubeInstance::new(Vec3::new(0.0, 5.0, 0.0), 1.0, [1.0, 0.0, 0.0]);
+    let frame = render(cube.with_outline());
+    for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let p = edge(&frame, dx, dy);
+        assert!(is_outline(p), "no outline in direction {dx},{dy}: {p:?}");
+    }
+}
+
+#[test]
+fn plain_cube_has_no_outline() {
+    let cube = CubeInstance::new(Vec3::new(0.0, 5.0, 0.0), 1.0, [1.0, 0.0, 0.0]);
+    let frame = render(cube);
+    let outlined = frame
+        .chunks(4)
+        .filter(|p| is_outline([p[0], p[1], p[2]]))
+        .count();
+    assert_eq!(outlined, 0);
+    assert!(!is_outline(edge(&frame, 1, 0)));
+}
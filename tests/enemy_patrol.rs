@@ -0,0 +1,77 @@
+// Автотест: враг без цели в зоне агрессии идёт к первой точке патруля, а
+// дойдя до неё, переключается на следующую
+// Запуск: cargo test --test enemy_patrol
+
+use astroforge::engine::physics::{Aabb, PhysicsObject, PhysicsWorld};
+use astroforge::player::Enemy;
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn floor() -> [Aabb; 1] {
+    [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )]
+}
+
+fn walk(enemy: &mut Enemy, target: Vec3, frames: usize) {
+    let world = PhysicsWorld::new();
+    for _ in 0..frames {
+        enemy.steer(target);
+        let mut objs = vec![PhysicsObject {
+            body: &mut enemy.body,
+            collider: enemy.collider,
+        }];
+        world.step(&mut objs, &floor(), DT);
+    }
+}
+
+/// Идти, пока враг не сменит точку патруля. Возвращает число кадров.
+fn frames_until_next_waypoint(enemy: &mut Enemy, target: Vec3) -> Option<usize> {
+    let current = enemy.current_waypoint();
+    (1..=2000).find(|_| {
+        walk(enemy, target, 1);
+        enemy.current_waypoint() != current
+    })
+}
+
+#[test]
+fn patrol_advances_and_cycles_through_waypoints() {
+    let start = Enemy::new().body.position;
+    let first = start + Vec3::new(2.0, 0.0, 0.0);
+    let second = start + Vec3::new(2.0, 0.0, 2.0);
+    let mut enemy = Enemy::new()
+        .with_aggro_range(5.0)
+        .with_patrol(vec![first, second]);
+    // Игрок далеко, враг его не замечает
+    let player = start + Vec3::new(-30.0, 0.0, 0.0);
+    assert_eq!(enemy.current_waypoint(), Some(first));
+
+    walk(&mut enemy, player, 60);
+    let d = (enemy.body.position.x - first.x).hypot(enemy.body.position.z - first.z);
+    assert!(d < 2.0, "did not move toward the first waypoint: {d}");
+
+    assert!(frames_until_next_waypoint(&mut enemy, player).is_some());
+    assert_eq!(enemy.current_waypoint(), Some(second));
+    let d = (enemy.body.position.x - first.x).hypot(enemy.body.position.z - first.z);
+    assert!(d <= 0.5, "switched before reaching the first: {d}");
+    assert!(frames_until_next_waypoint(&mut enemy, player).is_some());
+    assert_eq!(enemy.current_waypoint(), Some(first), "did not loop back");
+}
+
+#[test]
+fn player_in_range_breaks_the_patrol() {
+    let start = Enemy::new().body.position;
+    let waypoint = start + Vec3::new(3.0, 0.0, 0.0);
+    let mut enemy = Enemy::new()
+        .with_aggro_range(5.0)
+        .with_patrol(vec![waypoint]);
+    // Игрок рядом с противоположной стороны от точки патруля
+    let player = start + Vec3::new(-4.0, 0.0, 0.0);
+    walk(&mut enemy, player, 120);
+    assert!(
+        enemy.body.position.x < start.x,
+        "kept patrolling instead of chasing"
+    );
+}
@@ -0,0 +1,85 @@
+// Автотест: враги рядом друг с другом получают силы, расталкивающие их в
+// разные стороны, и погоня группой не сбивает их в кучу
+// Запуск: cargo test --test enemy_separation
+
+use astroforge::engine::physics::{step, Aabb, PhysicsObject};
+use astroforge::player::{separation, Enemy, EnemyBehavior};
+use glam::Vec3;
+
+fn enemy_at(position: Vec3) -> Enemy {
+    let mut enemy = Enemy::new();
+    enemy.body.position = position;
+    enemy
+}
+
+#[test]
+fn nearby_enemies_are_pushed_apart() {
+    let mut a = enemy_at(Vec3::new(-0.5, 0.75, 0.0));
+    let mut b = enemy_at(Vec3::new(0.5, 0.75, 0.0));
+    let (pa, pb) = (a.body.position, b.body.position);
+    a.separate(&[pa, pb]);
+    b.separate(&[pa, pb]);
+    assert!(a.body.force.x < 0.0, "{}", a.body.force);
+    assert!(b.body.force.x > 0.0, "{}", b.body.force);
+    assert_eq!(a.body.force, -b.body.force);
+    assert_eq!(a.body.force.y, 0.0);
+}
+
+#[test]
+fn closer_neighbors_push_harder_and_far_ones_not_at_all() {
+    let here = Vec3::ZERO;
+    let near = separation(here, &[Vec3::new(0.5, 0.0, 0.0)], 3.0);
+    let mid = separation(here, &[Vec3::new(2.0, 0.0, 0.0)], 3.0);
+    assert!(near.x < mid.x && mid.x < 0.0, "{near} {mid}");
+    assert_eq!(
+        separation(here, &[Vec3::new(5.0, 0.0, 0.0)], 3.0),
+        Vec3::ZERO
+    );
+    assert_eq!(separation(here, &[here], 3.0), Vec3::ZERO);
+
+    let mut still = enemy_at(here).with_behavior(EnemyBehavior::Static);
+    still.separate(&[Vec3::new(0.5, 0.0, 0.0)]);
+    assert_eq!(still.body.force, Vec3::ZERO);
+}
+
+/// Два врага бегут к цели из соседних точек. Возвращает расстояние между
+/// ними по горизонтали в конце погони.
+fn chase_spacing(separate: bool) -> f32 {
+    let target = Vec3::new(0.0, 0.75, 0.0);
+    let mut enemies = [-0.6, 0.6].map(|x| enemy_at(Vec3::new(x, 0.75, 10.0)));
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )];
+    for _ in 0..1200 {
+        let positions = enemies.each_ref().map(|e| e.body.position);
+        for e in &mut enemies {
+            if separate {
+                e.separate(&positions);
+            }
+            e.steer(target);
+        }
+        let [a, b] = &mut enemies;
+        let mut objs = [
+            PhysicsObject {
+                body: &mut a.body,
+                collider: a.collider,
+            },
+            PhysicsObject {
+                body: &mut b.body,
+                collider: b.collider,
+            },
+        ];
+        step(&mut objs, &floor, 1.0 / 60.0);
+    }
+    let offset = enemies[0].body.position - enemies[1].body.position;
+    (offset.x * offset.x + offset.z * offset.z).sqrt()
+}
+
+#[test]
+fn a_chasing_group_keeps_its_spacing() {
+    let clumped = chase_spacing(false);
+    let spread = chase_spacing(true);
+    assert!(clumped < 1.1, "{clumped}");
+    assert!(spread > 1.5, "{spread}");
+}
@@ -0,0 +1,61 @@
+// Автотест: выстрелы врага расходятся в пределах заданного конуса вокруг
+// направления на игрока, а один и тот же seed повторяет тот же разброс
+// Запуск: cargo test --test enemy_spread
+
+use astroforge::config::Difficulty;
+use astroforge::player::Enemy;
+use glam::Vec3;
+
+const FROM: Vec3 = Vec3::new(8.0, 1.35, -8.0);
+const TARGET: Vec3 = Vec3::new(0.0, 1.0, 2.0);
+
+fn shots(enemy: &mut Enemy, count: usize) -> Vec<Vec3> {
+    (0..count).map(|_| enemy.aim(FROM, TARGET)).collect()
+}
+
+#[test]
+fn shots_scatter_within_the_cone() {
+    let spread = 0.1;
+    let mut enemy = Enemy::new().with_spread(spread);
+    let exact = (TARGET - FROM).normalize();
+    let angles: Vec<f32> = shots(&mut enemy, 500)
+        .iter()
+        .map(|d| {
+            assert!((d.length() - 1.0).abs() < 1e-4, "{d}");
+            d.angle_between(exact)
+        })
+        .collect();
+    let widest = angles.iter().cloned().fold(0.0, f32::max);
+    assert!(widest <= spread + 1e-3, "shot outside the cone: {widest}");
+    // Разброс заполняет конус, а не жмётся к оси
+    assert!(widest > spread * 0.8, "{widest}");
+    assert!(angles.iter().filter(|&&a| a > 1e-3).count() > 490);
+}
+
+#[test]
+fn zero_spread_fires_straight_at_the_target() {
+    let mut enemy = Enemy::new();
+    assert_eq!(enemy.spread, 0.0);
+    let exact = (TARGET - FROM).normalize();
+    for d in shots(&mut enemy, 10) {
+        assert!(d.angle_between(exact) < 1e-4, "{d}");
+    }
+}
+
+#[test]
+fn same_seed_repeats_the_same_shots() {
+    let mut a = Enemy::new().with_spread(0.1).with_seed(7);
+    let mut b = Enemy::new().with_spread(0.1).with_seed(7);
+    let mut c = Enemy::new().with_spread(0.1).with_seed(8);
+    let first = shots(&mut a, 20);
+    assert_eq!(first, shots(&mut b, 20));
+    assert_ne!(first, shots(&mut c, 20));
+}
+
+#[test]
+fn harder_enemies_aim_tighter() {
+    let easy = Difficulty::Easy.enemy_spread();
+    let normal = Difficulty::Normal.enemy_spread();
+    let hard = Difficulty::Hard.enemy_spread();
+    assert!(easy > normal && normal > hard && hard > 0.0);
+}
@@ -1,9 +1,8 @@
 // Автотест: проверяет, мигает ли враг (визуальная стабильность)
 // Запуск: cargo test --test enemy_visual_stability
 
-use astroforge::engine::renderer::CubeInstance;
 use astroforge::engine::Engine;
-use astroforge::player::Enemy;
+use astroforge::player::{Enemy, EnemyBehavior};
 
 #[test]
 fn enemy_visual_stability() {
@@ -12,7 +11,8 @@ fn enemy_visual_stability() {
     let height = 768u32;
     // Инициализация движка и врага
     let mut engine = Engine::new_headless(width, height); // Требуется headless-режим
-    let mut enemy = Enemy::new();
+    // Враг стоит на месте, чтобы сравнение кадров не зависело от ИИ
+    let mut enemy = Enemy::new().with_behavior(EnemyBehavior::Static);
     let mut cubes = Vec::new();
     // Первый кадр
     cubes.clear();
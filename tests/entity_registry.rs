@@ -0,0 +1,68 @@
+// Автотест: реестр сущностей — вставка/удаление и обход по компонентам
+// Запуск: cargo test --test entity_registry
+
+use astroforge::engine::ecs::Registry;
+use astroforge::engine::physics::{Collider, RigidBody};
+use astroforge::world::{Entity, Renderable, Tag, World};
+use glam::Vec3;
+
+fn bullet(x: f32) -> Entity {
+    Entity::new(Tag::Bullet)
+        .with_body(RigidBody::new(0.05, Vec3::new(x, 1.0, 0.0)))
+        .with_collider(Collider::new(Vec3::splat(0.1)))
+        .with_renderable(Renderable {
+            size: 0.1,
+            color: [1.0, 1.0, 0.0],
+            tracer: 0.0,
+        })
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut reg = Registry::new();
+    let a = reg.insert("a");
+    let b = reg.insert("b");
+    assert_eq!(reg.len(), 2);
+    assert_eq!(reg.get(a), Some(&"a"));
+
+    assert_eq!(reg.remove(a), Some("a"));
+    assert_eq!(reg.remove(a), None);
+    assert!(!reg.contains(a));
+    assert_eq!(reg.len(), 1);
+
+    // Слот переиспользуется, но старый id на новую сущность не указывает
+    let c = reg.insert("c");
+    assert_ne!(a, c);
+    assert_eq!(reg.get(a), None);
+    assert_eq!(reg.get(c), Some(&"c"));
+    assert_eq!(reg.get(b), Some(&"b"));
+    assert_eq!(
+        reg.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+        vec!["c", "b"]
+    );
+}
+
+#[test]
+fn iterate_entities_with_component() {
+    let mut world = World::new();
+    let player = world.spawn(Entity::new(Tag::Player));
+    let b1 = world.spawn(bullet(1.0));
+    let b2 = world.spawn(bullet(2.0));
+
+    let physical: Vec<_> = world.with_physics().map(|(id, _)| id).collect();
+    assert_eq!(physical, vec![b1, b2]);
+    assert_eq!(world.with_tag(Tag::Player).count(), 1);
+    assert_eq!(world.tag(player), Some(Tag::Player));
+
+    let mut cubes = Vec::new();
+    world.append_cubes(&mut cubes);
+    assert_eq!(cubes.len(), 2);
+    assert_eq!(cubes[1].position, Vec3::new(2.0, 1.0, 0.0));
+
+    world.despawn(b1);
+    let mut ids = Vec::new();
+    let mut objs = Vec::new();
+    world.physics_objects(&mut ids, &mut objs);
+    assert_eq!(ids, vec![b2]);
+    assert_eq!(objs.len(), 1);
+}
@@ -0,0 +1,103 @@
+// Автотест: взрыв одной бочки подрывает соседнюю, а дальняя уцелевает;
+// взрыв отталкивает и ранит игрока рядом; бочки арены подрываются из
+// винтовки
+// Запуск: cargo test --test explosive_barrels
+
+use astroforge::config::{Level, Scenario};
+use astroforge::engine::input::InputState;
+use astroforge::engine::physics::RigidBody;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::weapon::Hitscan;
+use astroforge::world::{Entity, Explosive, Tag, World};
+use glam::{Quat, Vec3};
+
+const BLAST: Explosive = Explosive {
+    radius: 3.0,
+    impulse: 500.0,
+    damage: 50,
+};
+
+fn barrel(world: &mut World, x: f32) -> astroforge::engine::ecs::EntityId {
+    world.spawn(
+        Entity::new(Tag::Barrel)
+            .with_body(RigidBody::new(30.0, Vec3::new(x, 0.5, 0.0)))
+            .with_health(30)
+            .with_explosive(BLAST),
+    )
+}
+
+#[test]
+fn blast_sets_off_a_neighbour_but_not_a_far_barrel() {
+    let mut world = World::new();
+    let first = barrel(&mut world, 0.0);
+    let second = barrel(&mut world, 1.0);
+    let far = barrel(&mut world, 10.0);
+    assert!(world.detonate().is_empty(), "nothing is destroyed yet");
+
+    world.damage(first, 30);
+    let blasts = world.detonate();
+    assert_eq!(blasts.len(), 2);
+    assert_eq!(blasts[0].explosion.center.x, 0.0);
+    assert_eq!(blasts[1].explosion.center.x, 1.0);
+    assert!(world.get(first).is_none());
+    assert!(world.get(second).is_none());
+    let survivor = world.get(far).expect("far barrel survived");
+    assert_eq!(survivor.health, Some(30));
+    assert_eq!(survivor.body.unwrap().velocity, Vec3::ZERO);
+}
+
+#[test]
+fn barrel_blast_hurts_and_pushes_the_player() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(200, 150);
+    let mut game = Game::new(GameOptions::default());
+    // Ждём конца защиты после появления
+    game.player.update(&InputState::default(), 10.0);
+    let position = game.player.body.position + Vec3::new(1.5, 0.0, 0.0);
+    let id = game.spawn_barrel(position);
+    game.world.damage(id, 1000);
+
+    game.update(&mut engine, 1.0 / 60.0);
+    assert!(game.world.get(id).is_none(), "barrel did not explode");
+    assert!(game.health < 100, "health: {}", game.health);
+    assert!(
+        game.player.body.velocity.x < 0.0,
+        "player was not thrown away"
+    );
+}
+
+#[test]
+fn rifle_shots_set_off_the_arena_barrel_cluster() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions {
+        scenario: Scenario {
+            level: Level::Arena,
+            ..Scenario::default()
+        },
+        ..GameOptions::default()
+    });
+    let barrels = Level::Arena.barrels();
+    assert_eq!(game.world.with_tag(Tag::Barrel).count(), barrels.len());
+    // Целимся в первую бочку скопления
+    let aim = (barrels[0] - game.player.position).normalize();
+    game.player.rotation = Quat::from_rotation_arc(-Vec3::Z, aim);
+
+    // Винтовке нужно два попадания, чтобы разбить бочку
+    assert!(game.fire_hitscan(Hitscan::new()));
+    game.update(&mut engine, 1.0 / 60.0);
+    assert_eq!(game.world.with_tag(Tag::Barrel).count(), barrels.len());
+    game.player.rotation = Quat::from_rotation_arc(-Vec3::Z, aim);
+    assert!(game.fire_hitscan(Hitscan::new()));
+    game.update(&mut engine, 1.0 / 60.0);
+
+    // Скопление взорвалось цепочкой, отдельная бочка уцелела
+    let left: Vec<_> = game
+        .world
+        .with_tag(Tag::Barrel)
+        .map(|(_, e)| e.body.unwrap().position)
+        .collect();
+    assert_eq!(left.len(), 1, "{left:?}");
+    assert!(left[0].distance(barrels[3]) < 0.5);
+}
@@ -0,0 +1,51 @@
+// Автотест: шейдер пола рисует сетку с линиями через равные промежутки
+// Запуск: cargo test --test floor_grid
+
+use astroforge::engine::renderer::GridStyle;
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 200;
+
+#[test]
+fn grid_lines_are_periodic() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    // Ортографическая камера сверху: 20 единиц мира на 200 пикселей
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 20.0, 0.0), Vec3::ZERO, Vec3::NEG_Z);
+    let proj = Mat4::orthographic_rh(-10.0, 10.0, -10.0, 10.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    engine.renderer.set_grid(GridStyle {
+        spacing: 2.0,
+        line_color: [1.0, 0.0, 0.0],
+        base_color: [0.0, 0.0, 0.3],
+    });
+    engine.renderer.render(None, 100, &[]);
+    let frame = engine.renderer.get_frame_rgba8();
+
+    // Строка внизу кадра между горизонтальными линиями, подальше от текста
+    // и куба в центре
+    let row = 185;
+    let is_line = |x: u32| {
+        let i = ((row * SIZE + x) * 4) as usize;
+        frame[i] > 128 && frame[i + 2] < 128
+    };
+    let mut centers = Vec::new();
+    let mut x = 0;
+    while x < SIZE {
+        if is_line(x) {
+            let start = x;
+            while x < SIZE && is_line(x) {
+                x += 1;
+            }
+            centers.push((start + x - 1) as f32 / 2.0);
+        }
+        x += 1;
+    }
+    // Линия каждые 2 единицы = каждые 20 пикселей
+    assert!(centers.len() >= 8, "too few grid lines: {centers:?}");
+    for pair in centers.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!((gap - 20.0).abs() <= 1.5, "uneven grid: {centers:?}");
+    }
+}
@@ -0,0 +1,46 @@
+// Автотест: покадровый дамп по сценарию ввода пишет по PNG на каждый кадр
+// Запуск: cargo test --test frame_dump
+
+use astroforge::engine::input::parse_script;
+use astroforge::engine::Engine;
+use astroforge::framedump::dump_frames;
+use astroforge::game::{Game, GameOptions};
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn five_scripted_frames_write_five_images() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let script = parse_script("W\nW D\nW D mouse=4,0\n\nS\n").unwrap();
+    assert_eq!(script.len(), 5);
+
+    let dir = std::env::temp_dir().join(format!("astroforge_frames_{}", std::process::id()));
+    let mut engine = Engine::new_headless(160, 120);
+    let mut game = Game::new(GameOptions::default());
+    let start = game.player.body.position;
+    let paths = dump_frames(&mut game, &mut engine, &script, &dir).unwrap();
+
+    assert_eq!(paths.len(), 5);
+    for (i, path) in paths.iter().enumerate() {
+        assert_eq!(path, &dir.join(format!("frame_{:04}.png", i + 1)));
+        let img = image::open(path).expect("valid PNG");
+        assert_eq!((img.width(), img.height()), (160, 120));
+    }
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(files, 5);
+    // Сценарий действительно управлял игроком
+    assert_ne!(game.player.body.position.z, start.z);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn script_parsing() {
+    let script = parse_script("W Space # прыжок\nmouse=-1.5,2").unwrap();
+    assert_eq!(
+        script[0].keys,
+        vec![VirtualKeyCode::W, VirtualKeyCode::Space]
+    );
+    assert_eq!(script[1].keys, vec![]);
+    assert_eq!(script[1].mouse_delta, (-1.5, 2.0));
+    assert!(parse_script("Q").is_err());
+    assert!(parse_script("mouse=1").is_err());
+}
@@ -0,0 +1,31 @@
+// Автотест: ограничение кадров досыпает остаток кадра, медленный кадр не ждёт
+// Запуск: cargo test --test frame_pacing
+
+use astroforge::engine::timer::pacing_sleep;
+use std::time::Duration;
+
+#[test]
+fn fast_frame_sleeps_the_rest_of_the_budget() {
+    let sleep = pacing_sleep(Duration::from_millis(4), 60.0);
+    assert!(sleep > Duration::ZERO);
+    let budget = Duration::from_secs_f32(1.0 / 60.0);
+    assert_eq!(sleep + Duration::from_millis(4), budget);
+}
+
+#[test]
+fn slow_frame_does_not_sleep() {
+    assert_eq!(
+        pacing_sleep(Duration::from_millis(20), 60.0),
+        Duration::ZERO
+    );
+    assert_eq!(
+        pacing_sleep(Duration::from_millis(8), 144.0),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn higher_cap_sleeps_less() {
+    let frame = Duration::from_millis(2);
+    assert!(pacing_sleep(frame, 144.0) < pacing_sleep(frame, 60.0));
+}
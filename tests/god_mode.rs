@@ -0,0 +1,89 @@
+// Автотест: в режиме бога пули и падения не снимают здоровье, а без него
+// снимают как обычно; F6 переключает режим
+// Запуск: cargo test --test god_mode
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions, MAX_HEALTH};
+use astroforge::world::{Entity, Hit, Tag};
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn game(god_mode: bool) -> Game {
+    let mut game = Game::new(GameOptions::default());
+    game.god_mode = god_mode;
+    // Ждём конца защиты после появления
+    game.player.update(&InputState::default(), 10.0);
+    game
+}
+
+fn shoot(game: &mut Game) {
+    let bullet = game.world.spawn(Entity::new(Tag::Bullet));
+    game.apply_hit(Hit {
+        bullet,
+        target: game.player_id,
+        damage: 30,
+        impulse: Vec3::new(-1.0, 0.0, 0.0),
+    });
+}
+
+/// Бросает игрока вниз вдали от кольца и ждёт приземления.
+fn fall(game: &mut Game, engine: &mut Engine) {
+    game.player.body.position = Vec3::new(10.0, 1.5, 10.0);
+    game.player.body.velocity = Vec3::new(0.0, -30.0, 0.0);
+    for _ in 0..60 {
+        game.update(engine, DT);
+    }
+    assert!(game.player.body.on_ground, "{}", game.player.body.position);
+}
+
+#[test]
+fn bullets_and_falls_spare_a_god() {
+    let mut god = game(true);
+    shoot(&mut god);
+    assert_eq!(god.health, MAX_HEALTH);
+    // Отбрасывание остаётся, меняется только урон
+    assert!(god.player.body.velocity.x < 0.0);
+
+    let mut mortal = game(false);
+    shoot(&mut mortal);
+    assert_eq!(mortal.health, MAX_HEALTH - 30);
+}
+
+#[test]
+fn falls_hurt_only_without_god_mode() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut god = game(true);
+    fall(&mut god, &mut engine);
+    assert_eq!(god.health, MAX_HEALTH);
+    assert!(!god.game_over);
+
+    let mut mortal = game(false);
+    fall(&mut mortal, &mut engine);
+    assert!(mortal.health < MAX_HEALTH, "{}", mortal.health);
+
+    // Падение бьёт тем же уроном, что и пули, поэтому защиту после
+    // появления тоже уважает
+    let mut fresh = Game::new(GameOptions::default());
+    assert!(fresh.player.invulnerable());
+    fall(&mut fresh, &mut engine);
+    assert_eq!(fresh.health, MAX_HEALTH);
+}
+
+#[test]
+fn f6_toggles_god_mode() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = game(false);
+    engine
+        .input
+        .apply_frame(&InputFrame::holding(&[VirtualKeyCode::F6]));
+    game.update(&mut engine, DT);
+    assert!(game.god_mode);
+    engine.input.reset();
+    game.update(&mut engine, DT);
+    assert!(game.god_mode, "held key toggled again");
+}
@@ -0,0 +1,49 @@
+// Автотест: профилировщик GPU на адаптере без меток времени сообщает, что
+// замер недоступен, а кадры с включённым замером рисуются без паники
+// Запуск: cargo test --test gpu_profiler
+
+use astroforge::engine::input::InputFrame;
+use astroforge::engine::profiler::GpuProfiler;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn an_explicitly_unavailable_profiler_says_so() {
+    let profiler = GpuProfiler::unavailable();
+    assert!(!profiler.available());
+    assert_eq!(profiler.last(), None);
+    assert_eq!(profiler.report(), "gpu: unavailable");
+}
+
+#[test]
+fn profiling_without_timestamp_support_falls_back() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(200, 150);
+    let supported = engine
+        .renderer
+        .device
+        .features()
+        .contains(wgpu::Features::TIMESTAMP_QUERY);
+    assert_eq!(engine.renderer.gpu_profiler.available(), supported);
+
+    // F3 показывает отладочный вывод и включает замер
+    let mut game = Game::new(GameOptions::default());
+    engine
+        .input
+        .apply_frame(&InputFrame::holding(&[VirtualKeyCode::F3]));
+    game.update(&mut engine, 1.0 / 60.0);
+    engine.input.reset();
+    for _ in 0..3 {
+        game.render(&mut engine);
+    }
+    assert!(engine.renderer.profile_gpu);
+    let text = engine.renderer.hud_debug.clone().expect("debug text");
+    if supported {
+        assert!(engine.renderer.gpu_profiler.last().is_some());
+        assert!(text.contains("gpu: ") && text.contains(" ms"), "{text}");
+    } else {
+        assert_eq!(engine.renderer.gpu_profiler.last(), None);
+        assert!(text.contains("gpu: unavailable"), "{text}");
+    }
+}
@@ -0,0 +1,82 @@
+// Автотест: тело в зоне с гравитацией 0.2 падает медленнее, чем снаружи, а
+// в зоне с перевёрнутой гравитацией поднимается
+// Запуск: cargo test --test gravity_zones
+
+use astroforge::engine::physics::{
+    Aabb, Collider, GravityZone, PhysicsObject, PhysicsWorld, RigidBody, GRAVITY,
+};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn fall(world: &PhysicsWorld, start: Vec3, frames: usize) -> RigidBody {
+    let mut body = RigidBody::new(2.0, start);
+    let mut objs = vec![PhysicsObject {
+        body: &mut body,
+        collider: Collider::new(Vec3::splat(0.5)),
+    }];
+    for _ in 0..frames {
+        world.step(&mut objs, &[], DT);
+    }
+    body
+}
+
+#[test]
+fn low_gravity_zone_slows_the_fall() {
+    let mut world = PhysicsWorld::new();
+    world.add_gravity_zone(GravityZone::scaled(
+        Aabb::new(Vec3::new(0.0, 10.0, 0.0), Vec3::splat(5.0)),
+        0.2,
+    ));
+    let inside = fall(&world, Vec3::new(0.0, 12.0, 0.0), 30);
+    let outside = fall(&world, Vec3::new(20.0, 12.0, 0.0), 30);
+
+    assert!(inside.velocity.y < 0.0, "still falls inside");
+    assert!(
+        inside.velocity.y > outside.velocity.y * 0.3,
+        "{} vs {}",
+        inside.velocity.y,
+        outside.velocity.y
+    );
+    assert!(inside.position.y > outside.position.y);
+    // Снаружи обычная гравитация
+    assert!((outside.velocity.y + GRAVITY * 0.5).abs() < 0.2);
+}
+
+#[test]
+fn flipped_zone_lifts_bodies_off_the_ground() {
+    let mut world = PhysicsWorld::new();
+    world.add_gravity_zone(GravityZone::scaled(
+        Aabb::new(Vec3::new(0.0, 2.0, 0.0), Vec3::splat(3.0)),
+        -1.0,
+    ));
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(10.0, 0.5, 10.0),
+    )];
+    let mut body = RigidBody::new(2.0, Vec3::new(0.0, 0.5, 0.0));
+    body.on_ground = true;
+    let mut objs = vec![PhysicsObject {
+        body: &mut body,
+        collider: Collider::new(Vec3::splat(0.5)),
+    }];
+    for _ in 0..20 {
+        world.step(&mut objs, &floor, DT);
+    }
+    assert!(body.velocity.y > 0.0);
+    assert!(body.position.y > 0.6, "{}", body.position.y);
+}
+
+#[test]
+fn last_overlapping_zone_wins() {
+    let mut world = PhysicsWorld::new();
+    let bounds = Aabb::new(Vec3::ZERO, Vec3::splat(2.0));
+    world.add_gravity_zone(GravityZone::scaled(bounds, 0.5));
+    world.add_gravity_zone(GravityZone::new(bounds, Vec3::new(1.0, 0.0, 0.0)));
+    let collider = Collider::new(Vec3::splat(0.5));
+    assert_eq!(world.gravity_at(Vec3::ZERO, &collider), Vec3::X);
+    assert_eq!(
+        world.gravity_at(Vec3::new(9.0, 0.0, 0.0), &collider),
+        Vec3::new(0.0, -GRAVITY, 0.0)
+    );
+}
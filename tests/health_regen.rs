@@ -0,0 +1,58 @@
+// Автотест: здоровье восстанавливается после паузы без урона, а новое
+// попадание запускает паузу заново
+// Запуск: cargo test --test health_regen
+
+use astroforge::engine::input::InputState;
+use astroforge::game::{Game, GameOptions, HealthRegen, MAX_HEALTH};
+
+const DT: f32 = 0.1;
+
+fn wounded_game() -> Game {
+    let mut game = Game::new(GameOptions::default());
+    game.health_regen = Some(HealthRegen {
+        delay: 2.0,
+        rate: 10.0,
+    });
+    // Защита после появления не даёт получить урон, ждём её конца
+    game.player.update(&InputState::default(), 10.0);
+    assert!(game.damage_player(50));
+    game
+}
+
+fn wait(game: &mut Game, seconds: f32) {
+    for _ in 0..(seconds / DT).round() as usize {
+        game.tick_health(DT);
+    }
+}
+
+#[test]
+fn health_regenerates_after_the_delay() {
+    let mut game = wounded_game();
+    wait(&mut game, 1.9);
+    assert_eq!(game.health, 50, "healing started before the delay");
+    // Через секунду после конца паузы прибавилось около rate
+    wait(&mut game, 1.1);
+    assert!((58..=61).contains(&game.health), "{}", game.health);
+    wait(&mut game, 10.0);
+    assert_eq!(game.health, MAX_HEALTH);
+}
+
+#[test]
+fn a_hit_restarts_the_delay() {
+    let mut game = wounded_game();
+    wait(&mut game, 1.5);
+    assert!(game.damage_player(10));
+    wait(&mut game, 1.5);
+    assert_eq!(game.health, 40, "the hit did not restart the delay");
+    wait(&mut game, 1.0);
+    assert!(game.health > 40);
+}
+
+#[test]
+fn regen_is_off_by_default() {
+    let mut game = Game::new(GameOptions::default());
+    game.player.update(&InputState::default(), 10.0);
+    game.damage_player(30);
+    wait(&mut game, 20.0);
+    assert_eq!(game.health, MAX_HEALTH - 30);
+}
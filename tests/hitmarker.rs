@@ -0,0 +1,112 @@
+// Автотест: попадание игрока по врагу показывает хитмаркер на заданное
+// время, а добивание — более долгий маркер убийства; чужие попадания
+// маркер не показывают
+// Запуск: cargo test --test hitmarker
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::hitmarker::{HitKind, Hitmarker, HIT_TIME, KILL_TIME};
+use astroforge::player::Enemy;
+use astroforge::world::{Entity, Hit, Projectile, Tag};
+use glam::{Vec2, Vec3};
+
+/// Игра с врагом заданного здоровья и попаданием по нему пули игрока
+/// или ничьей.
+fn game_with_enemy(health: i32, by_player: bool) -> (Game, Hit) {
+    let mut game = Game::new(GameOptions::default());
+    let enemy = game.world.spawn(Entity::new(Tag::Enemy));
+    game.enemy = Some((enemy, Enemy::new().with_health(health)));
+    let owner = if by_player {
+        Some(game.player_id)
+    } else {
+        None
+    };
+    let bullet = game
+        .world
+        .spawn(Entity::new(Tag::Bullet).with_projectile(Projectile::new(owner)));
+    let hit = Hit {
+        bullet,
+        target: enemy,
+        damage: 10,
+        impulse: Vec3::ZERO,
+    };
+    (game, hit)
+}
+
+#[test]
+fn player_hit_shows_marker_for_its_lifetime() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let (mut game, hit) = game_with_enemy(100, true);
+    assert!(game.hitmarker.is_none());
+
+    game.apply_hit(hit);
+    let marker = game.hitmarker.expect("no hitmarker");
+    assert_eq!(marker.kind, HitKind::Hit);
+    assert_eq!(marker.remaining(), HIT_TIME);
+
+    let dt = HIT_TIME / 4.0;
+    game.update(&mut engine, dt);
+    assert!(game.hitmarker.is_some_and(|m| m.remaining() < HIT_TIME));
+    for _ in 0..4 {
+        game.update(&mut engine, dt);
+    }
+    assert!(game.hitmarker.is_none(), "marker did not fade out");
+}
+
+#[test]
+fn lethal_hit_shows_longer_kill_marker() {
+    let (mut game, hit) = game_with_enemy(10, true);
+    game.apply_hit(hit);
+    let marker = game.hitmarker.expect("no hitmarker");
+    assert_eq!(marker.kind, HitKind::Kill);
+    assert_eq!(marker.remaining(), KILL_TIME);
+}
+
+#[test]
+fn hits_by_others_show_no_marker() {
+    let (mut game, hit) = game_with_enemy(100, false);
+    game.apply_hit(hit);
+    assert!(game.hitmarker.is_none());
+}
+
+#[test]
+fn target_hits_by_others_show_no_marker() {
+    let mut game = Game::new(GameOptions::default());
+    let target = game.spawn_target(Vec3::new(0.0, 1.0, -5.0));
+    let hit = |game: &mut Game, owner| {
+        let bullet = game
+            .world
+            .spawn(Entity::new(Tag::Bullet).with_projectile(Projectile::new(owner)));
+        game.apply_hit(Hit {
+            bullet,
+            target,
+            damage: 1,
+            impulse: Vec3::ZERO,
+        });
+    };
+    hit(&mut game, None);
+    assert!(game.hitmarker.is_none());
+    let player = Some(game.player_id);
+    hit(&mut game, player);
+    assert_eq!(game.hitmarker.unwrap().kind, HitKind::Hit);
+}
+
+#[test]
+fn plain_hit_does_not_replace_kill_marker() {
+    let (mut game, _) = game_with_enemy(100, true);
+    game.confirm_hit(HitKind::Kill);
+    game.confirm_hit(HitKind::Hit);
+    assert_eq!(game.hitmarker.unwrap().kind, HitKind::Kill);
+}
+
+#[test]
+fn marker_fades_and_draws_four_arms() {
+    let mut marker = Hitmarker::new(HitKind::Hit);
+    assert_eq!(marker.alpha(), 1.0);
+    let quads = marker.quads(Vec2::new(800.0, 600.0));
+    assert_eq!(quads.len(), 4);
+    assert!(marker.tick(HIT_TIME / 2.0));
+    assert!(marker.alpha() < 1.0 && marker.alpha() > 0.0);
+    assert!(!marker.tick(HIT_TIME));
+}
@@ -0,0 +1,59 @@
+// Автотест: выстрел хитскана по врагу на линии огня снимает здоровье, промах — нет
+// Запуск: cargo test --test hitscan
+
+use astroforge::engine::physics::{raycast, Aabb};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::{Enemy, EnemyBehavior};
+use astroforge::weapon::Hitscan;
+use astroforge::world::{Entity, Tag};
+use glam::Vec3;
+use winit::event::MouseButton;
+
+fn game_with_enemy_at(position: Vec3) -> Game {
+    let mut game = Game::new(GameOptions::default());
+    game.player.body.position = Vec3::new(0.0, 1.5, 2.0);
+    let mut enemy = Enemy::new().with_behavior(EnemyBehavior::Static);
+    enemy.body.position = position;
+    let id = game.world.spawn(Entity::new(Tag::Enemy));
+    game.enemy = Some((id, enemy));
+    game
+}
+
+fn click_and_update(game: &mut Game, engine: &mut Engine) {
+    engine.input.click(MouseButton::Left);
+    game.update(engine, 1.0 / 60.0);
+    engine.input.reset();
+}
+
+#[test]
+fn shot_at_enemy_in_line_deals_damage() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    // Игрок смотрит вдоль -Z
+    let mut game = game_with_enemy_at(Vec3::new(0.0, 1.5, -6.0));
+    click_and_update(&mut game, &mut engine);
+    let (_, enemy) = game.enemy.as_ref().unwrap();
+    assert_eq!(enemy.health, 100 - Hitscan::new().damage);
+    assert!(enemy.body.velocity.z < 0.0, "knocked back along the ray");
+}
+
+#[test]
+fn missed_shot_leaves_health_alone() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = game_with_enemy_at(Vec3::new(6.0, 1.5, -6.0));
+    click_and_update(&mut game, &mut engine);
+    assert_eq!(game.enemy.as_ref().unwrap().1.health, 100);
+    assert!(!game.fire_hitscan(Hitscan::new()));
+}
+
+#[test]
+fn obstacles_block_the_shot() {
+    let wall = Aabb::new(Vec3::new(0.0, 1.0, -3.0), Vec3::new(2.0, 2.0, 0.1));
+    let enemy = Aabb::new(Vec3::new(0.0, 1.0, -6.0), Vec3::splat(0.5));
+    let hit = raycast(Vec3::new(0.0, 1.0, 0.0), -Vec3::Z, 50.0, &[enemy, wall]).unwrap();
+    assert_eq!(hit.index, 1);
+    assert!((hit.distance - 2.9).abs() < 1e-5);
+    assert!(raycast(Vec3::new(0.0, 1.0, 0.0), Vec3::Z, 50.0, &[enemy, wall]).is_none());
+}
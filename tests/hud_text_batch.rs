@@ -0,0 +1,57 @@
+// Автотест: весь текст HUD ставится в очередь и рисуется за один проход
+// глифов, и на кадре видны все строки
+// Запуск: cargo test --test hud_text_batch
+
+use astroforge::engine::Engine;
+use glam::Mat4;
+
+const WIDTH: u32 = 400;
+const HEIGHT: u32 = 300;
+
+/// Светлые пиксели в полосе строк `top..bottom` у левого края.
+fn lit_rows(frame: &[u8], top: u32, bottom: u32) -> usize {
+    (top..bottom)
+        .flat_map(|y| (30..250).map(move |x| ((y * WIDTH + x) * 4) as usize))
+        .filter(|&i| frame[i..i + 3].iter().any(|&c| c > 60))
+        .count()
+}
+
+#[test]
+fn all_hud_text_is_drawn_in_one_pass() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(WIDTH, HEIGHT);
+    let renderer = &mut engine.renderer;
+    renderer.update_camera(&Mat4::ZERO);
+    renderer.hud_ammo = Some((7, 21));
+    renderer.hud_debug = Some("pos: 1.00 2.00 3.00".to_string());
+
+    let texts: Vec<String> = renderer
+        .hud_sections(Some("Beacon"), 80)
+        .iter()
+        .flat_map(|s| s.text.iter().map(|t| t.text.clone()))
+        .collect();
+    for expected in ["Beacon", "Health: ", "80", "Ammo: ", "7 / 21", "pos: 1.00"] {
+        assert!(
+            texts.iter().any(|t| t.contains(expected)),
+            "{expected} missing from {texts:?}"
+        );
+    }
+
+    renderer.render(Some("Beacon"), 80, &[]);
+    assert_eq!(renderer.text_draws(), 1);
+    let frame = renderer.get_frame_rgba8();
+    // Сообщение, здоровье, патроны и отладочная строка внизу
+    for (top, bottom) in [(30, 66), (72, 98), (107, 133), (250, 290)] {
+        assert!(
+            lit_rows(&frame, top, bottom) > 20,
+            "no text in rows {top}..{bottom}"
+        );
+    }
+
+    // Без HUD текста нет совсем
+    renderer.hud_visible = false;
+    renderer.render(Some("Beacon"), 80, &[]);
+    assert_eq!(renderer.text_draws(), 0);
+    let frame = renderer.get_frame_rgba8();
+    assert_eq!(lit_rows(&frame, 0, HEIGHT), 0);
+}
@@ -0,0 +1,39 @@
+// Автотест: тема HUD определяет цвета текстовых секций
+// Запуск: cargo test --test hud_theme
+
+use astroforge::engine::hud::HudTheme;
+use astroforge::engine::Engine;
+
+#[test]
+fn switching_theme_changes_text_colors() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(640, 480);
+    let renderer = &mut engine.renderer;
+
+    let overlay = renderer.overlay_sections("Маяк активирован");
+    let health = renderer.health_section(100);
+    assert_eq!(overlay[0].text[0].extra.color, HudTheme::default().text);
+    assert_eq!(health.text[1].extra.color, HudTheme::default().health_high);
+
+    let contrast = HudTheme::high_contrast();
+    renderer.hud_theme = contrast;
+    let overlay = renderer.overlay_sections("Маяк активирован");
+    let health = renderer.health_section(100);
+    assert!(overlay
+        .iter()
+        .all(|s| s.text[0].extra.color == contrast.text));
+    assert_eq!(health.text[0].extra.color, contrast.accent);
+    assert_eq!(health.text[1].extra.color, contrast.health_high);
+    assert_ne!(contrast.text, HudTheme::default().text);
+}
+
+#[test]
+fn health_color_blends_between_ends() {
+    let theme = HudTheme::default();
+    assert_eq!(theme.health_color(100), theme.health_high);
+    assert_eq!(theme.health_color(0), theme.health_low);
+    assert_eq!(theme.health_color(-20), theme.health_low);
+    let mid = theme.health_color(50);
+    assert!(mid[0] > 0.0 && mid[0] < 1.0);
+    assert!(mid[1] > 0.0 && mid[1] < 1.0);
+}
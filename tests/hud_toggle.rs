@@ -0,0 +1,40 @@
+// Автотест: со скрытым HUD в кадре нет текста, с показанным — есть
+// Запуск: cargo test --test hud_toggle
+
+use astroforge::engine::Engine;
+
+const W: u32 = 400;
+const H: u32 = 300;
+
+/// Сколько пикселей в области текста HUD не чёрные.
+fn lit_hud_pixels(frame: &[u8]) -> usize {
+    let mut lit = 0;
+    for y in 25..180 {
+        for x in 20..380 {
+            let i = ((y * W + x) * 4) as usize;
+            if frame[i..i + 3] != [0, 0, 0] {
+                lit += 1;
+            }
+        }
+    }
+    lit
+}
+
+#[test]
+fn hidden_hud_draws_no_text() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(W, H);
+    let renderer = &mut engine.renderer;
+    // Нулевая матрица камеры схлопывает геометрию: в кадре только фон и текст
+    renderer.update_camera(&glam::Mat4::ZERO);
+    renderer.hud_ammo = Some((8, 32));
+
+    renderer.render(Some("Маяк активирован"), 100, &[]);
+    let shown = lit_hud_pixels(&renderer.get_frame_rgba8());
+    renderer.hud_visible = false;
+    renderer.render(Some("Маяк активирован"), 100, &[]);
+    let hidden = lit_hud_pixels(&renderer.get_frame_rgba8());
+
+    assert!(shown > 500, "text pixels with HUD shown: {shown}");
+    assert_eq!(hidden, 0);
+}
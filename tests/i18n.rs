@@ -0,0 +1,31 @@
+// Автотест: таблица локализации переключает язык и не падает на неизвестных ключах
+// Запуск: cargo test --test i18n
+
+use astroforge::i18n::{Language, Locale};
+
+#[test]
+fn default_language_is_russian() {
+    let locale = Locale::default();
+    assert_eq!(locale.language, Language::Russian);
+    assert_eq!(locale.get("game_over"), "Вы погибли");
+}
+
+#[test]
+fn switching_language_translates() {
+    let mut locale = Locale::default();
+    assert_eq!(locale.get("game_over"), "Вы погибли");
+    locale.language = Language::English;
+    assert_eq!(locale.get("game_over"), "You died");
+    assert_eq!(
+        locale.get("beacon_unlocked"),
+        "Technology unlocked: energy beacon"
+    );
+    assert_eq!(Language::from_code("EN"), Some(Language::English));
+    assert_eq!(Language::from_code("xx"), None);
+}
+
+#[test]
+fn unknown_key_falls_back_to_key() {
+    let locale = Locale::new(Language::English);
+    assert_eq!(locale.get("no_such_string"), "no_such_string");
+}
@@ -0,0 +1,63 @@
+// Автотест: буфер экземпляров переиспользуется между кадрами и
+// пересоздаётся, только когда кубов больше, чем в него помещается
+// Запуск: cargo test --test instance_buffer
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 100;
+
+/// `count` мелких кубов в ряд за кадром и один красный куб в центре.
+fn cubes(count: usize) -> Vec<CubeInstance> {
+    let mut cubes: Vec<_> = (0..count.saturating_sub(1))
+        .map(|i| CubeInstance::new(Vec3::new(i as f32, 0.0, 50.0), 0.1, [0.0, 1.0, 0.0]))
+        .collect();
+    if count > 0 {
+        cubes.push(CubeInstance::new(
+            Vec3::new(0.0, 5.0, 0.0),
+            1.0,
+            [1.0, 0.0, 0.0],
+        ));
+    }
+    cubes
+}
+
+fn center(engine: &Engine) -> [u8; 3] {
+    let frame = engine.renderer.get_frame_rgba8();
+    let i = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+    [frame[i], frame[i + 1], frame[i + 2]]
+}
+
+#[test]
+fn instance_buffer_grows_only_past_capacity() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    let capacity = engine.renderer.instance_capacity();
+    assert_eq!(engine.renderer.instance_allocations(), 1);
+
+    // Пока всё помещается, буфер тот же, а рисуется только текущий кадр
+    for count in [1, capacity, 3, 0, capacity / 2, 1] {
+        engine.renderer.render(None, 100, &cubes(count));
+        assert_eq!(engine.renderer.instance_allocations(), 1, "{count} cubes");
+        assert_eq!(engine.renderer.instance_capacity(), capacity);
+        let red = center(&engine)[0] > 200;
+        assert_eq!(red, count > 0, "{count} cubes: {:?}", center(&engine));
+    }
+
+    engine.renderer.render(None, 100, &cubes(capacity + 1));
+    assert_eq!(engine.renderer.instance_allocations(), 2);
+    let grown = engine.renderer.instance_capacity();
+    assert_eq!(grown, (capacity + 1) * 2);
+    assert!(center(&engine)[0] > 200);
+
+    // После роста меньшие кадры снова обходятся без новых буферов
+    for count in [grown, 2, grown / 2] {
+        engine.renderer.render(None, 100, &cubes(count));
+        assert_eq!(engine.renderer.instance_allocations(), 2, "{count} cubes");
+        assert!(center(&engine)[0] > 200);
+    }
+}
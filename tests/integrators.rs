@@ -0,0 +1,81 @@
+// Автотест: RK4 держит энергию орбиты точнее, чем полунеявный Эйлер
+// Запуск: cargo test --test integrators
+
+use astroforge::engine::physics::{
+    Collider, Integrator, PhysicsObject, PhysicsWorld, RigidBody, GRAVITY,
+};
+use glam::Vec3;
+
+const STRENGTH: f32 = 50.0;
+const ORBIT: f32 = 5.0;
+const DT: f32 = 1.0 / 30.0;
+
+fn energy(body: &RigidBody) -> f32 {
+    let distance = body.position.length();
+    0.5 * body.mass * body.velocity.length_squared() - STRENGTH * body.mass / distance
+}
+
+/// Наибольшее отклонение энергии от начальной за несколько оборотов
+/// по круговой орбите вокруг колодца в начале координат.
+fn energy_drift(integrator: Integrator) -> f32 {
+    let mut world = PhysicsWorld::new().with_integrator(integrator);
+    world.add_attractor(Vec3::ZERO, STRENGTH, 100.0);
+    let mut body = RigidBody::new(1.0, Vec3::new(ORBIT, 0.0, 0.0));
+    body.velocity = Vec3::new(0.0, 0.0, (STRENGTH / ORBIT).sqrt());
+    // Без общей гравитации: тело держит только колодец
+    body.on_ground = true;
+    let collider = Collider::new(Vec3::splat(0.1));
+    let start = energy(&body);
+    let mut drift = 0.0f32;
+    for _ in 0..3000 {
+        let mut objs = [PhysicsObject {
+            body: &mut body,
+            collider,
+        }];
+        world.step(&mut objs, &[], DT);
+        drift = drift.max((energy(&body) - start).abs());
+    }
+    assert!(
+        body.position.length() > ORBIT * 0.5,
+        "body fell into the well"
+    );
+    drift / start.abs()
+}
+
+#[test]
+fn rk4_drifts_less_than_euler() {
+    let euler = energy_drift(Integrator::Euler);
+    let rk4 = energy_drift(Integrator::Rk4);
+    assert!(
+        rk4 < euler * 0.1,
+        "relative drift: euler {euler}, rk4 {rk4}"
+    );
+    assert!(rk4 < 1e-3, "rk4 drift {rk4}");
+}
+
+#[test]
+fn euler_is_the_default() {
+    assert_eq!(PhysicsWorld::default().integrator, Integrator::Euler);
+}
+
+#[test]
+fn rk4_is_exact_under_constant_gravity() {
+    // Под постоянной силой RK4 точен: x = v t + a t² / 2
+    let world = PhysicsWorld::new().with_integrator(Integrator::Rk4);
+    let mut body = RigidBody::new(2.0, Vec3::new(0.0, 100.0, 0.0));
+    body.velocity = Vec3::X * 3.0;
+    let collider = Collider::new(Vec3::splat(0.1));
+    for _ in 0..60 {
+        let mut objs = [PhysicsObject {
+            body: &mut body,
+            collider,
+        }];
+        world.step(&mut objs, &[], 1.0 / 60.0);
+    }
+    assert!((body.position.x - 3.0).abs() < 1e-4);
+    assert!(
+        (body.position.y - (100.0 - 0.5 * GRAVITY)).abs() < 1e-3,
+        "y = {}",
+        body.position.y
+    );
+}
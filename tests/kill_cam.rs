@@ -0,0 +1,111 @@
+// Автотест: при смерти игрока сохраняется повтор последних трёх секунд,
+// который проигрывается кадр за кадром с камеры у убийцы; пока время
+// стоит, запись не растёт
+// Запуск: cargo test --test kill_cam
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::killcam::{KillCam, ReplayFrame};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn frame(i: usize) -> ReplayFrame {
+    ReplayFrame {
+        dt: DT,
+        eye: Vec3::new(i as f32, 1.0, 0.0),
+        killer: Some(Vec3::new(i as f32, 1.0, -10.0)),
+        cubes: vec![CubeInstance::new(Vec3::ZERO, 0.5, [1.0; 3])],
+    }
+}
+
+#[test]
+fn only_the_last_seconds_are_kept() {
+    let mut cam = KillCam::new(3.0);
+    for i in 0..600 {
+        cam.record(frame(i));
+    }
+    assert!((179..=181).contains(&cam.len()), "{}", cam.len());
+    assert!((cam.duration() - 3.0).abs() < 0.05, "{}", cam.duration());
+
+    // Повтор начинается с самого старого кадра и кончается последним
+    let mut replay = cam.replay();
+    let first = replay.frame().unwrap().eye.x as usize;
+    assert_eq!(first, 600 - cam.len());
+    let mut shown = Vec::new();
+    while let Some(f) = replay.frame() {
+        shown.push(f.eye.x as usize);
+        replay.tick(DT);
+    }
+    assert!(replay.finished());
+    assert_eq!(shown, (first..600).collect::<Vec<_>>());
+    assert!(replay.view().is_none());
+}
+
+#[test]
+fn replay_camera_looks_at_the_killer() {
+    let mut cam = KillCam::new(3.0);
+    cam.record(frame(0));
+    let view = cam.replay().view().unwrap();
+    let killer = frame(0).killer.unwrap();
+    // В координатах камеры убийца прямо впереди, по оси -Z
+    let seen = view.transform_point3(killer);
+    assert!(seen.x.abs() < 1e-4 && seen.y.abs() < 1e-4, "{seen}");
+    assert!(seen.z < 0.0);
+    // А камера стоит позади него, дальше от игрока
+    let eye = view.inverse().transform_point3(Vec3::ZERO);
+    assert!(eye.distance(frame(0).eye) > killer.distance(frame(0).eye));
+}
+
+#[test]
+fn death_captures_a_playable_replay() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    for _ in 0..300 {
+        game.frame(&mut engine, DT);
+    }
+    assert!(game.replay.is_none());
+
+    game.health = 0;
+    game.update(&mut engine, DT);
+    assert!(game.game_over);
+    let replay = game.replay.clone().expect("no replay on death");
+    assert!((179..=181).contains(&replay.len()), "{}", replay.len());
+
+    // Повтор отрисовывается и доходит до конца за свою длину
+    for _ in 0..replay.len() {
+        assert!(!game.replay.as_ref().unwrap().finished());
+        game.render(&mut engine);
+        game.update(&mut engine, DT);
+    }
+    assert!(game.replay.as_ref().unwrap().finished());
+    assert_eq!(game.kill_cam.len(), replay.len(), "recorded after death");
+}
+
+#[test]
+fn frozen_time_records_nothing() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    for _ in 0..10 {
+        game.frame(&mut engine, DT);
+    }
+    assert_eq!(game.kill_cam.len(), 10);
+    game.time_scale = 0.0;
+    for _ in 0..100 {
+        game.frame(&mut engine, DT);
+    }
+    assert_eq!(game.kill_cam.len(), 10);
+
+    // Кадры без обновления и обновления без кадров записываются по
+    // одному на отрисовку, со всем накопленным временем
+    game.time_scale = 1.0;
+    game.update(&mut engine, DT);
+    game.update(&mut engine, DT);
+    game.render(&mut engine);
+    game.render(&mut engine);
+    assert_eq!(game.kill_cam.len(), 11);
+    assert!((game.kill_cam.duration() - 12.0 * DT).abs() < 1e-5);
+}
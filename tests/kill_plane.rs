@@ -0,0 +1,65 @@
+// Автотест: тело, упавшее ниже плоскости смерти, обрабатывается: игрок
+// погибает или, в режиме бога, возвращается на старт, а предметы исчезают
+// Запуск: cargo test --test kill_plane
+
+use astroforge::config::Level;
+use astroforge::engine::input::InputState;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions, MAX_HEALTH};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+/// Под полом, но выше плоскости смерти, заданной в `setup`.
+const UNDER_FLOOR: Vec3 = Vec3::new(10.0, -10.0, 10.0);
+
+fn setup() -> (Engine, Game) {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.player.update(&InputState::default(), 10.0);
+    game.kill_plane = -20.0;
+    (engine, game)
+}
+
+#[test]
+fn the_player_dies_below_the_kill_plane() {
+    let (mut engine, mut game) = setup();
+    game.player.body.position = UNDER_FLOOR;
+    game.update(&mut engine, DT);
+    assert!(!game.game_over, "died above the kill plane");
+
+    game.player.body.position.y = -25.0;
+    game.update(&mut engine, DT);
+    assert!(game.game_over);
+    assert!(game.health <= 0);
+}
+
+#[test]
+fn a_god_respawns_instead() {
+    let (mut engine, mut game) = setup();
+    game.god_mode = true;
+    game.player.body.position = Vec3::new(10.0, -25.0, 10.0);
+    game.player.body.velocity = Vec3::new(0.0, -30.0, 0.0);
+    game.update(&mut engine, DT);
+    // Возрождение прячется за затемнением, игрока переносят в темноте
+    assert!(game.fade.is_some());
+    while game.fade.as_ref().is_some_and(|f| f.alpha() < 1.0) {
+        game.update(&mut engine, DT);
+    }
+    assert!(!game.game_over);
+    assert_eq!(game.health, MAX_HEALTH);
+    // Тот же кадр уже успел сдвинуть игрока шагом физики
+    let start = Level::Beacon.player_start();
+    assert!(game.player.body.position.distance(start) < 0.1);
+    assert!(game.player.body.velocity.length() < 5.0);
+}
+
+#[test]
+fn props_below_the_kill_plane_are_removed() {
+    let (mut engine, mut game) = setup();
+    let falling = game.spawn_barrel(Vec3::new(10.0, -25.0, 10.0));
+    let resting = game.spawn_barrel(Vec3::new(10.0, 0.4, -10.0));
+    game.update(&mut engine, DT);
+    assert!(game.world.get(falling).is_none());
+    assert!(game.world.get(resting).is_some());
+}
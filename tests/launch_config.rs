@@ -0,0 +1,81 @@
+// Автотест: флаги командной строки разбираются в конфигурацию запуска, и
+// она задаёт начало игры: уровень, сложность, врагов и бессмертие
+// Запуск: cargo test --test launch_config
+
+use astroforge::config::{Difficulty, LaunchConfig, Level, Scenario};
+use astroforge::engine::input::InputState;
+use astroforge::game::{Game, GameOptions, MAX_HEALTH};
+
+#[test]
+fn flags_are_parsed_into_the_config() {
+    let args = [
+        "--lang",
+        "en",
+        "--level",
+        "arena",
+        "--difficulty",
+        "hard",
+        "--no-enemies",
+        "--god",
+        "--practice",
+        "--screenshot",
+    ];
+    assert_eq!(
+        LaunchConfig::from_args(&args),
+        Ok(LaunchConfig {
+            scenario: Scenario {
+                level: Level::Arena,
+                difficulty: Difficulty::Hard,
+                enemies: false,
+                god: true,
+                practice: true,
+            },
+            selftest: false,
+            screenshot: true,
+        })
+    );
+    let none: [&str; 0] = [];
+    assert_eq!(LaunchConfig::from_args(&none), Ok(LaunchConfig::default()));
+    assert!(LaunchConfig::from_args(&["--selftest"]).unwrap().selftest);
+}
+
+#[test]
+fn bad_values_are_reported() {
+    assert!(LaunchConfig::from_args(&["--level", "moon"]).is_err());
+    assert!(LaunchConfig::from_args(&["--difficulty"]).is_err());
+}
+
+fn game_with(scenario: Scenario) -> Game {
+    let mut game = Game::new(GameOptions {
+        scenario,
+        ..GameOptions::default()
+    });
+    // Ждём конца защиты после появления
+    game.player.update(&InputState::default(), 10.0);
+    game
+}
+
+#[test]
+fn the_scenario_drives_the_game() {
+    let arena = game_with(Scenario {
+        level: Level::Arena,
+        ..Scenario::default()
+    });
+    assert!(arena.tech_unlocked);
+    assert_eq!(arena.player.body.position, Level::Arena.player_start());
+    assert!(!game_with(Scenario::default()).tech_unlocked);
+
+    let mut god = game_with(Scenario {
+        god: true,
+        ..Scenario::default()
+    });
+    assert!(!god.damage_player(40));
+    assert_eq!(god.health, MAX_HEALTH);
+
+    let mut easy = game_with(Scenario {
+        difficulty: Difficulty::Easy,
+        ..Scenario::default()
+    });
+    assert!(easy.damage_player(40));
+    assert_eq!(easy.health, MAX_HEALTH - 20);
+}
@@ -0,0 +1,65 @@
+// Автотест: со сглаживанием обзора один большой рывок мыши доворачивает
+// камеру за несколько кадров, а без него — сразу; при любой частоте кадров
+// доворот идёт одинаково
+// Запуск: cargo test --test look_smoothing
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::player::Player;
+use glam::Quat;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Угол поворота игрока после каждого кадра длиной `dt`: первый кадр с
+/// рывком мыши, дальше мышь неподвижна.
+fn turn_per_frame(smoothing: f32, frames: usize, dt: f32) -> Vec<f32> {
+    let mut player = Player::new();
+    player.look_smoothing = smoothing;
+    let mut flick = InputState::default();
+    flick.apply_frame(&InputFrame {
+        mouse_delta: (400.0, 0.0),
+        ..InputFrame::default()
+    });
+    let idle = InputState::default();
+    (0..frames)
+        .map(|i| {
+            player.update(if i == 0 { &flick } else { &idle }, dt);
+            player.rotation.angle_between(Quat::IDENTITY)
+        })
+        .collect()
+}
+
+#[test]
+fn smoothing_spreads_a_flick_over_several_frames() {
+    let raw = turn_per_frame(0.0, 60, DT);
+    let smooth = turn_per_frame(0.8, 60, DT);
+
+    // Без сглаживания весь поворот сразу, дальше камера стоит
+    assert!(raw[0] > 0.7);
+    assert_eq!(raw[0], raw[1]);
+
+    // Со сглаживанием первый кадр даёт лишь часть поворота, и камера
+    // доворачивает ещё несколько кадров
+    assert!(smooth[0] < raw[0] * 0.5, "{} vs {}", smooth[0], raw[0]);
+    assert!(smooth[1] > smooth[0]);
+    assert!(smooth[4] > smooth[3]);
+    assert!(
+        (smooth[59] - raw[0]).abs() < 1e-3,
+        "{} vs {}",
+        smooth[59],
+        raw[0]
+    );
+}
+
+#[test]
+fn smoothing_converges_the_same_at_any_frame_rate() {
+    let at_60 = turn_per_frame(0.8, 60, DT);
+    let at_240 = turn_per_frame(0.8, 240, DT / 4.0);
+    // Сравниваем в одни и те же моменты времени
+    for (i, &angle) in at_60.iter().enumerate().step_by(5) {
+        let same_time = at_240[i * 4 + 3];
+        assert!(
+            (angle - same_time).abs() < 1e-4,
+            "frame {i}: {angle} vs {same_time}"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// Автотест: выбор в меню ходит вверх и вниз по кругу, а активация
+// возвращает id выбранного пункта
+// Запуск: cargo test --test menu_navigation
+
+use astroforge::engine::hud::HudTheme;
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::engine::menu::{MenuItem, MenuState};
+use winit::event::VirtualKeyCode;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Choice {
+    Resume,
+    Settings,
+    Quit,
+}
+
+fn pause_menu() -> MenuState<Choice> {
+    MenuState::new(vec![
+        MenuItem::new(Choice::Resume, "Resume"),
+        MenuItem::new(Choice::Settings, "Settings"),
+        MenuItem::new(Choice::Quit, "Quit"),
+    ])
+}
+
+/// Нажать и отпустить клавишу за один кадр меню.
+fn press(
+    menu: &mut MenuState<Choice>,
+    input: &mut InputState,
+    key: VirtualKeyCode,
+) -> Option<Choice> {
+    input.apply_frame(&InputFrame::holding(&[key]));
+    let activated = menu.update(input);
+    input.reset();
+    input.apply_frame(&InputFrame::default());
+    activated
+}
+
+#[test]
+fn navigation_wraps_and_activation_returns_the_id() {
+    let mut menu = pause_menu();
+    let mut input = InputState::default();
+    assert_eq!(menu.selected(), 0);
+
+    // Вверх с первого пункта — на последний
+    assert_eq!(press(&mut menu, &mut input, VirtualKeyCode::Up), None);
+    assert_eq!(menu.selected(), 2);
+    // Вниз с последнего — на первый
+    press(&mut menu, &mut input, VirtualKeyCode::Down);
+    assert_eq!(menu.selected(), 0);
+    press(&mut menu, &mut input, VirtualKeyCode::S);
+    assert_eq!(menu.selected(), 1);
+
+    assert_eq!(
+        press(&mut menu, &mut input, VirtualKeyCode::Return),
+        Some(Choice::Settings)
+    );
+}
+
+#[test]
+fn held_key_moves_only_once() {
+    let mut menu = pause_menu();
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::Down]));
+    menu.update(&input);
+    for _ in 0..5 {
+        input.reset();
+        input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::Down]));
+        assert_eq!(menu.update(&input), None);
+    }
+    assert_eq!(menu.selected(), 1);
+}
+
+#[test]
+fn sections_highlight_the_selected_item() {
+    let mut menu = pause_menu();
+    menu.select(1);
+    let theme = HudTheme::default();
+    let sections = menu.sections((30.0, 30.0), 28.0, &theme);
+    assert_eq!(sections.len(), 3);
+    let text = |i: usize| &sections[i].text[0];
+    assert_eq!(text(1).text, "> Settings");
+    assert_eq!(text(1).extra.color, theme.accent);
+    assert_eq!(text(0).text, "  Resume");
+    assert_eq!(text(0).extra.color, theme.text);
+    assert!(sections[2].screen_position.1 > sections[1].screen_position.1);
+
+    let mut empty = MenuState::<Choice>::new(Vec::new());
+    empty.move_down();
+    assert_eq!(empty.update(&InputState::default()), None);
+    assert!(empty.selected_item().is_none());
+}
@@ -0,0 +1,60 @@
+// Автотест: вторая зарегистрированная сетка рисуется своей геометрией,
+// а не кубом
+// Запуск: cargo test --test mesh_registry
+
+use astroforge::engine::mesh::{MeshInstance, CUBE_MESH};
+use astroforge::engine::renderer::{CubeInstance, Vertex};
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 400;
+/// Пикселей на единицу длины на плоскости z = 0.5 при камере ниже.
+const PX_PER_UNIT: f32 = 99.0;
+
+fn lit(frame: &[u8], x: f32, y: f32) -> bool {
+    let x = (SIZE as f32 / 2.0 + x * PX_PER_UNIT) as u32;
+    let y = (SIZE as f32 / 2.0 - y * PX_PER_UNIT) as u32;
+    let i = ((y * SIZE + x) * 4) as usize;
+    frame[i..i + 3] != [0, 0, 0]
+}
+
+#[test]
+fn second_mesh_draws_its_own_geometry() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    let renderer = &mut engine.renderer;
+    // Прямоугольный треугольник в левой нижней половине единичного квадрата
+    let white = [1.0, 1.0, 1.0];
+    let triangle = renderer.register_mesh(
+        &[
+            Vertex::new([-0.5, -0.5, 0.0], white),
+            Vertex::new([0.5, -0.5, 0.0], white),
+            Vertex::new([-0.5, 0.5, 0.0], white),
+        ],
+        &[0, 1, 2],
+    );
+    assert_ne!(triangle, CUBE_MESH);
+    assert_eq!(renderer.meshes.len(), 2);
+
+    // Куб слева, треугольник справа, передние грани на z = 0.5
+    let cube = CubeInstance::new(Vec3::new(-1.5, 5.0, 0.0), 1.0, [0.0, 1.0, 0.0]);
+    renderer.mesh_instances = vec![
+        MeshInstance::from(cube),
+        MeshInstance::new(
+            triangle,
+            Mat4::from_translation(Vec3::new(1.5, 5.0, 0.5)),
+            [1.0, 0.0, 0.0],
+        ),
+    ];
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    renderer.update_camera(&(proj * view));
+    renderer.render(None, 100, &[]);
+    let frame = renderer.get_frame_rgba8();
+
+    // Левые нижние углы есть у обеих фигур, правый верхний — только у куба
+    assert!(lit(&frame, -1.8, -0.3), "cube missing");
+    assert!(lit(&frame, -1.2, 0.3), "cube corner missing");
+    assert!(lit(&frame, 1.2, -0.3), "triangle missing");
+    assert!(!lit(&frame, 1.8, 0.3), "triangle drawn as a cube");
+}
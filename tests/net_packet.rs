@@ -0,0 +1,64 @@
+// Автотест: кадрирование пакетов с префиксом длины, сглаживание удалённой
+// позиции и отбрасывание старых снимков, кроме снимков перезапущенного пира
+// Запуск: cargo test --features net --test net_packet
+#![cfg(feature = "net")]
+
+use astroforge::engine::net::{frame, newer_tick, read_frame, Interpolator};
+use astroforge::engine::physics::RigidBody;
+use astroforge::engine::snapshot::WorldSnapshot;
+use glam::Vec3;
+
+#[test]
+fn framed_snapshot_roundtrips() {
+    let body = RigidBody::new(80.0, Vec3::new(1.0, 2.0, 3.0));
+    let snapshot = WorldSnapshot::capture(5, [&body]);
+    let packet = frame(&snapshot.to_bytes());
+    assert_eq!(&packet[..4], &(packet.len() as u32 - 4).to_le_bytes());
+
+    let (payload, rest) = read_frame(&packet).expect("complete packet");
+    assert!(rest.is_empty());
+    assert_eq!(WorldSnapshot::from_bytes(payload), Some(snapshot));
+}
+
+#[test]
+fn truncated_packet_is_rejected() {
+    let packet = frame(b"hello");
+    assert_eq!(read_frame(&packet[..packet.len() - 1]), None);
+    assert_eq!(read_frame(&packet[..3]), None);
+}
+
+#[test]
+fn back_to_back_packets_split_apart() {
+    let mut stream = frame(b"one");
+    stream.extend(frame(b""));
+    stream.extend(frame(b"three"));
+    let (a, rest) = read_frame(&stream).unwrap();
+    let (b, rest) = read_frame(rest).unwrap();
+    let (c, rest) = read_frame(rest).unwrap();
+    assert_eq!((a, b, c), (&b"one"[..], &b""[..], &b"three"[..]));
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn interpolator_glides_to_new_position() {
+    let mut remote = Interpolator::new(Vec3::ZERO, 0.1);
+    remote.push(Vec3::new(1.0, 0.0, 0.0));
+    assert_eq!(remote.position(), Vec3::ZERO);
+    remote.update(0.05);
+    assert!((remote.position().x - 0.5).abs() < 1e-5);
+    remote.update(0.1);
+    assert_eq!(remote.position(), Vec3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn late_snapshots_are_dropped_but_a_restarted_peer_is_not() {
+    assert!(newer_tick(1, 0));
+    assert!(newer_tick(11, 10));
+    assert!(!newer_tick(10, 10), "repeated tick");
+    assert!(!newer_tick(8, 10), "reordered tick");
+    // Пир перезапустился и снова считает с единицы
+    assert!(newer_tick(1, 500));
+    // Счётчик переполнился
+    assert!(newer_tick(2, u32::MAX - 1));
+    assert!(!newer_tick(u32::MAX - 1, 2));
+}
@@ -0,0 +1,58 @@
+// Автотест: OBJ-файл превращается в вершины и индексы сетки
+// Запуск: cargo test --test obj_loader
+
+use astroforge::engine::mesh::parse_obj;
+
+/// Квадрат из двух треугольников с общей нормалью, плюс строки, которые
+/// загрузчик должен пропускать.
+const QUAD: &str = "\
+# квадрат
+mtllib quad.mtl
+o Quad
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vn 0 0 1
+usemtl plain
+s off
+f 1//1 2//1 3//1
+f 1/1/1 3/1/1 4/1/1
+";
+
+#[test]
+fn parses_quad_into_shared_vertices() {
+    let mesh = parse_obj(QUAD).unwrap();
+    // Общие углы с одной и той же нормалью не дублируются
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.normals.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert!(mesh.normals.iter().all(|&n| n == [0.0, 0.0, 1.0]));
+}
+
+#[test]
+fn different_normals_split_a_vertex() {
+    let text = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nvn 0 0 -1\n\
+                f 1//1 2//1 3//1\nf 1//2 3//2 2//2\n";
+    let mesh = parse_obj(text).unwrap();
+    assert_eq!(mesh.vertices.len(), 6);
+    assert_eq!(mesh.indices.len(), 6);
+}
+
+#[test]
+fn negative_indices_count_from_the_end() {
+    let mesh = parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n").unwrap();
+    assert_eq!(mesh.vertices.len(), 3);
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    // Без нормалей в файле нормаль нулевая
+    assert_eq!(mesh.normals[0], [0.0; 3]);
+}
+
+#[test]
+fn rejects_bad_faces() {
+    let quad_face = parse_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n").unwrap_err();
+    assert!(quad_face.starts_with("line 5:"), "{quad_face}");
+    assert!(parse_obj("v 0 0 0\nf 1 2 3\n").is_err());
+    assert!(parse_obj("v 0 zero 0\n").is_err());
+}
@@ -0,0 +1,43 @@
+// Автотест: время паузы не попадает в dt после возобновления
+// Запуск: cargo test --test pause_clock
+
+use astroforge::engine::timer::FrameClock;
+use std::time::Duration;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn dt_across_a_pause_excludes_the_paused_interval() {
+    let t0 = Duration::ZERO;
+    let mut clock = FrameClock::new(t0);
+    assert!((clock.tick(t0 + ms(16)) - 0.016).abs() < 1e-6);
+    // 10 мс игры, 5 секунд паузы, ещё 6 мс игры
+    clock.pause(t0 + ms(26));
+    clock.resume(t0 + ms(5026));
+    let dt = clock.tick(t0 + ms(5032));
+    assert!((dt - 0.016).abs() < 1e-6, "dt after pause: {dt}");
+    // Следующий кадр уже обычный
+    assert!((clock.tick(t0 + ms(5048)) - 0.016).abs() < 1e-6);
+}
+
+#[test]
+fn tick_during_a_pause_counts_only_the_time_before_it() {
+    let t0 = Duration::ZERO;
+    let mut clock = FrameClock::new(t0);
+    clock.pause(t0 + ms(10));
+    assert!((clock.tick(t0 + ms(1000)) - 0.010).abs() < 1e-6);
+    clock.resume(t0 + ms(2000));
+    assert!((clock.tick(t0 + ms(2004)) - 0.004).abs() < 1e-6);
+}
+
+#[test]
+fn repeated_pause_keeps_the_first_start() {
+    let t0 = Duration::ZERO;
+    let mut clock = FrameClock::new(t0);
+    clock.pause(t0 + ms(5));
+    clock.pause(t0 + ms(500));
+    clock.resume(t0 + ms(1000));
+    assert!((clock.tick(t0 + ms(1003)) - 0.008).abs() < 1e-6);
+}
@@ -0,0 +1,74 @@
+// Нагрузочный тест физики: N тел, M кадров, бюджет по времени и детерминизм
+// Запуск: cargo test --release --test physics_bench -- --nocapture
+// Число тел: PHYSICS_BENCH_BODIES (по умолчанию 100),
+// бюджет: PHYSICS_BENCH_BUDGET_MS (по умолчанию 5000)
+
+use astroforge::engine::physics::{step, Aabb, Collider, PhysicsObject, RigidBody};
+use glam::Vec3;
+use std::time::Instant;
+
+const FRAMES: usize = 300;
+
+fn env_or(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Bodies on a loose grid, dropped from staggered heights so some of them
+/// land on each other and the pair loop has real contacts to resolve.
+fn scene(count: usize) -> Vec<RigidBody> {
+    let side = (count as f32).sqrt().ceil() as usize;
+    (0..count)
+        .map(|i| {
+            let x = (i % side) as f32 * 0.9 - side as f32 * 0.45;
+            let z = (i / side) as f32 * 0.9 - side as f32 * 0.45;
+            let y = 0.75 + (i % 3) as f32 * 1.6;
+            RigidBody::new(80.0, Vec3::new(x, y, z))
+        })
+        .collect()
+}
+
+fn simulate(count: usize) -> Vec<RigidBody> {
+    let mut bodies = scene(count);
+    let collider = Collider::new(Vec3::new(0.5, 0.75, 0.5));
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )];
+    for _ in 0..FRAMES {
+        let mut objs: Vec<PhysicsObject> = bodies
+            .iter_mut()
+            .map(|body| PhysicsObject { body, collider })
+            .collect();
+        step(&mut objs, &floor, 1.0 / 60.0);
+    }
+    bodies
+}
+
+#[test]
+fn physics_step_within_budget_and_deterministic() {
+    let count = env_or("PHYSICS_BENCH_BODIES", 100) as usize;
+    let budget = env_or("PHYSICS_BENCH_BUDGET_MS", 5000) as u128;
+
+    let start = Instant::now();
+    let first = simulate(count);
+    let elapsed = start.elapsed();
+    println!(
+        "physics_bench: {count} bodies x {FRAMES} frames in {:.2} ms ({:.3} ms/frame)",
+        elapsed.as_secs_f64() * 1000.0,
+        elapsed.as_secs_f64() * 1000.0 / FRAMES as f64
+    );
+    assert!(
+        elapsed.as_millis() <= budget,
+        "physics step too slow: {} ms > {budget} ms",
+        elapsed.as_millis()
+    );
+
+    let second = simulate(count);
+    for (i, (a, b)) in first.iter().zip(&second).enumerate() {
+        assert_eq!(a.position, b.position, "body {i} position diverged");
+        assert_eq!(a.velocity, b.velocity, "body {i} velocity diverged");
+    }
+}
@@ -0,0 +1,65 @@
+// Регрессионный тест: одна и та же сцена дважды даёт побитово одинаковый результат
+// Запуск: cargo test --test physics_determinism
+
+use astroforge::engine::physics::{step, Aabb, Collider, PhysicsObject, RigidBody};
+use glam::Vec3;
+
+type Frame = (Vec<[u32; 6]>, Vec<(usize, usize)>);
+
+fn run() -> Vec<Frame> {
+    let mut bodies = [
+        RigidBody::new(80.0, Vec3::new(0.0, 1.0, 2.0)),
+        RigidBody::new(80.0, Vec3::new(0.4, 2.2, 2.3)),
+        RigidBody::new(80.0, Vec3::new(-0.3, 3.1, 1.8)),
+        RigidBody::new(0.05, Vec3::new(1.0, 1.5, 2.0)),
+    ];
+    bodies[3].velocity = Vec3::new(-5.0, 0.0, 0.1);
+    let collider = Collider::new(Vec3::new(0.5, 0.75, 0.5));
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )];
+    let mut frames = Vec::new();
+    for i in 0..240 {
+        bodies[0].apply_force(Vec3::new((i % 7) as f32 * 13.0, 0.0, -40.0));
+        let mut objs: Vec<PhysicsObject> = bodies
+            .iter_mut()
+            .map(|body| PhysicsObject { body, collider })
+            .collect();
+        let pairs = step(&mut objs, &floor, 1.0 / 60.0);
+        let state = bodies
+            .iter()
+            .map(|b| {
+                let (p, v) = (b.position, b.velocity);
+                [
+                    p.x.to_bits(),
+                    p.y.to_bits(),
+                    p.z.to_bits(),
+                    v.x.to_bits(),
+                    v.y.to_bits(),
+                    v.z.to_bits(),
+                ]
+            })
+            .collect();
+        frames.push((state, pairs));
+    }
+    frames
+}
+
+#[test]
+fn same_scene_is_bit_identical() {
+    let a = run();
+    let b = run();
+    for (i, (fa, fb)) in a.iter().zip(&b).enumerate() {
+        assert_eq!(fa, fb, "simulation diverged at frame {i}");
+    }
+}
+
+#[test]
+fn contact_pairs_are_in_index_order() {
+    for (state, pairs) in run() {
+        assert!(!state.is_empty());
+        assert!(pairs.iter().all(|(i, j)| i < j));
+        assert!(pairs.windows(2).all(|w| w[0] < w[1]));
+    }
+}
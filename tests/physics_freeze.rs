@@ -0,0 +1,58 @@
+// Автотест: замороженная физика не делает шагов, пока не попросят один,
+// а взгляд игрока при этом работает
+// Запуск: cargo test --test physics_freeze
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn frozen_physics_steps_only_on_request() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.update(&mut engine, DT);
+    assert_eq!(game.physics_steps, 1);
+
+    game.physics_frozen = true;
+    game.player.body.velocity = Vec3::new(2.0, 0.0, 0.0);
+    let start = game.player.body.position;
+    for _ in 0..10 {
+        game.update(&mut engine, DT);
+    }
+    assert_eq!(game.physics_steps, 1, "stepped while frozen");
+    assert_eq!(
+        game.player.body.position, start,
+        "player moved while frozen"
+    );
+
+    game.request_step();
+    game.update(&mut engine, DT);
+    assert_eq!(game.physics_steps, 2);
+    assert!(
+        game.player.body.position.x > start.x,
+        "single step left the player still"
+    );
+    for _ in 0..10 {
+        game.update(&mut engine, DT);
+    }
+    assert_eq!(game.physics_steps, 2, "one request stepped more than once");
+
+    game.physics_frozen = false;
+    game.update(&mut engine, DT);
+    assert_eq!(game.physics_steps, 3);
+}
+
+#[test]
+fn step_request_while_running_is_not_kept() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.request_step();
+    game.update(&mut engine, DT);
+    game.physics_frozen = true;
+    game.update(&mut engine, DT);
+    assert_eq!(game.physics_steps, 1);
+}
@@ -0,0 +1,67 @@
+// Автотест: после шага известной сцены счётчики физики совпадают с
+// числом тел, проверенных пар и контактов, и попадают в отладочный HUD
+// Запуск: cargo test --test physics_stats
+
+use astroforge::engine::input::InputFrame;
+use astroforge::engine::physics::{
+    layers, Collider, PhysicsObject, PhysicsWorld, RigidBody, StepStats,
+};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+#[test]
+fn stats_count_bodies_pairs_and_contacts() {
+    let solid = Collider::new(Vec3::splat(0.5));
+    // Обломки не сталкиваются друг с другом, их пара не проверяется
+    let debris = solid.with_layer(layers::DEBRIS, layers::DEFAULT);
+    let mut bodies = [
+        (Vec3::new(0.0, 5.0, 0.0), solid),
+        (Vec3::new(0.8, 5.0, 0.0), solid),
+        (Vec3::new(10.0, 5.0, 0.0), debris),
+        (Vec3::new(20.0, 5.0, 0.0), debris),
+    ]
+    .map(|(p, c)| (RigidBody::new(80.0, p), c));
+    let mut objs: Vec<PhysicsObject> = bodies
+        .iter_mut()
+        .map(|(body, collider)| PhysicsObject {
+            body,
+            collider: *collider,
+        })
+        .collect();
+    let world = PhysicsWorld::new().with_solver_iterations(4);
+    let (contacts, stats) = world.step_stats(&mut objs, &[], 1.0 / 60.0);
+    assert_eq!(contacts.len(), 1);
+    // Пары: 0-1, 0-2, 0-3, 1-2, 1-3; 2-3 отсеяна слоями
+    assert_eq!(
+        stats,
+        StepStats {
+            objects: 4,
+            pairs: 5,
+            contacts: 1,
+        }
+    );
+}
+
+#[test]
+fn f5_shows_the_last_step_on_the_debug_hud() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(400, 300);
+    let mut game = Game::new(GameOptions::default());
+    engine
+        .input
+        .apply_frame(&InputFrame::holding(&[VirtualKeyCode::F5]));
+    game.update(&mut engine, 1.0 / 60.0);
+    engine.input.reset();
+    assert!(game.show_physics_stats);
+    // В начале игры шагает только игрок
+    assert_eq!(game.physics_stats.objects, 1);
+    assert_eq!(game.physics_stats.pairs, 0);
+
+    game.render(&mut engine);
+    let text = engine.renderer.hud_debug.clone().expect("debug text");
+    assert!(text.contains("physics: 1 objects, 0 pairs"), "{text}");
+    assert!(text.contains(" ms"), "{text}");
+    assert!(!text.contains("pos:"), "F3 readout is still off");
+}
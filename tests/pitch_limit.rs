@@ -0,0 +1,54 @@
+// Автотест: наклон взгляда останавливается на заданном пределе и никогда
+// не доходит до вертикали, где вид бы перевернулся
+// Запуск: cargo test --test pitch_limit
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::player::Player;
+use glam::Vec3;
+
+/// Много кадров подряд ведём мышь на `dy` пикселей за кадр.
+fn look(player: &mut Player, dy: f32) {
+    for _ in 0..200 {
+        let mut input = InputState::default();
+        input.apply_frame(&InputFrame {
+            keys: Vec::new(),
+            mouse_delta: (0.0, dy),
+        });
+        player.update(&input, 1.0 / 60.0);
+    }
+}
+
+#[test]
+fn tighter_limit_stops_looking_up() {
+    let mut player = Player::new().with_pitch_limit(0.8);
+    look(&mut player, -50.0);
+    assert!((player.pitch() - 0.8).abs() < 1e-6, "{}", player.pitch());
+    let forward = player.rotation * Vec3::NEG_Z;
+    assert!((forward.y.asin() - 0.8).abs() < 1e-4, "{forward}");
+
+    look(&mut player, 50.0);
+    assert!((player.pitch() + 0.8).abs() < 1e-6, "{}", player.pitch());
+}
+
+#[test]
+fn default_limit_is_unchanged() {
+    let mut player = Player::new();
+    assert_eq!(player.pitch_limit, 1.54);
+    look(&mut player, -50.0);
+    assert!((player.pitch() - 1.54).abs() < 1e-6);
+}
+
+#[test]
+fn view_never_flips_over() {
+    let mut player = Player::new().with_pitch_limit(10.0);
+    for dy in [-500.0, 500.0] {
+        look(&mut player, dy);
+        assert!(player.pitch().abs() < std::f32::consts::FRAC_PI_2);
+        // Вид не перевернулся: «вверх» камеры по-прежнему смотрит вверх,
+        // а «вперёд» не ушло за спину
+        let up = player.rotation * Vec3::Y;
+        let forward = player.rotation * Vec3::NEG_Z;
+        assert!(up.y > 0.0, "{up}");
+        assert!(forward.z < 0.0, "{forward}");
+    }
+}
@@ -0,0 +1,111 @@
+// Автотест: в тире вместо врага стоят мишени; попадание игрока
+// засчитывается, чужое — нет, а разбитая мишень исчезает
+// Запуск: cargo test --test practice_range
+
+use astroforge::config::{Level, Scenario};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::weapon::Hitscan;
+use astroforge::world::{Entity, Hit, Projectile, Tag};
+use glam::{Quat, Vec3};
+
+fn practice() -> Game {
+    Game::new(GameOptions {
+        scenario: Scenario {
+            level: Level::Arena,
+            practice: true,
+            ..Scenario::default()
+        },
+        ..GameOptions::default()
+    })
+}
+
+fn targets(game: &Game) -> Vec<astroforge::engine::ecs::EntityId> {
+    game.world.with_tag(Tag::Target).map(|(id, _)| id).collect()
+}
+
+#[test]
+fn rifle_hit_scores_and_breaks_a_target() {
+    let mut game = practice();
+    let all = targets(&game);
+    assert_eq!(all.len(), 5);
+    let target = all[0];
+    let position = game.world.get(target).unwrap().body.unwrap().position;
+    let aim = (position - game.player.position).normalize();
+    game.player.rotation = Quat::from_rotation_arc(-Vec3::Z, aim);
+
+    assert!(game.fire_hitscan(Hitscan::new()));
+    assert_eq!(game.practice_hits, 1);
+    assert_eq!(game.targets_destroyed, 1);
+    assert!(game.world.get(target).is_none(), "target still standing");
+    assert_eq!(targets(&game).len(), 4);
+}
+
+#[test]
+fn bullet_hits_count_until_the_target_breaks() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = practice();
+    let target = targets(&game)[2];
+    let bullet = game
+        .world
+        .spawn(Entity::new(Tag::Bullet).with_projectile(Projectile::new(Some(game.player_id))));
+    let hit = Hit {
+        bullet,
+        target,
+        damage: 10,
+        impulse: Vec3::new(0.0, 0.0, -5.0),
+    };
+    game.apply_hit(hit);
+    game.update(&mut engine, 1.0 / 60.0);
+    assert_eq!(game.practice_hits, 1);
+    assert_eq!(game.targets_destroyed, 0);
+    // Мишень неподвижна: попадание её не толкает
+    let body = game.world.get(target).expect("broke too early").body;
+    assert_eq!(body.unwrap().velocity.z, 0.0);
+
+    game.apply_hit(hit);
+    game.apply_hit(hit);
+    game.update(&mut engine, 1.0 / 60.0);
+    assert_eq!(game.practice_hits, 3);
+    assert_eq!(game.targets_destroyed, 1);
+    assert!(game.world.get(target).is_none());
+}
+
+#[test]
+fn hits_by_others_are_not_counted() {
+    let mut game = practice();
+    let target = targets(&game)[0];
+    let health = |game: &Game| game.world.get(target).unwrap().health.unwrap();
+    let before = health(&game);
+    let bullet = game
+        .world
+        .spawn(Entity::new(Tag::Bullet).with_projectile(Projectile::new(None)));
+    game.apply_hit(Hit {
+        bullet,
+        target,
+        damage: 10,
+        impulse: Vec3::ZERO,
+    });
+    assert_eq!(game.practice_hits, 0);
+    // Урон мишень всё равно получает
+    assert_eq!(health(&game), before - 10);
+}
+
+#[test]
+fn range_has_no_enemy_and_resets_when_cleared() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = practice();
+    for _ in 0..400 {
+        game.update(&mut engine, 1.0 / 60.0);
+    }
+    assert!(game.enemy.is_none());
+
+    for id in targets(&game) {
+        game.world.damage(id, 1000);
+    }
+    game.update(&mut engine, 1.0 / 60.0);
+    assert_eq!(game.targets_destroyed, 5);
+    assert_eq!(targets(&game).len(), 5, "range was not set up again");
+}
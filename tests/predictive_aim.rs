@@ -0,0 +1,55 @@
+// Автотест: враг с упреждением целится туда, где движущийся игрок окажется
+// к прилёту пули, а не туда, где он стоит
+// Запуск: cargo test --test predictive_aim
+
+use astroforge::config::Difficulty;
+use astroforge::player::{intercept, Enemy};
+use glam::Vec3;
+
+const FROM: Vec3 = Vec3::new(0.0, 1.0, -10.0);
+const TARGET: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+const SPEED: f32 = 5.0;
+
+#[test]
+fn lead_aims_ahead_of_a_moving_target() {
+    let velocity = Vec3::new(3.0, 0.0, 0.0);
+    let enemy = Enemy::new().with_lead(true);
+    let point = enemy.aim_point(FROM, TARGET, velocity, SPEED);
+    let direct = (TARGET - FROM).normalize();
+    let led = (point - FROM).normalize();
+    assert!(led.angle_between(direct) > 0.1, "{led}");
+    // Упреждение в сторону движения цели
+    assert!(led.x > 0.0, "{led}");
+
+    // Пуля и цель приходят в точку встречи одновременно
+    let bullet_time = point.distance(FROM) / SPEED;
+    let target_time = point.distance(TARGET) / velocity.length();
+    assert!((bullet_time - target_time).abs() < 1e-3);
+}
+
+#[test]
+fn without_lead_or_motion_the_enemy_aims_straight() {
+    let velocity = Vec3::new(3.0, 0.0, 0.0);
+    let direct = Enemy::new();
+    assert!(!direct.lead);
+    assert_eq!(direct.aim_point(FROM, TARGET, velocity, SPEED), TARGET);
+    let leading = Enemy::new().with_lead(true);
+    let still = leading.aim_point(FROM, TARGET, Vec3::ZERO, SPEED);
+    assert!(still.distance(TARGET) < 1e-4, "{still}");
+}
+
+#[test]
+fn an_uncatchable_target_gets_no_intercept() {
+    // Цель убегает быстрее пули
+    let away = Vec3::new(0.0, 0.0, 10.0);
+    assert_eq!(intercept(FROM, TARGET, away, SPEED), None);
+    let enemy = Enemy::new().with_lead(true);
+    assert_eq!(enemy.aim_point(FROM, TARGET, away, SPEED), TARGET);
+}
+
+#[test]
+fn only_hard_enemies_lead() {
+    assert!(!Difficulty::Easy.predictive_aim());
+    assert!(!Difficulty::Normal.predictive_aim());
+    assert!(Difficulty::Hard.predictive_aim());
+}
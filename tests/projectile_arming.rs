@@ -0,0 +1,52 @@
+// Автотест: пуля безвредна, пока не истечёт время взведения
+// Запуск: cargo test --test projectile_arming
+
+use astroforge::engine::physics::{step, Collider, RigidBody};
+use astroforge::world::{Entity, Hit, Projectile, Tag, World};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+fn step_world(world: &mut World) -> Vec<Hit> {
+    world.tick_projectiles(DT);
+    let mut ids = Vec::new();
+    let mut objs = Vec::new();
+    world.physics_objects(&mut ids, &mut objs);
+    let pairs = step(&mut objs, &[], DT);
+    world.bullet_hits(&ids, &pairs)
+}
+
+#[test]
+fn bullet_hits_only_after_arming() {
+    let mut world = World::new();
+    world.spawn(
+        Entity::new(Tag::Player)
+            .with_body(RigidBody::new(70.0, Vec3::new(0.0, 5.0, 0.0)))
+            .with_collider(Collider::new(Vec3::new(0.5, 0.75, 0.5))),
+    );
+    // Пуля внутри игрока, летит вдоль x и задевает его сверху
+    let mut body = RigidBody::new(0.05, Vec3::new(0.3, 5.8, 0.0));
+    body.velocity = Vec3::new(-5.0, 0.0, 0.0);
+    let bullet = world.spawn(
+        Entity::new(Tag::Bullet)
+            .with_body(body)
+            .with_collider(Collider::new(Vec3::splat(0.1)))
+            .with_projectile(Projectile::new(None).with_arming(0.05)),
+    );
+
+    // Первые кадры: пуля ещё не взведена и не мешает игроку
+    for _ in 0..2 {
+        assert!(step_world(&mut world).is_empty());
+        let p = world.get(bullet).unwrap().projectile.unwrap();
+        assert!(!p.armed());
+        // Чтобы контакт сохранялся, возвращаем пулю на исходную позицию
+        let b = world.get_mut(bullet).unwrap().body.as_mut().unwrap();
+        b.position = Vec3::new(0.3, 5.8, 0.0);
+        b.velocity = Vec3::new(-5.0, 0.0, 0.0);
+    }
+
+    let hits = step_world(&mut world);
+    assert!(world.get(bullet).unwrap().projectile.unwrap().armed());
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].damage > 0);
+}
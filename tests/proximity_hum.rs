@@ -0,0 +1,28 @@
+// Автотест: гул маяка громче и выше по тону, когда игрок ближе
+// Запуск: cargo test --test proximity_hum --features audio
+#![cfg(feature = "audio")]
+
+use astroforge::engine::audio::ProximityHum;
+
+#[test]
+fn closer_distances_give_higher_gain() {
+    let hum = ProximityHum::new(10.0);
+    let gains: Vec<f32> = [12.0, 10.0, 7.5, 5.0, 2.5, 0.0]
+        .iter()
+        .map(|&d| hum.gain(d))
+        .collect();
+    assert_eq!(gains[0], 0.0, "silent outside the radius");
+    assert_eq!(gains[1], 0.0);
+    for pair in gains[1..].windows(2) {
+        assert!(pair[1] > pair[0], "{gains:?}");
+    }
+    assert_eq!(gains[5], hum.max_gain);
+}
+
+#[test]
+fn pitch_rises_toward_the_beacon() {
+    let hum = ProximityHum::new(10.0);
+    assert_eq!(hum.pitch(20.0), hum.min_pitch);
+    assert!(hum.pitch(3.0) > hum.pitch(6.0));
+    assert_eq!(hum.pitch(0.0), hum.max_pitch);
+}
@@ -0,0 +1,44 @@
+// Автотест: выстрел подбрасывает камеру вверх, и отдача затухает к нулю
+// Запуск: cargo test --test recoil
+
+use astroforge::engine::camera::ViewKick;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::weapon::RecoilPattern;
+
+#[test]
+fn firing_kicks_the_view_up_and_it_recovers() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    assert_eq!(game.view_kick.pitch, 0.0);
+
+    assert!(game.fire().is_some());
+    let kicked = game.view_kick.pitch;
+    assert!(kicked > 0.0, "view goes up");
+
+    let mut last = kicked;
+    for _ in 0..60 {
+        game.update(&mut engine, 1.0 / 60.0);
+        assert!(game.view_kick.pitch < last);
+        last = game.view_kick.pitch;
+    }
+    assert!(last < kicked * 0.01, "almost back after a second: {last}");
+}
+
+#[test]
+fn pattern_alternates_sideways_kick() {
+    let pattern = RecoilPattern::new(0.05, 0.01);
+    assert_eq!(pattern.kick(0), (0.05, 0.01));
+    assert_eq!(pattern.kick(1), (0.05, -0.01));
+}
+
+#[test]
+fn kick_decays_by_recovery_per_second() {
+    let mut kick = ViewKick::new();
+    kick.recovery = 0.5;
+    kick.kick(0.2, -0.1);
+    kick.tick(1.0);
+    assert!((kick.pitch - 0.1).abs() < 1e-6);
+    assert!((kick.yaw + 0.05).abs() < 1e-6);
+}
@@ -0,0 +1,109 @@
+// Автотест: возрождение закрывает экран затемнением: посреди перехода
+// прозрачность между 0 и 1, а после него затемнение исчезает
+// Запуск: cargo test --test respawn_fade
+
+use astroforge::config::Level;
+use astroforge::engine::input::InputState;
+use astroforge::engine::overlay::{OverlayQuad, ScreenFade};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use glam::{Mat4, Vec2, Vec3};
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn fade_covers_then_clears() {
+    let mut fade = ScreenFade::new(0.3, 0.4);
+    assert_eq!(fade.alpha(), 0.0);
+    let mut covered_at = Vec::new();
+    let mut alphas = Vec::new();
+    for i in 0..60 {
+        if fade.tick(DT) {
+            covered_at.push(i);
+        }
+        alphas.push(fade.alpha());
+    }
+    // Экран закрывается ровно один раз, на полной темноте
+    assert_eq!(covered_at.len(), 1);
+    assert_eq!(alphas[covered_at[0]], 1.0);
+    assert!(alphas[5] > 0.0 && alphas[5] < 1.0);
+    assert!(alphas[..covered_at[0]].windows(2).all(|w| w[0] < w[1]));
+    assert!(fade.finished());
+    assert_eq!(fade.alpha(), 0.0);
+    let quad = fade.quad(Vec2::new(200.0, 100.0));
+    assert_eq!(quad.center, Vec2::new(100.0, 50.0));
+    assert_eq!(quad.size, Vec2::new(200.0, 100.0));
+}
+
+/// Средняя яркость кадра, 0..255. Вместо сцены под затемнением белый
+/// прямоугольник во весь экран.
+fn brightness(engine: &mut Engine, game: &mut Game) -> f32 {
+    game.render(engine);
+    engine.renderer.update_camera(&Mat4::ZERO);
+    let screen = Vec2::new(64.0, 64.0);
+    engine.renderer.overlay_quads = vec![OverlayQuad::new(screen * 0.5, screen, [1.0; 4])];
+    engine.renderer.crosshair_quads.clear();
+    engine.renderer.render(None, 100, &[]);
+    let frame = engine.renderer.get_frame_rgba8();
+    let sum: u64 = frame.chunks(4).map(|p| p[1] as u64).sum();
+    sum as f32 / (frame.len() / 4) as f32
+}
+
+#[test]
+fn respawn_fades_out_and_back_in() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.player.update(&InputState::default(), 10.0);
+    game.player.body.position = Vec3::new(10.0, 0.75, 10.0);
+    let clear = brightness(&mut engine, &mut game);
+
+    game.respawn_with_fade();
+    for _ in 0..9 {
+        game.update(&mut engine, DT);
+    }
+    // Середина затемнения: экран частично закрыт, игрок ещё на месте
+    let alpha = game.fade.unwrap().alpha();
+    assert!(alpha > 0.0 && alpha < 1.0, "{alpha}");
+    assert_eq!(engine.renderer.fade_quad, None, "set only by render");
+    let mid = brightness(&mut engine, &mut game);
+    assert_eq!(engine.renderer.fade_quad.unwrap().color[3], alpha);
+    assert!(mid < clear - 10.0, "{mid} vs {clear}");
+    assert_ne!(game.player.body.position, Level::Beacon.player_start());
+
+    for _ in 0..60 {
+        game.update(&mut engine, DT);
+    }
+    assert!(game.fade.is_none());
+    let start = Level::Beacon.player_start();
+    assert!(game.player.body.position.distance(start) < 0.5);
+    game.render(&mut engine);
+    assert_eq!(engine.renderer.fade_quad, None);
+}
+
+#[test]
+fn fall_momentum_is_gone_after_the_cut() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    game.respawn_with_fade();
+    // Каждый кадр игрок будто всё ещё падает на большой скорости
+    let start = Level::Beacon.player_start();
+    for _ in 0..60 {
+        if game.player.body.position.distance(start) < 0.5 {
+            break;
+        }
+        game.player.body.position = Vec3::new(5.0, -20.0, 5.0);
+        game.player.body.velocity = Vec3::new(3.0, -30.0, 0.0);
+        game.update(&mut engine, DT);
+    }
+    // После переноса прошёл один шаг физики с места
+    let body = game.player.body;
+    assert!(body.position.distance(start) < 0.5, "{}", body.position);
+    assert!(
+        body.velocity.length() < 1.0,
+        "kept fall velocity {}",
+        body.velocity
+    );
+    assert_eq!(game.player.position, body.position);
+}
@@ -0,0 +1,71 @@
+// Автотест: в режиме reversed-Z глубина очищается в 0, сравнивается через
+// Greater, а ближний куб по-прежнему закрывает дальний
+// Запуск: cargo test --test reversed_z
+
+use astroforge::engine::renderer::{CubeInstance, DepthMode};
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 200;
+const NEAR: [f32; 3] = [1.0, 0.0, 0.0];
+const FAR: [f32; 3] = [0.0, 1.0, 0.0];
+
+#[test]
+fn reversed_mode_flips_clear_and_compare() {
+    assert_eq!(DepthMode::default(), DepthMode::Standard);
+    assert_eq!(DepthMode::Standard.clear_value(), 1.0);
+    assert_eq!(DepthMode::Standard.compare(), wgpu::CompareFunction::Less);
+    assert_eq!(DepthMode::Reversed.clear_value(), 0.0);
+    assert_eq!(
+        DepthMode::Reversed.compare(),
+        wgpu::CompareFunction::Greater
+    );
+}
+
+#[test]
+fn reversed_projection_puts_near_at_one() {
+    let proj = DepthMode::Reversed.perspective(1.0, 1.0, 0.1, 100.0);
+    let depth = |z: f32| proj.project_point3(Vec3::new(0.0, 0.0, -z)).z;
+    assert!((depth(0.1) - 1.0).abs() < 1e-5, "{}", depth(0.1));
+    assert!(depth(100.0).abs() < 1e-5, "{}", depth(100.0));
+    assert!(depth(5.0) > depth(10.0));
+}
+
+/// Пиксели в центре кадра и правее, за краем ближнего куба.
+fn pixels(mode: DepthMode, cubes: &[CubeInstance]) -> ([u8; 3], [u8; 3]) {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    engine.renderer.set_depth_mode(mode);
+    assert_eq!(engine.renderer.depth_mode(), mode);
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = engine.renderer.perspective(60f32.to_radians(), 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    engine.renderer.render(None, 100, cubes);
+    let frame = engine.renderer.get_frame_rgba8();
+    let pixel = |x: u32| {
+        let i = ((SIZE / 2 * SIZE + x) * 4) as usize;
+        [frame[i], frame[i + 1], frame[i + 2]]
+    };
+    (pixel(SIZE / 2), pixel(SIZE / 2 + 38))
+}
+
+#[test]
+fn near_cube_hides_far_cube_in_both_modes() {
+    let near = CubeInstance::new(Vec3::new(0.0, 5.0, 0.0), 1.0, NEAR);
+    let far = CubeInstance::new(Vec3::new(0.0, 5.0, -10.0), 8.0, FAR);
+    for mode in [DepthMode::Standard, DepthMode::Reversed] {
+        // Порядок отрисовки не важен: решает тест глубины
+        for cubes in [[near, far], [far, near]] {
+            let (center, side) = pixels(mode, &cubes);
+            assert!(
+                center[0] > 200 && center[1] < 50 && center[2] < 50,
+                "{mode:?}: far cube shows through: {center:?}"
+            );
+            // Дальний куб всё же нарисован вокруг ближнего
+            assert!(
+                side[0] < 50 && side[1] > 200 && side[2] < 50,
+                "{mode:?}: far cube missing: {side:?}"
+            );
+        }
+    }
+}
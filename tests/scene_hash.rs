@@ -0,0 +1,78 @@
+// Автотест: одинаковые симуляции дают одинаковый хеш сцены, возмущённая — другой
+// Запуск: cargo test --test scene_hash
+
+use astroforge::engine::physics::{scene_hash, step, Aabb, Collider, PhysicsObject, RigidBody};
+use glam::Vec3;
+
+/// Три тела падают на пол и толкаются. `nudge` сдвигает первое тело.
+fn simulate(nudge: f32) -> u64 {
+    let mut bodies = [
+        RigidBody::new(80.0, Vec3::new(nudge, 1.0, 2.0)),
+        RigidBody::new(80.0, Vec3::new(0.4, 2.2, 2.3)),
+        RigidBody::new(0.05, Vec3::new(1.0, 1.5, 2.0)),
+    ];
+    bodies[2].velocity = Vec3::new(-5.0, 0.0, 0.1);
+    let collider = Collider::new(Vec3::new(0.5, 0.75, 0.5));
+    let floor = [Aabb::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(50.0, 0.5, 50.0),
+    )];
+    let mut objs: Vec<PhysicsObject> = bodies
+        .iter_mut()
+        .map(|body| PhysicsObject { body, collider })
+        .collect();
+    for _ in 0..120 {
+        step(&mut objs, &floor, 1.0 / 60.0);
+    }
+    scene_hash(&objs)
+}
+
+#[test]
+fn identical_simulations_hash_equal() {
+    assert_eq!(simulate(0.0), simulate(0.0));
+}
+
+#[test]
+fn perturbed_simulation_hashes_differently() {
+    assert_ne!(simulate(0.0), simulate(1e-4));
+}
+
+#[test]
+fn hash_depends_on_velocity_and_order() {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut a = RigidBody::new(1.0, Vec3::X);
+    let mut b = RigidBody::new(1.0, Vec3::Y);
+    let forward = scene_hash(&[
+        PhysicsObject {
+            body: &mut a,
+            collider,
+        },
+        PhysicsObject {
+            body: &mut b,
+            collider,
+        },
+    ]);
+    let backward = scene_hash(&[
+        PhysicsObject {
+            body: &mut b,
+            collider,
+        },
+        PhysicsObject {
+            body: &mut a,
+            collider,
+        },
+    ]);
+    assert_ne!(forward, backward);
+    a.velocity = Vec3::Z;
+    let moving = scene_hash(&[
+        PhysicsObject {
+            body: &mut a,
+            collider,
+        },
+        PhysicsObject {
+            body: &mut b,
+            collider,
+        },
+    ]);
+    assert_ne!(forward, moving);
+}
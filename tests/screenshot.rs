@@ -0,0 +1,39 @@
+// Автотест: захват кадра и сохранение скриншота в PNG
+// Запуск: cargo test --test screenshot
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::screenshot::{save_png, timestamped_path};
+use astroforge::engine::Engine;
+use glam::Vec3;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn captured_frame_is_saved_as_png_of_window_size() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let (width, height) = (320u32, 240u32);
+    let mut engine = Engine::new_headless(width, height);
+    let cube = CubeInstance::new(Vec3::new(0.0, 0.0, 0.5), 0.5, [1.0, 0.0, 0.0]);
+
+    // Без запроса кадр не сохраняется
+    engine.renderer.render(None, 100, &[cube]);
+    assert!(engine.renderer.take_capture().is_none());
+
+    engine.renderer.request_capture();
+    engine.renderer.render(None, 100, &[cube]);
+    let frame = engine.renderer.take_capture().expect("frame captured");
+    assert_eq!(frame.len(), (width * height * 4) as usize);
+    assert_eq!(frame, engine.renderer.get_frame_rgba8());
+
+    let dir = std::env::temp_dir().join(format!("astroforge_screenshot_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = timestamped_path(&dir, UNIX_EPOCH + Duration::from_millis(1234));
+    assert_eq!(path, dir.join("screenshot_1234.png"));
+    save_png(&frame, width, height, &path).unwrap();
+
+    let img = image::open(&path).expect("valid PNG").to_rgba8();
+    assert_eq!(img.dimensions(), (width, height));
+    assert_eq!(img.into_raw(), frame);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!Path::new(&path).exists());
+}
@@ -0,0 +1,69 @@
+// Автотест: снимки мира — apply(diff(a, b)) поверх a даёт b, сериализация обратима
+// Запуск: cargo test --test snapshot_sync
+
+use astroforge::engine::physics::RigidBody;
+use astroforge::engine::snapshot::{SnapshotDelta, WorldSnapshot};
+use glam::Vec3;
+
+fn bodies() -> Vec<RigidBody> {
+    let mut player = RigidBody::new(80.0, Vec3::new(0.0, 1.0, 2.0));
+    player.velocity = Vec3::new(1.0, 0.0, -0.5);
+    let enemy = RigidBody::new(80.0, Vec3::new(8.0, 0.75, -8.0));
+    vec![player, enemy]
+}
+
+#[test]
+fn apply_diff_reproduces_newer_snapshot() {
+    let mut world = bodies();
+    let a = WorldSnapshot::capture(1, &world);
+    world[0].position.x += 0.25;
+    world[0].velocity.y = -3.0;
+    let b = WorldSnapshot::capture(2, &world);
+
+    let delta = a.diff(&b);
+    assert_eq!(delta.changed.len(), 1, "only the moved body should be sent");
+    let mut client = a.clone();
+    client.apply(&delta);
+    assert_eq!(client, b);
+}
+
+#[test]
+fn apply_diff_handles_spawn_and_despawn() {
+    let mut world = bodies();
+    let a = WorldSnapshot::capture(1, &world);
+    world.push(RigidBody::new(0.05, Vec3::new(7.5, 1.35, -7.5)));
+    let b = WorldSnapshot::capture(2, &world);
+    world.truncate(1);
+    let c = WorldSnapshot::capture(3, &world);
+
+    let mut client = a.clone();
+    client.apply(&a.diff(&b));
+    assert_eq!(client, b);
+    client.apply(&b.diff(&c));
+    assert_eq!(client, c);
+}
+
+#[test]
+fn snapshot_and_delta_roundtrip_through_bytes() {
+    let mut world = bodies();
+    let a = WorldSnapshot::capture(7, &world);
+    assert_eq!(WorldSnapshot::from_bytes(&a.to_bytes()), Some(a.clone()));
+
+    world[1].position.z += 1.0;
+    let delta = a.diff(&WorldSnapshot::capture(8, &world));
+    assert_eq!(SnapshotDelta::from_bytes(&delta.to_bytes()), Some(delta));
+
+    let bytes = a.to_bytes();
+    assert_eq!(WorldSnapshot::from_bytes(&bytes[..bytes.len() - 1]), None);
+}
+
+#[test]
+fn restore_writes_state_back_to_bodies() {
+    let world = bodies();
+    let snap = WorldSnapshot::capture(1, &world);
+    let mut fresh = vec![RigidBody::new(80.0, Vec3::ZERO); 2];
+    snap.restore(&mut fresh);
+    assert_eq!(fresh[0].position, world[0].position);
+    assert_eq!(fresh[0].velocity, world[0].velocity);
+    assert_eq!(fresh[1].position, world[1].position);
+}
@@ -0,0 +1,78 @@
+// Автотест: несколько проходов решателя оставляют меньше взаимопроникновения
+// Запуск: cargo test --test solver_iterations
+
+use astroforge::engine::physics::{Aabb, Collider, PhysicsObject, PhysicsWorld, RigidBody};
+use glam::Vec3;
+
+/// Тело зажато между двумя соседями вдоль x. Возвращает самое глубокое
+/// взаимопроникновение после одного шага и найденные пары.
+fn squeeze(iterations: usize) -> (f32, Vec<(usize, usize)>) {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut bodies = [-0.9, 0.0, 0.9].map(|x| RigidBody::new(80.0, Vec3::new(x, 5.0, 0.0)));
+    let world = PhysicsWorld::new().with_solver_iterations(iterations);
+    let mut objs: Vec<PhysicsObject> = bodies
+        .iter_mut()
+        .map(|body| PhysicsObject { body, collider })
+        .collect();
+    let pairs = world.step(&mut objs, &[], 1.0 / 60.0);
+    let boxes: Vec<Aabb> = bodies
+        .iter()
+        .map(|b| Aabb::around(b.position, &collider))
+        .collect();
+    let mut deepest = 0.0f32;
+    for i in 0..boxes.len() {
+        for j in (i + 1)..boxes.len() {
+            deepest = deepest.max(boxes[i].penetration(&boxes[j]));
+        }
+    }
+    (deepest, pairs)
+}
+
+#[test]
+fn more_iterations_leave_less_penetration() {
+    let (single, _) = squeeze(1);
+    let (several, _) = squeeze(8);
+    assert!(single > 0.01, "one pass already separated the bodies");
+    assert!(
+        several < single * 0.5,
+        "1 pass: {single}, 8 passes: {several}"
+    );
+}
+
+#[test]
+fn pairs_are_reported_once() {
+    let (_, pairs) = squeeze(8);
+    assert_eq!(pairs, vec![(0, 1), (1, 2)]);
+}
+
+#[test]
+fn separated_bodies_stop_the_passes_early() {
+    // Без пересечений лишние проходы ничего не меняют
+    let collider = Collider::new(Vec3::splat(0.5));
+    let run = |iterations| {
+        let mut bodies = [-2.0, 2.0].map(|x| RigidBody::new(80.0, Vec3::new(x, 5.0, 0.0)));
+        let mut objs: Vec<PhysicsObject> = bodies
+            .iter_mut()
+            .map(|body| PhysicsObject { body, collider })
+            .collect();
+        let pairs =
+            PhysicsWorld::new()
+                .with_solver_iterations(iterations)
+                .step(&mut objs, &[], 1.0 / 60.0);
+        assert!(pairs.is_empty());
+        bodies.map(|b| b.position)
+    };
+    assert_eq!(run(1), run(8));
+}
+
+#[test]
+fn penetration_is_the_shallowest_overlap() {
+    let a = Aabb::new(Vec3::ZERO, Vec3::splat(0.5));
+    let b = Aabb::new(Vec3::new(0.9, 0.2, 0.0), Vec3::splat(0.5));
+    assert!((a.penetration(&b) - 0.1).abs() < 1e-6);
+    let apart = Aabb {
+        center: Vec3::new(3.0, 0.0, 0.0),
+        ..b
+    };
+    assert_eq!(a.penetration(&apart), 0.0);
+}
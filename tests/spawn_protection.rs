@@ -0,0 +1,59 @@
+// Автотест: сразу после появления и возрождения игрок не получает урон,
+// позже — получает
+// Запуск: cargo test --test spawn_protection
+
+use astroforge::engine::input::InputState;
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions, SPAWN_PROTECTION};
+use astroforge::player::Player;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn damage_is_ignored_during_spawn_protection() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+    assert!(game.player.invulnerable());
+    assert!(!game.damage_player(20));
+    assert_eq!(game.health, 100);
+
+    // HUD показывает оставшееся время защиты
+    game.render(&mut engine);
+    let shown = engine.renderer.hud_protection.unwrap();
+    assert!((shown - SPAWN_PROTECTION).abs() < 1e-6);
+
+    let frames = (SPAWN_PROTECTION / DT).ceil() as usize + 1;
+    for _ in 0..frames {
+        game.update(&mut engine, DT);
+    }
+    assert!(!game.player.invulnerable());
+    assert!(game.damage_player(20));
+    assert_eq!(game.health, 80);
+    game.render(&mut engine);
+    assert_eq!(engine.renderer.hud_protection, None);
+}
+
+#[test]
+fn protect_keeps_the_longer_window() {
+    let mut player = Player::new();
+    assert!(!player.invulnerable());
+    player.protect(2.0);
+    player.protect(0.5);
+    assert_eq!(player.protection_remaining(), 2.0);
+    player.protect(3.0);
+    assert_eq!(player.protection_remaining(), 3.0);
+}
+
+#[test]
+fn respawning_protects_again() {
+    let mut game = Game::new(GameOptions::default());
+    game.spawn_protection = 1.0;
+    game.player.update(&InputState::default(), 10.0);
+    assert!(game.damage_player(20));
+
+    game.respawn_player();
+    assert_eq!(game.player.protection_remaining(), 1.0);
+    assert!(!game.damage_player(20));
+    assert_eq!(game.health, 80);
+}
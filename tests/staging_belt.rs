@@ -0,0 +1,26 @@
+// Автотест: много кадров подряд с текстом HUD рисуются без сбоев и дают
+// тот же кадр, что и первый, хотя память загрузки переиспользуется
+// Запуск: cargo test --test staging_belt
+
+use astroforge::engine::Engine;
+use glam::Mat4;
+
+#[test]
+fn many_renders_reuse_the_staging_belt() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(200, 150);
+    let renderer = &mut engine.renderer;
+    renderer.update_camera(&Mat4::ZERO);
+    renderer.hud_ammo = Some((7, 21));
+    renderer.hud_debug = Some("pos: 1.00 2.00 3.00\nspeed: 0.00".to_string());
+    renderer.render(Some("Beacon"), 80, &[]);
+    let first = renderer.get_frame_rgba8();
+    assert!(first.iter().any(|&c| c > 60), "nothing drawn");
+
+    for i in 0..500 {
+        // Меняющийся текст заставляет каждый кадр заново загружать вершины
+        renderer.render(Some(&format!("Frame {i}")), i % 100, &[]);
+    }
+    renderer.render(Some("Beacon"), 80, &[]);
+    assert_eq!(renderer.get_frame_rgba8(), first);
+}
@@ -0,0 +1,72 @@
+// Автотест: бег расходует выносливость до нуля, затем сила бега обычная, а в покое выносливость восстанавливается
+// Запуск: cargo test --test stamina
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::engine::Engine;
+use astroforge::player::Player;
+use glam::Vec3;
+use winit::event::VirtualKeyCode;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Движущая сила за один кадр при заданных клавишах.
+fn frame_force(player: &mut Player, input: &InputState) -> f32 {
+    player.body.force = Vec3::ZERO;
+    player.body.velocity = Vec3::ZERO;
+    player.update(input, DT);
+    player.body.force.length()
+}
+
+#[test]
+fn sprint_drains_stamina_then_falls_back_to_walking() {
+    let mut player = Player::new();
+    let mut input = InputState::default();
+    input.apply_frame(&InputFrame::holding(&[
+        VirtualKeyCode::W,
+        VirtualKeyCode::LControl,
+    ]));
+    let sprint = frame_force(&mut player, &input);
+    assert!((sprint - player.movement_force * player.sprint_multiplier).abs() < 1e-3);
+    assert!(player.stamina.current < player.stamina.max);
+
+    for _ in 0..600 {
+        frame_force(&mut player, &input);
+    }
+    assert_eq!(player.stamina.current, 0.0);
+    let tired = frame_force(&mut player, &input);
+    assert!(
+        (tired - player.movement_force).abs() < 1e-3,
+        "walking force: {tired}"
+    );
+    assert!(!player.dash(Vec3::X), "no stamina left for a dash");
+}
+
+#[test]
+fn stamina_regenerates_while_idle() {
+    let mut player = Player::new();
+    player.stamina.current = 0.0;
+    let idle = InputState::default();
+    frame_force(&mut player, &idle);
+    let after_one = player.stamina.current;
+    assert!(after_one > 0.0);
+    for _ in 0..1200 {
+        frame_force(&mut player, &idle);
+    }
+    assert_eq!(player.stamina.current, player.stamina.max);
+}
+
+#[test]
+fn dash_costs_stamina() {
+    let mut player = Player::new();
+    let full = player.stamina.current;
+    assert!(player.dash(Vec3::X));
+    assert!(player.stamina.current < full);
+}
+
+#[test]
+fn stamina_is_shown_on_the_hud() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let engine = Engine::new_headless(320, 240);
+    let section = engine.renderer.stamina_section(0.42);
+    assert_eq!(section.text[1].text, "42%");
+}
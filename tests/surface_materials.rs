@@ -0,0 +1,48 @@
+// Автотест: луч и контакты сообщают материал поверхности, в которую попали
+// Запуск: cargo test --test surface_materials
+
+use astroforge::engine::physics::{raycast, Aabb, Collider, Contact, Material};
+use astroforge::game::{Game, GameOptions};
+use astroforge::player::Player;
+use astroforge::weapon::Hitscan;
+use glam::Vec3;
+
+#[test]
+fn raycast_reports_the_material_it_struck() {
+    let metal =
+        Aabb::new(Vec3::new(0.0, 0.0, -5.0), Vec3::splat(0.5)).with_material(Material::Metal);
+    let floor = Aabb::new(Vec3::new(0.0, -1.0, 0.0), Vec3::new(10.0, 0.5, 10.0))
+        .with_material(Material::Floor);
+    let boxes = [floor, metal];
+
+    let hit = raycast(Vec3::ZERO, Vec3::NEG_Z, 20.0, &boxes).unwrap();
+    assert_eq!(hit.index, 1);
+    assert_eq!(hit.material, Material::Metal);
+
+    let hit = raycast(Vec3::ZERO, Vec3::NEG_Y, 20.0, &boxes).unwrap();
+    assert_eq!(hit.material, Material::Floor);
+    assert_eq!(Aabb::new(Vec3::ZERO, Vec3::ONE).material, Material::Generic);
+}
+
+#[test]
+fn level_geometry_and_contacts_carry_materials() {
+    let game = Game::new(GameOptions::default());
+    let obstacles = game.static_obstacles();
+    assert!(obstacles.iter().any(|b| b.material == Material::Floor));
+    assert!(Player::artifact_aabbs(12)
+        .iter()
+        .all(|b| b.material == Material::Artifact));
+
+    // Выстрел в пол сообщает материал пола
+    let shot = Hitscan::new().fire(Vec3::new(0.0, 2.0, 0.0), Vec3::NEG_Y, &obstacles, &[]);
+    assert_eq!(shot.material, Some(Material::Floor));
+    let miss = Hitscan::new().fire(Vec3::new(0.0, 2.0, 0.0), Vec3::Y, &obstacles, &[]);
+    assert_eq!(miss.material, None);
+
+    // Коллайдер передаёт материал своему боксу и контакту
+    let crate_box = Collider::new(Vec3::splat(0.5)).with_material(Material::Metal);
+    let a = Aabb::around(Vec3::ZERO, &crate_box);
+    let b = Aabb::around(Vec3::new(0.8, 0.0, 0.0), &Collider::new(Vec3::splat(0.5)));
+    let contact = Contact::between(0, 1, &a, &b).unwrap();
+    assert_eq!(contact.materials, [Material::Metal, Material::Generic]);
+}
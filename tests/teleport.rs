@@ -0,0 +1,88 @@
+// Автотест: телепорт внутрь препятствия выталкивает тело в ближайшую свободную точку
+// Запуск: cargo test --test teleport
+
+use astroforge::config::Level;
+use astroforge::engine::physics::{teleport, Aabb, Collider, RigidBody};
+use astroforge::game::{Game, GameOptions};
+use glam::Vec3;
+
+fn wall() -> Aabb {
+    Aabb::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.5, 1.0, 3.0))
+}
+
+#[test]
+fn teleport_into_an_obstacle_resolves_outside_it() {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut body = RigidBody::new(1.0, Vec3::new(10.0, 1.0, 0.0));
+    body.velocity = Vec3::new(7.0, -3.0, 0.0);
+    let obstacles = [wall()];
+
+    assert!(teleport(
+        &mut body,
+        &collider,
+        Vec3::new(0.2, 1.0, 0.5),
+        &obstacles
+    ));
+    assert!(!obstacles[0].overlaps(&Aabb::around(body.position, &collider)));
+    // Ближайший выход — по X, в сторону смещения
+    assert_eq!(body.position, Vec3::new(1.0, 1.0, 0.5));
+    assert_eq!(body.velocity, Vec3::ZERO);
+}
+
+#[test]
+fn teleport_resolves_out_of_overlapping_obstacles() {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let obstacles = [
+        wall(),
+        Aabb::new(Vec3::new(1.5, 1.0, 0.0), Vec3::new(0.5, 1.0, 3.0)),
+    ];
+    let mut body = RigidBody::new(1.0, Vec3::ZERO);
+    assert!(teleport(
+        &mut body,
+        &collider,
+        Vec3::new(0.9, 1.0, 2.0),
+        &obstacles
+    ));
+    let bounds = Aabb::around(body.position, &collider);
+    assert!(
+        obstacles.iter().all(|o| !o.overlaps(&bounds)),
+        "{:?}",
+        body.position
+    );
+}
+
+#[test]
+fn free_destination_is_kept() {
+    let collider = Collider::new(Vec3::splat(0.5));
+    let mut body = RigidBody::new(1.0, Vec3::ZERO);
+    assert!(teleport(
+        &mut body,
+        &collider,
+        Vec3::new(5.0, 1.0, 0.0),
+        &[wall()]
+    ));
+    assert_eq!(body.position, Vec3::new(5.0, 1.0, 0.0));
+}
+
+#[test]
+fn respawn_teleports_the_player_to_the_start_at_rest() {
+    let mut game = Game::new(GameOptions::default());
+    // Игрок падает: скорость, накопленная сила и касание земли от падения
+    let body = &mut game.player.body;
+    body.position = Vec3::new(30.0, -40.0, 30.0);
+    body.velocity = Vec3::new(1.0, -25.0, 0.0);
+    body.force = Vec3::new(0.0, -500.0, 0.0);
+    body.on_ground = true;
+
+    game.respawn_player();
+    let body = game.player.body;
+    let start = Level::Beacon.player_start();
+    // Старт задевает препятствия уровня, и тело чуть сдвигается наружу
+    assert!(body.position.distance(start) < 0.5, "{}", body.position);
+    assert_eq!(game.player.position, body.position);
+    assert_eq!(body.velocity, Vec3::ZERO);
+    assert_eq!(body.force, Vec3::ZERO);
+    assert!(!body.on_ground);
+    let bounds = Aabb::around(body.position, &game.player.collider);
+    assert!(game.static_obstacles().iter().all(|o| !o.overlaps(&bounds)));
+}
@@ -0,0 +1,94 @@
+// Автотест: обводка текста HUD даёт тёмные пиксели вплотную к светлым буквам
+// Запуск: cargo test --test text_outline
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::text::TextStyle;
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 400;
+
+fn pixel(frame: &[u8], x: u32, y: u32) -> [u8; 3] {
+    let i = ((y * SIZE + x) * 4) as usize;
+    [frame[i], frame[i + 1], frame[i + 2]]
+}
+
+/// Буквы сообщения жёлтые: [1, 1, 0.5].
+fn is_glyph(p: [u8; 3]) -> bool {
+    p[0] > 200 && p[1] > 200 && p[2] < 180
+}
+
+fn is_dark(p: [u8; 3]) -> bool {
+    p.iter().all(|&c| c < 60)
+}
+
+/// Тёмные пиксели, у которых среди соседей есть пиксель буквы.
+fn dark_next_to_glyphs(frame: &[u8]) -> usize {
+    let mut count = 0;
+    for y in 21..80 {
+        for x in 21..SIZE - 21 {
+            if !is_dark(pixel(frame, x, y)) {
+                continue;
+            }
+            let near = (-1i32..=1).any(|dy| {
+                (-1i32..=1).any(|dx| {
+                    is_glyph(pixel(frame, (x as i32 + dx) as u32, (y as i32 + dy) as u32))
+                })
+            });
+            if near {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn render(style: TextStyle) -> Vec<u8> {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    // Белый куб закрывает весь кадр: светлый фон, на котором текст теряется
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 4.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    engine.renderer.text_style = style;
+    let cube = CubeInstance::new(Vec3::new(0.0, 5.0, 0.0), 6.0, [1.0, 1.0, 1.0]);
+    engine
+        .renderer
+        .render(Some("Маяк активирован"), 100, &[cube]);
+    engine.renderer.get_frame_rgba8()
+}
+
+#[test]
+fn outline_puts_dark_pixels_around_glyphs() {
+    let plain = render(TextStyle::Plain);
+    let outlined = render(TextStyle::outline());
+    let glyphs = (21..80)
+        .flat_map(|y| (21..SIZE - 21).map(move |x| (x, y)))
+        .filter(|&(x, y)| is_glyph(pixel(&outlined, x, y)))
+        .count();
+    assert!(glyphs > 200, "glyph pixels: {glyphs}");
+    assert_eq!(dark_next_to_glyphs(&plain), 0);
+    let dark = dark_next_to_glyphs(&outlined);
+    assert!(dark > 100, "dark pixels next to glyphs: {dark}");
+}
+
+#[test]
+fn styles_queue_backing_copies_before_the_text() {
+    use wgpu_glyph::{OwnedSection, OwnedText};
+    let section = OwnedSection {
+        screen_position: (10.0, 20.0),
+        text: vec![OwnedText::new("HP").with_color([1.0, 1.0, 0.5, 1.0])],
+        ..OwnedSection::default()
+    };
+    assert_eq!(TextStyle::Plain.apply(&section), vec![section.clone()]);
+
+    let shadowed = TextStyle::shadow().apply(&section);
+    assert_eq!(shadowed.len(), 2);
+    assert_eq!(shadowed[0].screen_position, (12.0, 22.0));
+    assert_eq!(shadowed[0].text[0].extra.color, [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(shadowed[1], section);
+
+    let outlined = TextStyle::outline().apply(&section);
+    assert_eq!(outlined.len(), 9);
+    assert_eq!(outlined.last(), Some(&section));
+}
@@ -0,0 +1,38 @@
+// Автотест: длинный текст переносится по ширине, а '\n' начинает новую строку
+// Запуск: cargo test --test text_wrap
+
+use astroforge::engine::text::{line_height, measure_text, wrap_text};
+use wgpu_glyph::ab_glyph::FontArc;
+
+fn font() -> FontArc {
+    FontArc::try_from_vec(std::fs::read("assets/DejaVuSans.ttf").expect("read font")).unwrap()
+}
+
+#[test]
+fn long_text_wraps_to_multiple_lines() {
+    let font = font();
+    let short = "Маяк";
+    let long = "Технология разблокирована: энергетический маяк излучает сигнал по всей арене";
+    let (_, short_h) = measure_text(&font, short, 36.0, 300.0, 1.0);
+    let (long_w, long_h) = measure_text(&font, long, 36.0, 300.0, 1.0);
+    assert!(
+        long_h > short_h,
+        "wrapped text should be taller: {long_h} <= {short_h}"
+    );
+    assert!(wrap_text(&font, long, 36.0, 300.0).len() > 1);
+    assert!(
+        long_w <= 300.0,
+        "wrapped line is wider than the bound: {long_w}"
+    );
+}
+
+#[test]
+fn newlines_and_spacing() {
+    let font = font();
+    let lines = wrap_text(&font, "Первая строка\nВторая строка", 28.0, 1000.0);
+    assert_eq!(lines, vec!["Первая строка", "Вторая строка"]);
+    let (_, h1) = measure_text(&font, "a\nb", 28.0, 1000.0, 1.0);
+    let (_, h2) = measure_text(&font, "a\nb", 28.0, 1000.0, 1.5);
+    assert!((h1 - 2.0 * line_height(&font, 28.0)).abs() < 0.001);
+    assert!((h2 - 1.5 * h1).abs() < 0.001);
+}
@@ -0,0 +1,64 @@
+// Автотест: при шкале времени 0.5 тело за реальную секунду пролетает вдвое
+// меньше, а при 0 стоит на месте, хотя кадры рисуются, а затемнение
+// возрождения и хитмаркер идут в реальном времени
+// Запуск: cargo test --test time_scale
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::hitmarker::HitKind;
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Сдвиг по x бочки, брошенной вдоль +X, за `frames` кадров реального
+/// времени при шкале `scale`.
+fn distance(engine: &mut Engine, scale: f32, frames: usize) -> f32 {
+    let mut game = Game::new(GameOptions::default());
+    game.time_scale = scale;
+    let start = Vec3::new(20.0, 30.0, 20.0);
+    let barrel = game.spawn_barrel(start);
+    game.world
+        .get_mut(barrel)
+        .unwrap()
+        .body
+        .as_mut()
+        .unwrap()
+        .velocity = Vec3::new(4.0, 0.0, 0.0);
+    for _ in 0..frames {
+        game.frame(engine, DT);
+    }
+    game.world.get(barrel).unwrap().body.unwrap().position.x - start.x
+}
+
+#[test]
+fn half_time_scale_covers_half_the_distance() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(160, 120);
+    let full = distance(&mut engine, 1.0, 60);
+    let half = distance(&mut engine, 0.5, 60);
+    assert!(full > 3.0, "{full}");
+    assert!((half - full * 0.5).abs() < 0.05, "{half} vs {full}");
+    // Полсекунды на полной скорости — то же самое, что секунда на половинной
+    let half_second = distance(&mut engine, 1.0, 30);
+    assert!((half - half_second).abs() < 1e-4, "{half} vs {half_second}");
+
+    assert_eq!(distance(&mut engine, 0.0, 60), 0.0, "moved while frozen");
+}
+
+#[test]
+fn screen_effects_finish_while_time_stands_still() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(160, 120);
+    let mut game = Game::new(GameOptions::default());
+    game.time_scale = 0.0;
+    let start = game.player.body.position;
+    game.player.body.position = Vec3::new(10.0, 5.0, 10.0);
+    game.respawn_with_fade();
+    game.confirm_hit(HitKind::Kill);
+    for _ in 0..60 {
+        game.frame(&mut engine, DT);
+    }
+    assert!(game.fade.is_none(), "fade stuck at time scale 0");
+    assert!(game.hitmarker.is_none(), "hitmarker stuck at time scale 0");
+    assert!(game.player.body.position.distance(start) < 0.5);
+}
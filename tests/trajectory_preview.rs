@@ -0,0 +1,52 @@
+// Автотест: предсказанная траектория снаряда совпадает с настоящей симуляцией
+// Запуск: cargo test --test trajectory_preview
+
+use astroforge::engine::physics::{
+    predict_trajectory, predict_trajectory_until, step, Aabb, Collider, PhysicsObject, RigidBody,
+    GRAVITY,
+};
+use glam::Vec3;
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn predicted_path_matches_simulation() {
+    let start = Vec3::new(0.0, 2.0, 0.0);
+    let velocity = Vec3::new(4.0, 6.0, -1.5);
+    let path = predict_trajectory(start, velocity, Vec3::new(0.0, -GRAVITY, 0.0), 90, DT);
+    assert_eq!(path.len(), 91);
+    assert_eq!(path[0], start);
+
+    let mut body = RigidBody::new(1.0, start);
+    body.velocity = velocity;
+    for expected in &path[1..] {
+        let mut objs = vec![PhysicsObject {
+            body: &mut body,
+            collider: Collider::new(Vec3::splat(0.1)),
+        }];
+        step(&mut objs, &[], DT);
+        assert!(
+            (body.position - *expected).length() < 1e-4,
+            "{:?} vs {expected:?}",
+            body.position
+        );
+    }
+}
+
+#[test]
+fn path_stops_at_the_first_obstacle() {
+    let floor = Aabb::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(50.0, 0.5, 50.0));
+    let gravity = Vec3::new(0.0, -GRAVITY, 0.0);
+    let path = predict_trajectory_until(
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::X * 3.0,
+        gravity,
+        600,
+        DT,
+        &[floor],
+    );
+    assert!(path.len() < 601);
+    let last = *path.last().unwrap();
+    assert!(floor.contains(last));
+    assert!(path[..path.len() - 1].iter().all(|p| !floor.contains(*p)));
+}
@@ -0,0 +1,43 @@
+// Автотест: сообщение «печатается» постепенно и со временем показывается целиком
+// Запуск: cargo test --test typewriter
+
+use astroforge::engine::text::{reveal, Typewriter};
+use astroforge::engine::timer::Timer;
+
+#[test]
+fn revealed_text_grows_with_time() {
+    let text = "Технология разблокирована";
+    let mut prev = 0;
+    for frame in 0..=60 {
+        let shown = reveal(text, frame as f32 / 60.0, 30.0);
+        let len = shown.chars().count();
+        assert!(len >= prev, "revealed text shrank at frame {frame}");
+        assert!(text.starts_with(shown));
+        prev = len;
+    }
+    assert_eq!(reveal(text, 0.0, 30.0), "");
+    assert_eq!(reveal(text, 10.0, 30.0), text);
+}
+
+#[test]
+fn typewriter_reveals_fully_then_expires() {
+    let mut msg = Typewriter::new("Маяк", 4.0, 1.0);
+    assert_eq!(msg.visible(), "");
+    msg.tick(0.5);
+    assert_eq!(msg.visible(), "Ма");
+    msg.tick(0.5);
+    assert_eq!(msg.visible(), "Маяк");
+    assert!(!msg.finished());
+    msg.tick(1.0);
+    assert!(msg.finished());
+}
+
+#[test]
+fn timer_reports_finish_once() {
+    let mut timer = Timer::new(1.0);
+    assert!(!timer.tick(0.6));
+    assert!(timer.tick(0.6));
+    assert!(!timer.tick(0.6));
+    assert!(timer.finished());
+    assert_eq!(timer.remaining(), 0.0);
+}
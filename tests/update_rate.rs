@@ -0,0 +1,40 @@
+// Автотест: ограничение частоты обновлений пропускает лишние кадры
+// Запуск: cargo test --test update_rate
+
+use astroforge::engine::timer::RateLimiter;
+
+fn updates(limit: &mut RateLimiter, frame_rate: f32, frames: usize) -> usize {
+    (0..frames).filter(|_| limit.tick(1.0 / frame_rate)).count()
+}
+
+#[test]
+fn cap_halves_updates_at_double_the_frame_rate() {
+    let mut limit = RateLimiter::new(120.0);
+    assert_eq!(updates(&mut limit, 240.0, 240), 120);
+}
+
+#[test]
+fn slower_frames_update_every_time() {
+    let mut limit = RateLimiter::new(120.0);
+    assert_eq!(updates(&mut limit, 60.0, 60), 60);
+    assert_eq!(updates(&mut limit, 120.0, 120), 120);
+}
+
+#[test]
+fn uneven_frames_keep_the_average_rate() {
+    // 144 Гц не делится на 120: часть кадров пропускается, но в среднем
+    // выходит 120 обновлений в секунду
+    let mut limit = RateLimiter::new(120.0);
+    let count = updates(&mut limit, 144.0, 144 * 10);
+    assert!((1195..=1200).contains(&count), "updates in 10 s: {count}");
+}
+
+#[test]
+fn long_frame_does_not_queue_catch_up_updates() {
+    let mut limit = RateLimiter::new(120.0);
+    assert!(limit.tick(1.0));
+    // После секундной паузы обновления идут в обычном темпе, без очереди
+    assert!(!limit.tick(1.0 / 240.0));
+    assert!(limit.remaining() > 0.0);
+    assert!(limit.tick(1.0 / 240.0));
+}
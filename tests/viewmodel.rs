@@ -0,0 +1,60 @@
+// Автотест: оружие в руках рисуется в нижней части экрана поверх сцены
+// Запуск: cargo test --test viewmodel
+
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::viewmodel::Viewmodel;
+
+const W: u32 = 320;
+const H: u32 = 240;
+
+/// Сколько пикселей нижней трети кадра различаются.
+fn lower_diff(a: &[u8], b: &[u8]) -> usize {
+    let row = (W * 4) as usize;
+    let start = (H as usize * 2 / 3) * row;
+    a[start..]
+        .chunks(4)
+        .zip(b[start..].chunks(4))
+        .filter(|(p, q)| p != q)
+        .count()
+}
+
+#[test]
+fn viewmodel_draws_in_the_lower_screen_region() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(W, H);
+    let mut game = Game::new(GameOptions::default());
+    game.update(&mut engine, 1.0 / 60.0);
+    let cubes = game.cubes();
+
+    engine.renderer.viewmodel.clear();
+    engine.renderer.render(None, 100, &cubes);
+    let bare = engine.renderer.get_frame_rgba8();
+    engine.renderer.viewmodel = game.viewmodel.cubes();
+    engine.renderer.render(None, 100, &cubes);
+    let armed = engine.renderer.get_frame_rgba8();
+
+    let changed = lower_diff(&bare, &armed);
+    assert!(
+        changed > 200,
+        "viewmodel pixels in the lower third: {changed}"
+    );
+    // Верхняя треть кадра не затронута
+    let row = (W * 4) as usize;
+    let top = (H as usize / 3) * row;
+    assert_eq!(bare[..top], armed[..top]);
+}
+
+#[test]
+fn firing_kicks_the_viewmodel_back_and_it_settles() {
+    let mut viewmodel = Viewmodel::new();
+    viewmodel.fire();
+    assert!(viewmodel.offset().z > 0.0);
+    for _ in 0..120 {
+        viewmodel.update((0.0, 0.0), 0.0, 1.0 / 60.0);
+    }
+    assert!(viewmodel.offset().length() < 1e-3);
+
+    viewmodel.update((40.0, 0.0), 0.0, 1.0 / 60.0);
+    assert!(viewmodel.offset().x < 0.0, "sways against the mouse motion");
+}
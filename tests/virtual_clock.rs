@@ -0,0 +1,54 @@
+// Автотест: виртуальные часы двигают время ровно на заданный шаг
+// Запуск: cargo test --test virtual_clock
+
+use astroforge::engine::timer::{RateLimiter, TimeSource, VirtualClock};
+use astroforge::engine::Engine;
+use std::time::Duration;
+
+#[test]
+fn virtual_clock_reports_exact_dt() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    engine.set_time_source(Box::new(VirtualClock::new(Duration::from_millis(20))));
+    for _ in 0..5 {
+        assert!(engine.begin_frame());
+        assert_eq!(engine.dt, 0.02);
+    }
+}
+
+#[test]
+fn virtual_clock_drives_the_update_limit() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    // Кадры по 4 мс при ограничении 125 обновлений в секунду: каждый второй
+    engine.set_time_source(Box::new(VirtualClock::new(Duration::from_millis(4))));
+    engine.update_limit = Some(RateLimiter::new(125.0));
+    let updates: Vec<bool> = (0..6).map(|_| engine.begin_frame()).collect();
+    assert_eq!(updates, [false, true, false, true, false, true]);
+    assert_eq!(engine.dt, 0.008);
+}
+
+#[test]
+fn pause_on_a_virtual_clock_is_left_out() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    engine.set_time_source(Box::new(VirtualClock::new(Duration::from_millis(10))));
+    assert!(engine.begin_frame());
+    engine.pause();
+    // Пока игра стоит на паузе, кадры идут без обновлений
+    for _ in 0..100 {
+        engine.begin_frame();
+    }
+    engine.resume();
+    assert!(engine.begin_frame());
+    assert_eq!(engine.dt, 0.01);
+}
+
+#[test]
+fn advance_moves_time_without_a_frame() {
+    let mut clock = VirtualClock::new(Duration::from_millis(5));
+    clock.advance(Duration::from_millis(7));
+    assert_eq!(clock.now(), Duration::from_millis(7));
+    clock.frame();
+    assert_eq!(clock.now(), Duration::from_millis(12));
+}
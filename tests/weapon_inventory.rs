@@ -0,0 +1,86 @@
+// Автотест: цифры и колесо мыши меняют оружие в руках, выстрел идёт из выбранного оружия
+// Запуск: cargo test --test weapon_inventory
+
+use astroforge::engine::input::{InputFrame, InputState};
+use astroforge::engine::Engine;
+use astroforge::game::{Game, GameOptions};
+use astroforge::weapon::{Inventory, WeaponKind};
+use astroforge::world::Tag;
+use winit::event::VirtualKeyCode;
+
+fn bullets(game: &Game) -> usize {
+    game.world
+        .entities
+        .iter()
+        .filter(|(_, e)| e.tag == Tag::Bullet)
+        .count()
+}
+
+#[test]
+fn number_keys_and_scroll_change_the_active_slot() {
+    let mut inventory = Inventory::default();
+    let mut input = InputState::default();
+    assert_eq!(inventory.active_index(), 0);
+
+    input.apply_frame(&InputFrame::holding(&[VirtualKeyCode::Key3]));
+    inventory.handle_input(&input);
+    assert_eq!(inventory.active_index(), 2);
+    input.reset();
+
+    input.scroll_delta = -1.0;
+    inventory.handle_input(&input);
+    assert_eq!(
+        inventory.active_index(),
+        0,
+        "scrolling down wraps to the first slot"
+    );
+    input.scroll_delta = 1.0;
+    inventory.handle_input(&input);
+    assert_eq!(inventory.active_index(), 2, "scrolling up goes back");
+}
+
+#[test]
+fn switching_delays_the_next_shot() {
+    let mut inventory = Inventory::default();
+    assert!(inventory.ready());
+    assert!(inventory.select(1));
+    assert!(!inventory.ready());
+    inventory.tick(1.0);
+    assert!(inventory.ready());
+    assert!(!inventory.select(1), "already in hand");
+}
+
+#[test]
+fn fire_dispatches_to_the_active_weapon() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(64, 64);
+    let mut game = Game::new(GameOptions::default());
+
+    // Винтовка стреляет лучом — пуль в мире нет
+    assert!(matches!(game.fire(), Some(WeaponKind::Rifle(_))));
+    assert_eq!(bullets(&game), 0);
+
+    engine
+        .input
+        .apply_frame(&InputFrame::holding(&[VirtualKeyCode::Key2]));
+    game.update(&mut engine, 1.0 / 60.0);
+    engine.input.reset();
+    assert_eq!(game.player.inventory.active_index(), 1);
+    assert_eq!(game.fire(), None, "pistol is still being drawn");
+
+    for _ in 0..30 {
+        game.update(&mut engine, 1.0 / 60.0);
+    }
+    let loaded = game.player.inventory.active().unwrap().ammo.loaded;
+    assert!(matches!(game.fire(), Some(WeaponKind::Pistol { .. })));
+    assert_eq!(bullets(&game), 1);
+    assert_eq!(
+        game.player.inventory.active().unwrap().ammo.loaded,
+        loaded - 1
+    );
+    assert_eq!(
+        game.player.inventory.weapons[0].ammo.loaded,
+        game.player.inventory.weapons[0].ammo.magazine_size - 1,
+        "each weapon keeps its own ammo"
+    );
+}
@@ -0,0 +1,55 @@
+// Автотест: разброс после одинаковой очереди за одно и то же время не
+// зависит от шага кадра, растёт при стрельбе и спадает после неё
+// Запуск: cargo test --test weapon_spread
+
+use astroforge::weapon::{Spread, Weapon};
+
+/// Очередь из `shots` выстрелов раз в 0.1 с, затем `rest` секунд тишины,
+/// с шагом кадра 1/`fps`. Возвращает разброс после очереди и в конце.
+fn burst(fps: u32, shots: u32, rest: f32) -> (f32, f32) {
+    let mut weapon = Weapon::rifle();
+    let dt = 1.0 / fps as f32;
+    let frames_per_shot = fps / 10;
+    for frame in 0..shots * frames_per_shot {
+        if frame % frames_per_shot == 0 {
+            weapon.stray();
+        }
+        weapon.spread.tick(dt);
+    }
+    let after_burst = weapon.spread.current();
+    for _ in 0..(rest * fps as f32).round() as u32 {
+        weapon.spread.tick(dt);
+    }
+    (after_burst, weapon.spread.current())
+}
+
+#[test]
+fn spread_is_the_same_at_any_frame_rate() {
+    let slow = burst(30, 3, 0.5);
+    let fast = burst(240, 3, 0.5);
+    assert!(slow.0 > 0.0, "firing did not grow the spread");
+    assert!((slow.0 - fast.0).abs() < 1e-4, "{slow:?} vs {fast:?}");
+    assert!((slow.1 - fast.1).abs() < 1e-4, "{slow:?} vs {fast:?}");
+    assert!(slow.1 < slow.0, "no recovery after the burst");
+}
+
+#[test]
+fn spread_grows_to_its_max_and_recovers_to_base() {
+    let (after_burst, rested) = burst(60, 40, 5.0);
+    let rifle = Weapon::rifle().spread;
+    assert_eq!(after_burst, rifle.max);
+    assert_eq!(rested, rifle.base);
+}
+
+#[test]
+fn first_shot_goes_straight_and_later_ones_stay_in_the_cone() {
+    let mut spread = Spread::new(0.0, 0.1, 1.0, 0.0).with_sustain(1.0);
+    assert_eq!(spread.offset(0), (0.0, 0.0));
+    spread.fire();
+    spread.tick(0.05);
+    assert!((spread.current() - 0.05).abs() < 1e-6);
+    for shot in 1..16 {
+        let (pitch, yaw) = spread.offset(shot);
+        assert!(pitch.hypot(yaw) <= spread.current() + 1e-6);
+    }
+}
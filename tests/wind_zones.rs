@@ -0,0 +1,43 @@
+// Автотест: зона ветра толкает тела внутри неё и не трогает тела снаружи
+// Запуск: cargo test --test wind_zones
+
+use astroforge::engine::physics::{Aabb, Collider, PhysicsObject, PhysicsWorld, RigidBody};
+use glam::Vec3;
+
+#[test]
+fn body_in_wind_zone_gains_velocity_along_the_wind() {
+    let mut world = PhysicsWorld::new();
+    world.add_wind_zone(
+        Aabb::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(2.0, 1.0, 2.0)),
+        Vec3::new(0.0, 0.0, 40.0),
+    );
+    let mut inside = RigidBody::new(2.0, Vec3::new(1.0, 1.0, 0.0));
+    let mut outside = RigidBody::new(2.0, Vec3::new(5.0, 1.0, 0.0));
+    inside.on_ground = true;
+    outside.on_ground = true;
+    let mut objs = vec![
+        PhysicsObject {
+            body: &mut inside,
+            collider: Collider::new(Vec3::splat(0.5)),
+        },
+        PhysicsObject {
+            body: &mut outside,
+            collider: Collider::new(Vec3::splat(0.5)),
+        },
+    ];
+    for _ in 0..10 {
+        world.step(&mut objs, &[], 1.0 / 60.0);
+    }
+
+    assert!(inside.velocity.z > 0.0, "pushed along +Z");
+    assert_eq!(inside.velocity.x, 0.0);
+    assert_eq!(outside.velocity, Vec3::ZERO);
+}
+
+#[test]
+fn partial_overlap_counts() {
+    let zone = Aabb::new(Vec3::ZERO, Vec3::ONE);
+    let collider = Collider::new(Vec3::splat(0.5));
+    assert!(zone.overlaps(&Aabb::around(Vec3::new(1.4, 0.0, 0.0), &collider)));
+    assert!(!zone.overlaps(&Aabb::around(Vec3::new(1.5, 0.0, 0.0), &collider)));
+}
@@ -0,0 +1,53 @@
+// Автотест: каркасный режим включается только при POLYGON_MODE_LINE, а без
+// него сцена по-прежнему рисуется залитой
+// Запуск: cargo test --test wireframe
+
+use astroforge::engine::renderer::CubeInstance;
+use astroforge::engine::Engine;
+use glam::{Mat4, Vec3};
+
+const SIZE: u32 = 200;
+
+/// Доля пикселей цвета куба в кадре.
+fn cube_coverage(frame: &[u8]) -> f32 {
+    let red = frame
+        .chunks(4)
+        .filter(|p| p[0] > 200 && p[1] < 50 && p[2] < 50)
+        .count();
+    red as f32 / (SIZE * SIZE) as f32
+}
+
+#[test]
+fn wireframe_falls_back_to_filled_without_the_feature() {
+    std::env::set_var("WGPU_BACKEND", "gl");
+    let mut engine = Engine::new_headless(SIZE, SIZE);
+    let supported = engine
+        .renderer
+        .device
+        .features()
+        .contains(wgpu::Features::POLYGON_MODE_LINE);
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 5.0, 3.0), Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+    engine.renderer.update_camera(&(proj * view));
+    let cube = CubeInstance::new(Vec3::new(0.0, 5.0, 0.0), 1.0, [1.0, 0.0, 0.0]);
+
+    engine.renderer.render(None, 100, &[cube]);
+    let filled = cube_coverage(&engine.renderer.get_frame_rgba8());
+    assert!(filled > 0.05, "{filled}");
+
+    assert!(!engine.renderer.wireframe());
+    assert_eq!(engine.renderer.set_wireframe(true), supported);
+    assert_eq!(engine.renderer.wireframe(), supported);
+    engine.renderer.render(None, 100, &[cube]);
+    let drawn = cube_coverage(&engine.renderer.get_frame_rgba8());
+    if supported {
+        // Остались только рёбра треугольников
+        assert!(drawn > 0.0 && drawn < filled * 0.5, "{drawn} of {filled}");
+    } else {
+        assert_eq!(drawn, filled);
+    }
+
+    assert!(!engine.renderer.set_wireframe(false));
+    engine.renderer.render(None, 100, &[cube]);
+    assert_eq!(cube_coverage(&engine.renderer.get_frame_rgba8()), filled);
+}